@@ -1,6 +1,7 @@
 /// Committee debate agent definitions — personas, system prompts, and round templates.
 /// Agents are stored as a registry (registry.json) + individual prompt files (.md).
 
+use crate::config::AppConfig;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
@@ -30,6 +31,41 @@ pub struct AgentFileInfo {
     pub content: String,
     pub modified_at: String,
     pub size_bytes: u64,
+    #[serde(default)]
+    pub word_count: usize,
+}
+
+/// A registry agent joined with the model it will actually run on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentWithModel {
+    pub key: String,
+    pub label: String,
+    pub emoji: String,
+    pub color: String,
+    pub role: String,
+    pub voice_gender: String,
+    pub enabled: bool,
+    pub model: String,
+    /// True when `model` comes from an explicit per-agent assignment rather than a fallback.
+    pub model_overridden: bool,
+}
+
+/// What `repair_agents` changed on disk.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct RepairReport {
+    /// registry.json was missing or malformed and has been rewritten.
+    pub registry_rewritten: bool,
+    /// Built-in agents that were missing from the registry and have been restored.
+    pub restored_builtins: Vec<String>,
+    /// Orphaned prompt files re-registered as custom agents (by key).
+    pub added: Vec<String>,
+    /// Prompt files not in the registry that were left alone (by filename stem).
+    /// Pass a stem back in `adopt` to register it as a custom agent.
+    pub unregistered: Vec<String>,
+    /// Files listed in `adopt` that couldn't be registered, as "stem: reason".
+    pub skipped: Vec<String>,
+    /// Prompt files renamed to match their derived agent key, as "old -> new".
+    pub renamed: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -94,7 +130,49 @@ pub fn save_registry(app_data_dir: &PathBuf, agents: &[AgentInfo]) -> Result<(),
     fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
     let registry = AgentRegistry { version: 1, agents: agents.to_vec() };
     let content = serde_json::to_string_pretty(&registry).map_err(|e| e.to_string())?;
-    fs::write(registry_path(app_data_dir), content).map_err(|e| e.to_string())
+    crate::config::write_atomic(&registry_path(app_data_dir), content.as_bytes())
+}
+
+/// Pair each registry agent with its resolved model. Debaters fall back to
+/// `debate_default_model`, then the main model; the moderator honours
+/// `default_moderator_model` instead. Every
+/// registry entry takes part in debates, so `enabled` is always true for now.
+pub fn agents_with_models(registry: &[AgentInfo], config: &AppConfig) -> Vec<AgentWithModel> {
+    registry
+        .iter()
+        .map(|agent| {
+            let explicit = config
+                .agent_models
+                .get(&agent.key)
+                .map(|m| m.trim())
+                .filter(|m| !m.is_empty());
+            let model = if agent.role == "moderator" {
+                crate::debate::resolve_moderator_model(
+                    &config.agent_models,
+                    &config.default_moderator_model,
+                    &config.model,
+                )
+            } else {
+                crate::debate::resolve_debater_model(
+                    &config.agent_models,
+                    &agent.key,
+                    &config.debate_default_model,
+                    &config.model,
+                )
+            };
+            AgentWithModel {
+                key: agent.key.clone(),
+                label: agent.label.clone(),
+                emoji: agent.emoji.clone(),
+                color: agent.color.clone(),
+                role: agent.role.clone(),
+                voice_gender: agent.voice_gender.clone(),
+                enabled: true,
+                model: model.to_string(),
+                model_overridden: explicit.is_some(),
+            }
+        })
+        .collect()
 }
 
 // ── Agent prompt file I/O ──
@@ -162,6 +240,7 @@ pub fn read_all_agent_files(app_data_dir: &PathBuf) -> Result<Vec<AgentFileInfo>
                 .to_string();
             files.push(AgentFileInfo {
                 filename,
+                word_count: crate::profile::word_count(&content),
                 content,
                 modified_at,
                 size_bytes: metadata.len(),
@@ -183,13 +262,61 @@ pub fn read_all_agent_files(app_data_dir: &PathBuf) -> Result<Vec<AgentFileInfo>
 
 /// Write an agent prompt file.
 pub fn write_agent_file(app_data_dir: &PathBuf, filename: &str, content: &str) -> Result<(), String> {
+    crate::profile::validate_md_filename(filename)?;
     let dir = get_agents_dir(app_data_dir);
     fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
-    let path = dir.join(filename);
-    fs::write(&path, content).map_err(|e| e.to_string())?;
+    crate::config::write_atomic(&dir.join(filename), content.as_bytes())
+}
+
+/// Minimum prompt length accepted for a hand-written custom agent.
+const MIN_AGENT_PROMPT_CHARS: usize = 10;
+
+/// Validate user-supplied fields for a custom agent before it's written to disk.
+pub fn validate_custom_agent_input(emoji: &str, prompt: &str) -> Result<(), String> {
+    if emoji.trim().is_empty() {
+        return Err("Pick an emoji for the agent.".to_string());
+    }
+    if prompt.trim().chars().count() < MIN_AGENT_PROMPT_CHARS {
+        return Err(format!(
+            "Agent prompt is too short. Describe the persona in at least {} characters.",
+            MIN_AGENT_PROMPT_CHARS
+        ));
+    }
     Ok(())
 }
 
+/// Generate an agent key from a display label ("The Economist" -> "the_economist").
+fn agent_key_from_label(label: &str) -> String {
+    label
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect::<String>()
+        .trim_matches('_')
+        .to_string()
+}
+
+/// Pick a color that's not already used by another agent.
+fn next_agent_color(registry: &[AgentInfo]) -> String {
+    let used_colors: Vec<&str> = registry.iter().map(|a| a.color.as_str()).collect();
+    let available_colors = ["green", "pink", "cyan", "indigo", "blue", "purple", "red", "teal", "orange"];
+    available_colors
+        .iter()
+        .find(|c| !used_colors.contains(c))
+        .unwrap_or(&"blue")
+        .to_string()
+}
+
+/// Sort order for a new debater: before the moderator, after the last debater.
+fn next_debater_sort_order(registry: &[AgentInfo]) -> u32 {
+    registry.iter()
+        .filter(|a| a.role == "debater")
+        .map(|a| a.sort_order)
+        .max()
+        .unwrap_or(4)
+        + 1
+}
+
 /// Add a custom agent to the registry and write its prompt file.
 pub fn create_custom_agent(
     app_data_dir: &PathBuf,
@@ -200,15 +327,7 @@ pub fn create_custom_agent(
 ) -> Result<AgentInfo, String> {
     let mut registry = load_registry(app_data_dir);
 
-    // Generate key from label
-    let key = label
-        .to_lowercase()
-        .chars()
-        .map(|c| if c.is_alphanumeric() { c } else { '_' })
-        .collect::<String>()
-        .trim_matches('_')
-        .to_string();
-
+    let key = agent_key_from_label(label);
     if key.is_empty() {
         return Err("Agent name must contain at least one alphanumeric character".to_string());
     }
@@ -218,30 +337,14 @@ pub fn create_custom_agent(
         return Err(format!("An agent with key '{}' already exists", key));
     }
 
-    // Pick a color that's not heavily used
-    let used_colors: Vec<&str> = registry.iter().map(|a| a.color.as_str()).collect();
-    let available_colors = ["green", "pink", "cyan", "indigo", "blue", "purple", "red", "teal", "orange"];
-    let color = available_colors
-        .iter()
-        .find(|c| !used_colors.contains(c))
-        .unwrap_or(&"blue")
-        .to_string();
-
-    // Determine sort order (before moderator, after last debater)
-    let max_debater_order = registry.iter()
-        .filter(|a| a.role == "debater")
-        .map(|a| a.sort_order)
-        .max()
-        .unwrap_or(4);
-
     let agent = AgentInfo {
         key: key.clone(),
         label: label.to_string(),
         emoji: emoji.to_string(),
-        color,
+        color: next_agent_color(&registry),
         role: "debater".to_string(),
         builtin: false,
-        sort_order: max_debater_order + 1,
+        sort_order: next_debater_sort_order(&registry),
         voice_gender: voice_gender.to_string(),
     };
 
@@ -280,6 +383,138 @@ pub fn delete_custom_agent(app_data_dir: &PathBuf, agent_key: &str) -> Result<()
     Ok(())
 }
 
+/// Return the effective prompt for an agent in the registry.
+pub fn get_agent_prompt(app_data_dir: &PathBuf, agent_key: &str) -> Result<String, String> {
+    let registry = load_registry(app_data_dir);
+    if !registry.iter().any(|a| a.key == agent_key) {
+        return Err(format!("Agent '{}' not found", agent_key));
+    }
+    Ok(read_agent_prompt(app_data_dir, agent_key))
+}
+
+/// Overwrite a built-in agent's prompt file with its hardcoded default.
+pub fn reset_agent_prompt(app_data_dir: &PathBuf, agent_key: &str) -> Result<String, String> {
+    let registry = load_registry(app_data_dir);
+
+    let agent = registry.iter()
+        .find(|a| a.key == agent_key)
+        .ok_or_else(|| format!("Agent '{}' not found", agent_key))?;
+
+    if !agent.builtin {
+        return Err("Only built-in agents can be reset".to_string());
+    }
+
+    let default = default_prompt_for_key(agent_key)
+        .ok_or_else(|| format!("No default prompt for agent '{}'", agent_key))?;
+    write_agent_file(app_data_dir, &format!("{}.md", agent_key), default)?;
+    Ok(default.to_string())
+}
+
+/// Turn a prompt filename stem into a display label ("market_skeptic" -> "Market Skeptic").
+fn label_from_file_stem(stem: &str) -> String {
+    stem.split(['_', '-', ' '])
+        .filter(|w| !w.is_empty())
+        .map(|w| {
+            let mut chars = w.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Rebuild the registry from what's on disk. A malformed registry.json is replaced
+/// with the built-ins and missing built-ins are restored. `.md` prompt files not in
+/// the registry are only reported, unless their stem is listed in `adopt` (the user
+/// confirmed them), in which case they're registered as custom debaters.
+pub fn repair_agents(app_data_dir: &PathBuf, adopt: &[String]) -> Result<RepairReport, String> {
+    let dir = get_agents_dir(app_data_dir);
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let mut report = RepairReport::default();
+
+    // Read the file directly — load_registry would silently reseed and hide the damage
+    let mut registry = match fs::read_to_string(registry_path(app_data_dir)) {
+        Ok(content) => match serde_json::from_str::<AgentRegistry>(&content) {
+            Ok(parsed) => parsed.agents,
+            Err(_) => {
+                report.registry_rewritten = true;
+                Vec::new()
+            }
+        },
+        Err(_) => {
+            report.registry_rewritten = true;
+            Vec::new()
+        }
+    };
+
+    for builtin in builtin_agents() {
+        if !registry.iter().any(|a| a.key == builtin.key) {
+            // A fresh registry gets the built-ins silently; only report real gaps
+            if !report.registry_rewritten {
+                report.restored_builtins.push(builtin.key.clone());
+            }
+            registry.push(builtin);
+        }
+    }
+
+    let mut stems: Vec<String> = fs::read_dir(&dir)
+        .map_err(|e| e.to_string())?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().and_then(|e| e.to_str()) == Some("md"))
+        .filter_map(|path| path.file_stem().map(|s| s.to_string_lossy().to_string()))
+        .collect();
+    stems.sort();
+
+    for stem in stems {
+        if registry.iter().any(|a| a.key == stem) {
+            continue;
+        }
+        if !adopt.contains(&stem) {
+            report.unregistered.push(stem);
+            continue;
+        }
+        let key = agent_key_from_label(&stem);
+        if key.is_empty() {
+            report.skipped.push(format!("{}: no usable agent key in the filename", stem));
+            continue;
+        }
+        if registry.iter().any(|a| a.key == key) {
+            report.skipped.push(format!("{}: agent '{}' already exists", stem, key));
+            continue;
+        }
+        if key != stem {
+            let target = dir.join(format!("{}.md", key));
+            if target.exists() {
+                report.skipped.push(format!("{}: {}.md already exists", stem, key));
+                continue;
+            }
+            fs::rename(dir.join(format!("{}.md", stem)), &target).map_err(|e| e.to_string())?;
+            report.renamed.push(format!("{}.md -> {}.md", stem, key));
+        }
+
+        let agent = AgentInfo {
+            key: key.clone(),
+            label: label_from_file_stem(&stem),
+            emoji: "\u{1f464}".to_string(),
+            color: next_agent_color(&registry),
+            role: "debater".to_string(),
+            builtin: false,
+            sort_order: next_debater_sort_order(&registry),
+            voice_gender: default_voice_gender(),
+        };
+        registry.push(agent);
+        report.added.push(key);
+    }
+
+    if report.registry_rewritten || !report.restored_builtins.is_empty() || !report.added.is_empty() {
+        registry.sort_by_key(|a| a.sort_order);
+        save_registry(app_data_dir, &registry)?;
+    }
+    Ok(report)
+}
+
 // ── Prompt constants ──
 
 pub const RATIONALIST_PROMPT: &str = r#"You are The Rationalist on a decision-making committee. You analyze decisions through pure logic, expected value calculations, and probabilistic thinking. You strip away emotion and look at what the numbers say.
@@ -414,7 +649,29 @@ Style constraints:
     )
 }
 
-pub fn round2_prompt(brief: &str, transcript: &str, exchange: i32) -> String {
+/// Round 2 prompt. With a single debater (`solo`) there is no one to rebut, so the
+/// speaker stress-tests their own opening instead.
+pub fn round2_prompt(brief: &str, transcript: &str, exchange: i32, solo: bool) -> String {
+    if solo {
+        let opening = if exchange == 1 { "Here is your Round 1 opening:\n\n" } else { "" };
+        return format!(
+            r#"{brief}
+
+{opening}{transcript}
+
+You are the only committee member taking part, so there is no one else to answer. Stress-test your own view instead.
+
+Rules:
+- Name the strongest objection someone could raise and answer it
+- Be explicit about whether your position changed
+- Do not address or invent other members
+
+Style constraints:
+- Natural spoken language
+- No markdown, no bullets, no section headers
+- 2-5 sentences, under 120 words"#
+        );
+    }
     if exchange == 1 {
         format!(
             r#"{brief}
@@ -456,7 +713,12 @@ Style constraints:
     }
 }
 
-pub fn round3_prompt(brief: &str, transcript: &str) -> String {
+pub fn round3_prompt(brief: &str, transcript: &str, solo: bool) -> String {
+    let doubt = if solo {
+        "the objection you weighed most seriously"
+    } else {
+        "what almost changed your mind"
+    };
     format!(
         r#"{brief}
 
@@ -466,7 +728,7 @@ Final statement. Make your closing call as spoken dialogue.
 
 Include naturally:
 - your final vote
-- what almost changed your mind
+- {doubt}
 - the one action this person should take next
 
 Style constraints:
@@ -476,11 +738,19 @@ Style constraints:
     )
 }
 
-pub fn moderator_prompt(brief: &str, transcript: &str, participants: &str) -> String {
+pub fn moderator_prompt(brief: &str, transcript: &str, debaters: &[AgentInfo]) -> String {
+    let participants = format_participant_names(debaters);
+    let participants = if debaters.len() == 1 {
+        format!(
+            "Only {participants} took part, so this was a single perspective rather than a debate. Under Key Disagreements, give the strongest objections to that view instead."
+        )
+    } else {
+        format!("The following committee members participated in this debate: {participants}")
+    };
     format!(
         r#"{brief}
 
-The following committee members participated in this debate: {participants}
+{participants}
 
 Here is the full committee debate:
 
@@ -502,6 +772,9 @@ Synthesize this debate into a clear recommendation. Structure your response as:
 **Confidence**: [High/Medium/Low]
 **Reasoning**: [Why this is the right call, weighing the debate]
 
+## Minority View
+[If members disagreed with the recommendation, who they were and the strongest case they made. Write "None" if the committee was unanimous.]
+
 ## What You're Giving Up
 [Explicit tradeoffs of the recommended choice]
 
@@ -593,6 +866,41 @@ mod tests {
     use super::*;
     use tempfile::tempdir;
 
+    #[test]
+    fn unit_agents_with_models_joins_overrides_and_falls_back_to_default_model() {
+        let mut config = AppConfig {
+            model: "anthropic/claude-sonnet-4-5".to_string(),
+            ..Default::default()
+        };
+        config.agent_models.insert("contrarian".to_string(), "openai/gpt-4o".to_string());
+        config.agent_models.insert("advocate".to_string(), "  ".to_string());
+
+        let agents = agents_with_models(&builtin_agents(), &config);
+        assert_eq!(agents.len(), 6);
+        let find = |key: &str| agents.iter().find(|a| a.key == key).unwrap();
+
+        assert_eq!(find("contrarian").model, "openai/gpt-4o");
+        assert!(find("contrarian").model_overridden);
+        assert_eq!(find("advocate").model, "anthropic/claude-sonnet-4-5");
+        assert!(!find("advocate").model_overridden);
+        assert_eq!(find("rationalist").model, "anthropic/claude-sonnet-4-5");
+        assert_eq!(find("advocate").voice_gender, "female");
+        assert!(agents.iter().all(|a| a.enabled));
+
+        assert_eq!(find("moderator").model, "anthropic/claude-sonnet-4-5");
+        config.debate_default_model = "openai/gpt-4o-mini".to_string();
+        let agents = agents_with_models(&builtin_agents(), &config);
+        let find = |key: &str| agents.iter().find(|a| a.key == key).unwrap();
+        assert_eq!(find("advocate").model, "openai/gpt-4o-mini");
+        assert_eq!(find("contrarian").model, "openai/gpt-4o");
+        assert_eq!(find("moderator").model, "anthropic/claude-sonnet-4-5");
+        config.default_moderator_model = "anthropic/claude-opus-4".to_string();
+        let agents = agents_with_models(&builtin_agents(), &config);
+        let moderator = agents.iter().find(|a| a.key == "moderator").unwrap();
+        assert_eq!(moderator.model, "anthropic/claude-opus-4");
+        assert!(!moderator.model_overridden);
+    }
+
     #[test]
     fn unit_builtin_agents_has_expected_keys_and_labels() {
         let agents = builtin_agents();
@@ -624,6 +932,32 @@ mod tests {
         assert!(default_prompt_for_key("unknown").is_none());
     }
 
+    #[test]
+    fn unit_moderator_prompt_special_cases_a_single_debater() {
+        let debaters: Vec<AgentInfo> = builtin_agents().into_iter().filter(|a| a.role == "debater").collect();
+
+        let committee = moderator_prompt("brief", "transcript", &debaters[..2]);
+        assert!(committee.contains("participated in this debate: The Rationalist and The Advocate"));
+
+        let solo = moderator_prompt("brief", "transcript", &debaters[..1]);
+        assert!(solo.contains("Only The Rationalist took part"));
+        assert!(!solo.contains("participated in this debate"));
+    }
+
+    #[test]
+    fn unit_rebuttal_prompts_special_case_a_single_debater() {
+        let committee = round2_prompt("brief", "transcript", 1, false);
+        assert!(committee.contains("Address at least one specific other member"));
+
+        let solo = round2_prompt("brief", "transcript", 1, true);
+        assert!(solo.contains("only committee member"));
+        assert!(!solo.contains("other member by name"));
+        assert!(round2_prompt("brief", "transcript", 2, true).contains("Stress-test your own view"));
+
+        assert!(round3_prompt("brief", "transcript", false).contains("what almost changed your mind"));
+        assert!(round3_prompt("brief", "transcript", true).contains("the objection you weighed most seriously"));
+    }
+
     #[test]
     fn unit_format_participant_names_handles_various_counts() {
         let agents = builtin_agents();
@@ -656,6 +990,12 @@ mod tests {
         assert_eq!(files.len(), 6);
         assert_eq!(files[0].filename, "rationalist.md");
         assert_eq!(files[5].filename, "moderator.md");
+        assert_eq!(files[0].word_count, RATIONALIST_PROMPT.split_whitespace().count());
+
+        write_agent_file(&app_data_dir, "rationalist.md", "  Weigh the odds,\n\tthen decide.  ")
+            .expect("agent file should write");
+        let files = read_all_agent_files(&app_data_dir).expect("agent files should load");
+        assert_eq!(files[0].word_count, 5);
     }
 
     #[test]
@@ -691,6 +1031,91 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn integration_repair_agents_reregisters_orphans_and_rewrites_corrupt_registry() {
+        let dir = tempdir().expect("temp directory should exist");
+        let app_data_dir = dir.path().to_path_buf();
+        init_agent_files(&app_data_dir).expect("agent files should initialize");
+
+        let agents_dir = get_agents_dir(&app_data_dir);
+        fs::write(agents_dir.join("economist.md"), "You are The Economist.").unwrap();
+        fs::write(agents_dir.join("market-skeptic.md"), "You doubt the market.").unwrap();
+
+        // Unknown files are reported but left alone until the user adopts them
+        let report = repair_agents(&app_data_dir, &[]).expect("repair should succeed");
+        assert!(report.added.is_empty() && report.renamed.is_empty());
+        assert_eq!(report.unregistered, vec!["economist", "market-skeptic"]);
+        assert!(agents_dir.join("market-skeptic.md").exists());
+        assert!(!load_registry(&app_data_dir).iter().any(|a| a.key == "economist"));
+
+        let adopt = vec!["economist".to_string(), "market-skeptic".to_string()];
+        let report = repair_agents(&app_data_dir, &adopt).expect("repair should succeed");
+        assert!(!report.registry_rewritten);
+        assert!(report.restored_builtins.is_empty());
+        assert!(report.unregistered.is_empty());
+        assert_eq!(report.added, vec!["economist", "market_skeptic"]);
+        assert_eq!(report.renamed, vec!["market-skeptic.md -> market_skeptic.md"]);
+
+        let registry = load_registry(&app_data_dir);
+        let economist = registry.iter().find(|a| a.key == "economist").expect("economist registered");
+        assert_eq!(economist.label, "Economist");
+        assert_eq!(economist.role, "debater");
+        assert!(!economist.builtin);
+        assert!(economist.sort_order < 100);
+        assert_eq!(registry.last().unwrap().key, "moderator");
+        assert_eq!(read_agent_prompt(&app_data_dir, "market_skeptic"), "You doubt the market.");
+
+        // Running again is a no-op
+        let again = repair_agents(&app_data_dir, &[]).unwrap();
+        assert!(again.added.is_empty() && again.renamed.is_empty() && !again.registry_rewritten);
+        assert!(again.unregistered.is_empty());
+
+        // A hand-broken registry is rewritten; custom agents come back once adopted
+        fs::write(registry_path(&app_data_dir), "{ not json").unwrap();
+        let report = repair_agents(&app_data_dir, &[]).unwrap();
+        assert!(report.registry_rewritten);
+        assert_eq!(report.unregistered, vec!["economist", "market_skeptic"]);
+        let adopt = vec!["economist".to_string(), "market_skeptic".to_string()];
+        let report = repair_agents(&app_data_dir, &adopt).unwrap();
+        assert_eq!(report.added, vec!["economist", "market_skeptic"]);
+        assert_eq!(load_registry(&app_data_dir).len(), 8);
+    }
+
+    #[test]
+    fn integration_repair_agents_reports_adopted_files_it_cannot_register() {
+        let dir = tempdir().expect("temp directory should exist");
+        let app_data_dir = dir.path().to_path_buf();
+        init_agent_files(&app_data_dir).expect("agent files should initialize");
+
+        // "market-skeptic" would be renamed onto a file that is already there
+        let agents_dir = get_agents_dir(&app_data_dir);
+        fs::write(agents_dir.join("market-skeptic.md"), "You doubt the market.").unwrap();
+        fs::write(agents_dir.join("market_skeptic.md"), "You doubt it harder.").unwrap();
+        fs::write(agents_dir.join("___.md"), "No name at all.").unwrap();
+
+        let adopt: Vec<String> = ["market-skeptic", "market_skeptic", "___"].iter().map(|s| s.to_string()).collect();
+        let report = repair_agents(&app_data_dir, &adopt).expect("repair should succeed");
+        assert_eq!(report.added, vec!["market_skeptic"]);
+        assert!(report.renamed.is_empty());
+        assert_eq!(
+            report.skipped,
+            vec![
+                "___: no usable agent key in the filename",
+                "market-skeptic: market_skeptic.md already exists",
+            ]
+        );
+        assert!(agents_dir.join("market-skeptic.md").exists(), "skipped file is left alone");
+        assert_eq!(read_agent_prompt(&app_data_dir, "market_skeptic"), "You doubt it harder.");
+    }
+
+    #[test]
+    fn unit_validate_custom_agent_input_rejects_missing_emoji_and_short_prompt() {
+        assert!(validate_custom_agent_input("", "A perfectly fine prompt").is_err());
+        assert!(validate_custom_agent_input("  ", "A perfectly fine prompt").is_err());
+        assert!(validate_custom_agent_input("\u{1f4b0}", "short").is_err());
+        assert!(validate_custom_agent_input("\u{1f4b0}", "You are The Economist.").is_ok());
+    }
+
     #[test]
     fn integration_read_agent_prompt_with_override() {
         let dir = tempdir().expect("temp directory should exist");
@@ -703,4 +1128,48 @@ mod tests {
         let custom_prompt = read_agent_prompt(&app_data_dir, "rationalist");
         assert_eq!(custom_prompt, "custom prompt");
     }
+
+    #[test]
+    fn integration_reset_agent_prompt_restores_builtin_default() {
+        let dir = tempdir().expect("temp directory should exist");
+        let app_data_dir = dir.path().to_path_buf();
+
+        init_agent_files(&app_data_dir).expect("agent files should initialize");
+        write_agent_file(&app_data_dir, "rationalist.md", "custom prompt")
+            .expect("agent file should write");
+        assert_eq!(
+            get_agent_prompt(&app_data_dir, "rationalist").expect("prompt should load"),
+            "custom prompt"
+        );
+
+        let restored = reset_agent_prompt(&app_data_dir, "rationalist").expect("reset should work");
+        assert_eq!(restored, RATIONALIST_PROMPT);
+        assert_eq!(read_agent_prompt(&app_data_dir, "rationalist"), RATIONALIST_PROMPT);
+
+        create_custom_agent(&app_data_dir, "Market Skeptic", "\u{1f9d0}", "Custom prompt", "male")
+            .expect("custom agent should be created");
+        assert_eq!(
+            reset_agent_prompt(&app_data_dir, "market_skeptic").unwrap_err(),
+            "Only built-in agents can be reset"
+        );
+        assert_eq!(
+            get_agent_prompt(&app_data_dir, "missing").unwrap_err(),
+            "Agent 'missing' not found"
+        );
+    }
+
+    #[test]
+    fn unit_write_agent_file_rejects_traversal_and_non_markdown() {
+        let dir = tempdir().expect("temp directory should exist");
+        let app_data_dir = dir.path().join("app");
+
+        for bad in ["../evil.md", "sub/dir.md", "../config.json", "registry.json"] {
+            assert_eq!(
+                write_agent_file(&app_data_dir, bad, "x").unwrap_err(),
+                "Invalid filename"
+            );
+        }
+        assert!(!dir.path().join("evil.md").exists());
+        assert!(!app_data_dir.join("config.json").exists());
+    }
 }
@@ -1,10 +1,11 @@
 use crate::agents;
 use crate::config::{self, AppConfig};
-use crate::db::{Database, DebateAudio, DebateRound, Decision};
+use crate::db::{self, Database, DebateAudio, DebateRound, Decision};
 use crate::debate;
 use crate::llm;
 use crate::profile;
 use crate::profile::ProfileFileInfo;
+use crate::templates;
 use crate::llm::StreamEvent;
 use crate::tts;
 use serde::{Deserialize, Serialize};
@@ -21,6 +22,8 @@ pub struct AppState {
     pub db: Database,
     pub app_data_dir: PathBuf,
     pub debate_cancel_flags: HashMap<String, Arc<AtomicBool>>,
+    pub model_list_cache: Option<llm::ModelListCache>,
+    pub live_debate_buffers: llm::LiveBuffers,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -33,12 +36,25 @@ pub struct SendMessageResponse {
 pub struct SettingsResponse {
     pub api_key_set: bool,
     pub api_key_preview: String,
+    pub llm_provider: String,
+    pub openai_api_key_set: bool,
+    pub openai_api_key_preview: String,
+    pub ollama_base_url: String,
     pub model: String,
     pub agent_models: std::collections::HashMap<String, String>,
+    pub default_moderator_model: String,
+    pub debate_default_model: String,
     pub elevenlabs_api_key_set: bool,
     pub elevenlabs_api_key_preview: String,
     pub tts_provider: String,
+    pub tts_mode: String,
     pub elevenlabs_model: String,
+    pub chat_max_tokens: u32,
+    pub debate_max_tokens: u32,
+    pub profile_versioning: bool,
+    pub request_timeout_secs: u64,
+    pub debate_temperature: f32,
+    pub min_debaters: usize,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -47,14 +63,17 @@ pub struct CreateDecisionResponse {
     pub decision_id: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct OpenRouterModelInfo {
-    pub id: String,
-    pub name: String,
-    pub context_length: Option<u32>,
-    pub prompt_price_per_million: Option<f64>,
-    pub completion_price_per_million: Option<f64>,
-    pub is_free: bool,
+#[derive(Debug, Serialize, Deserialize)]
+pub struct KeyHealth {
+    pub openrouter: llm::ServiceHealth,
+    pub elevenlabs: llm::ServiceHealth,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DecisionWithTags {
+    #[serde(flatten)]
+    pub decision: Decision,
+    pub tags: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -63,31 +82,29 @@ struct StandaloneSandboxMeta {
     model_map: HashMap<String, String>,
 }
 
-#[derive(Debug, Deserialize)]
-struct OpenRouterModelsResponse {
-    data: Vec<OpenRouterModelEntry>,
-}
-
-#[derive(Debug, Deserialize)]
-struct OpenRouterModelEntry {
-    id: String,
-    name: Option<String>,
-    context_length: Option<u32>,
-    pricing: Option<OpenRouterModelPricing>,
-}
-
-#[derive(Debug, Deserialize)]
-struct OpenRouterModelPricing {
-    prompt: Option<String>,
-    completion: Option<String>,
-}
-
 fn db_err(e: rusqlite::Error) -> String {
     e.to_string()
 }
 
-fn parse_price(value: Option<&str>) -> Option<f64> {
-    value.and_then(|raw| raw.parse::<f64>().ok()).map(|per_token| per_token * 1_000_000.0)
+fn key_preview(key: &str) -> String {
+    if key.len() > 8 {
+        format!("{}...{}", &key[..4], &key[key.len()-4..])
+    } else if !key.is_empty() {
+        "****".to_string()
+    } else {
+        String::new()
+    }
+}
+
+fn normalize_tag(tag: &str) -> Result<String, String> {
+    let tag = tag.trim().to_lowercase();
+    if tag.is_empty() {
+        return Err("Tag cannot be empty".to_string());
+    }
+    if tag.chars().count() > 40 {
+        return Err("Tag must be 40 characters or fewer".to_string());
+    }
+    Ok(tag)
 }
 
 fn short_model_label(model_id: &str) -> String {
@@ -175,13 +192,10 @@ pub async fn send_message(
     message: String,
     on_event: Channel<StreamEvent>,
 ) -> Result<SendMessageResponse, String> {
-    let (api_key, model, conv_id, history_messages, conv_type, decision_id) = {
+    let conv_id = {
         let state = state.lock().map_err(|e| e.to_string())?;
         let config = config::load_config(&state.app_data_dir);
-
-        if config.openrouter_api_key.is_empty() {
-            return Err("API key not set. Please go to Settings to add your OpenRouter API key.".to_string());
-        }
+        llm::LlmEndpoint::from_config(&config).require_api_key()?;
 
         let conv_id = match conversation_id {
             Some(id) => id,
@@ -197,8 +211,175 @@ pub async fn send_message(
         };
 
         state.db.add_message(&conv_id, "user", &message).map_err(db_err)?;
+        conv_id
+    };
+
+    let response_text = run_assistant_turn(&app_handle, &state, &conv_id, &on_event, llm::CHAT_TEMPERATURE).await?;
+
+    Ok(SendMessageResponse {
+        conversation_id: conv_id,
+        response: response_text,
+    })
+}
+
+/// Replace the latest assistant reply with a fresh one generated from the same history.
+#[tauri::command]
+pub async fn regenerate_last_response(
+    app_handle: tauri::AppHandle,
+    state: State<'_, Mutex<AppState>>,
+    conversation_id: String,
+    on_event: Channel<StreamEvent>,
+) -> Result<SendMessageResponse, String> {
+    const NOTHING_TO_REGENERATE: &str = "The last message is not an assistant reply, so there is nothing to regenerate.";
+    {
+        let state = state.lock().map_err(|e| e.to_string())?;
+        let config = config::load_config(&state.app_data_dir);
+        llm::LlmEndpoint::from_config(&config).require_api_key()?;
+
+        let last = state.db.get_last_message(&conversation_id).map_err(db_err)?;
+        if last.map(|m| m.role != "assistant").unwrap_or(true) {
+            return Err(NOTHING_TO_REGENERATE.into());
+        }
+    }
+
+    // The old reply stays in place until the new one has been generated
+    let response_text = generate_assistant_reply(
+        &app_handle,
+        &state,
+        &conversation_id,
+        &on_event,
+        llm::REGENERATE_TEMPERATURE,
+        ReplyHistory::WithoutLastReply,
+    ).await?;
+
+    {
+        let state = state.lock().map_err(|e| e.to_string())?;
+        if state.db.replace_last_message(&conversation_id, "assistant", &response_text).map_err(db_err)?.is_none() {
+            return Err(NOTHING_TO_REGENERATE.into());
+        }
+    }
+
+    Ok(SendMessageResponse {
+        conversation_id,
+        response: response_text,
+    })
+}
+
+/// Rewrite one of the user's earlier messages, drop everything after it, and
+/// generate a fresh reply from that point.
+#[tauri::command]
+pub async fn edit_message(
+    app_handle: tauri::AppHandle,
+    state: State<'_, Mutex<AppState>>,
+    message_id: String,
+    new_content: String,
+    on_event: Channel<StreamEvent>,
+) -> Result<SendMessageResponse, String> {
+    if new_content.trim().is_empty() {
+        return Err("Message cannot be empty".to_string());
+    }
+
+    let conversation_id = {
+        let state = state.lock().map_err(|e| e.to_string())?;
+        let config = config::load_config(&state.app_data_dir);
+        llm::LlmEndpoint::from_config(&config).require_api_key()?;
+
+        let message = state
+            .db
+            .get_message(&message_id)
+            .map_err(db_err)?
+            .ok_or_else(|| "Message not found".to_string())?;
+        if message.role != "user" {
+            return Err("Only your own messages can be edited".to_string());
+        }
+        message.conversation_id
+    };
+
+    // Nothing is rewritten or dropped until the new reply has been generated
+    let response_text = generate_assistant_reply(
+        &app_handle,
+        &state,
+        &conversation_id,
+        &on_event,
+        llm::CHAT_TEMPERATURE,
+        ReplyHistory::EditedAt { message_id: &message_id, content: &new_content },
+    ).await?;
+
+    {
+        let state = state.lock().map_err(|e| e.to_string())?;
+        state.db.rewrite_message_with_reply(&message_id, &new_content, &response_text)
+            .map_err(db_err)?
+            .ok_or_else(|| "Message not found".to_string())?;
+    }
+
+    Ok(SendMessageResponse {
+        conversation_id,
+        response: response_text,
+    })
+}
+
+/// Send the conversation's stored history to the model and persist the assistant reply.
+/// The caller is responsible for having saved the latest user message first.
+async fn run_assistant_turn(
+    app_handle: &tauri::AppHandle,
+    state: &State<'_, Mutex<AppState>>,
+    conv_id: &str,
+    on_event: &Channel<StreamEvent>,
+    temperature: f64,
+) -> Result<String, String> {
+    let response_text = generate_assistant_reply(
+        app_handle, state, conv_id, on_event, temperature, ReplyHistory::Stored,
+    ).await?;
+
+    {
+        let state = state.lock().map_err(|e| e.to_string())?;
+        state.db.add_message(conv_id, "assistant", &response_text).map_err(db_err)?;
+    }
+
+    Ok(response_text)
+}
+
+/// Which view of the stored conversation a reply is generated from. The
+/// database is left untouched; callers persist their change once the reply exists.
+enum ReplyHistory<'a> {
+    /// Every stored message.
+    Stored,
+    /// Everything except the trailing assistant reply that is being regenerated.
+    WithoutLastReply,
+    /// Messages up to `message_id`, with that message's text swapped for `content`.
+    EditedAt { message_id: &'a str, content: &'a str },
+}
 
-        let messages = state.db.get_messages(&conv_id).map_err(db_err)?;
+/// Generate an assistant reply from the conversation's history without saving it.
+async fn generate_assistant_reply(
+    app_handle: &tauri::AppHandle,
+    state: &State<'_, Mutex<AppState>>,
+    conv_id: &str,
+    on_event: &Channel<StreamEvent>,
+    temperature: f64,
+    history: ReplyHistory<'_>,
+) -> Result<String, String> {
+    let (endpoint, model, max_tokens, context_token_budget, history_messages, conv_type, decision_id, prompt_override, app_data_dir) = {
+        let state = state.lock().map_err(|e| e.to_string())?;
+        let config = config::load_config(&state.app_data_dir);
+        let endpoint = llm::LlmEndpoint::from_config(&config);
+        endpoint.require_api_key()?;
+
+        let mut messages = state.db.get_messages(conv_id).map_err(db_err)?;
+        match history {
+            ReplyHistory::Stored => {}
+            ReplyHistory::WithoutLastReply => {
+                if messages.last().map(|m| m.role == "assistant").unwrap_or(false) {
+                    messages.pop();
+                }
+            }
+            ReplyHistory::EditedAt { message_id, content } => {
+                if let Some(pos) = messages.iter().position(|m| m.id == message_id) {
+                    messages.truncate(pos + 1);
+                    messages[pos].content = content.to_string();
+                }
+            }
+        }
         let history: Vec<serde_json::Value> = messages.iter().map(|m| {
             json!({
                 "role": m.role,
@@ -206,45 +387,65 @@ pub async fn send_message(
             })
         }).collect();
 
-        let conv = state.db.get_conversation(&conv_id).map_err(db_err)?;
+        let conv = state.db.get_conversation(conv_id).map_err(db_err)?;
         let conv_type = conv.map(|c| c.conv_type).unwrap_or_else(|| "chat".to_string());
 
         let decision_id = if conv_type == "decision" {
-            state.db.get_decision_by_conversation(&conv_id)
+            state.db.get_decision_by_conversation(conv_id)
                 .map_err(db_err)?
                 .map(|d| d.id)
         } else {
             None
         };
 
-        (config.openrouter_api_key, config.model, conv_id, history, conv_type, decision_id)
+        let prompt_override = state.db.get_conversation_system_prompt(conv_id).map_err(db_err)?;
+
+        (
+            endpoint,
+            config.model,
+            config.chat_max_tokens,
+            config.context_token_budget,
+            history,
+            conv_type,
+            decision_id,
+            prompt_override,
+            state.app_data_dir.clone(),
+        )
     };
 
-    let app_data_dir = {
-        let state = state.lock().map_err(|e| e.to_string())?;
-        state.app_data_dir.clone()
+    let settings = llm::ChatSettings {
+        endpoint: &endpoint,
+        model: &model,
+        max_tokens,
+        temperature,
+        system_prompt_override: prompt_override.as_deref(),
+        context_token_budget,
     };
-
-    let response_text = llm::send_message(
-        &api_key,
-        &model,
+    llm::send_message(
+        &settings,
         history_messages,
         &app_data_dir,
-        &on_event,
+        on_event,
         &conv_type,
         decision_id.as_deref(),
-        &app_handle,
-    ).await?;
+        app_handle,
+    ).await
+}
 
-    {
-        let state = state.lock().map_err(|e| e.to_string())?;
-        state.db.add_message(&conv_id, "assistant", &response_text).map_err(db_err)?;
+/// Extra instructions for the assistant in one conversation (e.g. "be extremely terse").
+/// An empty prompt clears the override.
+#[tauri::command]
+pub fn set_conversation_system_prompt(
+    state: State<'_, Mutex<AppState>>,
+    conversation_id: String,
+    prompt: Option<String>,
+) -> Result<(), String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    let prompt = prompt.as_deref().map(str::trim).filter(|p| !p.is_empty());
+    if !state.db.set_conversation_system_prompt(&conversation_id, prompt).map_err(db_err)? {
+        return Err("Conversation not found".to_string());
     }
-
-    Ok(SendMessageResponse {
-        conversation_id: conv_id,
-        response: response_text,
-    })
+    Ok(())
 }
 
 #[tauri::command]
@@ -254,48 +455,205 @@ pub fn get_conversations(state: State<'_, Mutex<AppState>>) -> Result<Vec<crate:
 }
 
 #[tauri::command]
-pub fn get_messages(state: State<'_, Mutex<AppState>>, conversation_id: String) -> Result<Vec<crate::db::Message>, String> {
+pub fn get_conversation(state: State<'_, Mutex<AppState>>, conversation_id: String) -> Result<Option<crate::db::Conversation>, String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    state.db.get_conversation(&conversation_id).map_err(db_err)
+}
+
+/// Page size used when the caller pages with `before` but gives no `limit`.
+const DEFAULT_MESSAGE_PAGE_SIZE: u32 = 50;
+
+/// All messages, or with `limit`/`before` the most recent page older than `before`.
+#[tauri::command]
+pub fn get_messages(
+    state: State<'_, Mutex<AppState>>,
+    conversation_id: String,
+    limit: Option<u32>,
+    before: Option<String>,
+) -> Result<Vec<crate::db::Message>, String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    if limit.is_none() && before.is_none() {
+        return state.db.get_messages(&conversation_id).map_err(db_err);
+    }
+    state
+        .db
+        .get_messages_page(&conversation_id, limit.unwrap_or(DEFAULT_MESSAGE_PAGE_SIZE), before.as_deref())
+        .map_err(db_err)?
+        .ok_or_else(|| "Message cursor not found in this conversation".to_string())
+}
+
+/// Serialize the whole database into a versioned JSON bundle for backup.
+#[tauri::command]
+pub fn export_all(state: State<'_, Mutex<AppState>>) -> Result<String, String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    let bundle = state.db.export_bundle().map_err(db_err)?;
+    serde_json::to_string_pretty(&bundle).map_err(|e| e.to_string())
+}
+
+/// Restore a bundle produced by `export_all`. Refuses to mix into an existing
+/// database unless `overwrite` is set, in which case current data is replaced.
+#[tauri::command]
+pub fn import_all(state: State<'_, Mutex<AppState>>, bundle: String, overwrite: Option<bool>) -> Result<(), String> {
+    let bundle: db::DataBundle = serde_json::from_str(&bundle)
+        .map_err(|e| format!("Invalid backup file: {}", e))?;
+    if bundle.version > db::DATA_BUNDLE_VERSION {
+        return Err(format!(
+            "Backup version {} is newer than this app supports ({})",
+            bundle.version, db::DATA_BUNDLE_VERSION
+        ));
+    }
+    let overwrite = overwrite.unwrap_or(false);
+    let state = state.lock().map_err(|e| e.to_string())?;
+    if !overwrite && state.db.has_data().map_err(db_err)? {
+        return Err("This database already has data. Import with overwrite to replace it.".to_string());
+    }
+    state.db.import_bundle(&bundle, overwrite).map_err(db_err)
+}
+
+/// Base system prompts for plain chats and decision chats, with their built-in defaults.
+#[tauri::command]
+pub fn get_system_prompts(state: State<'_, Mutex<AppState>>) -> Result<Vec<llm::SystemPromptInfo>, String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    Ok(llm::get_system_prompts(&state.app_data_dir))
+}
+
+/// Override the base system prompt for `kind` ("chat" or "decision"); blank content resets it.
+#[tauri::command]
+pub fn update_system_prompt(state: State<'_, Mutex<AppState>>, kind: String, content: String) -> Result<(), String> {
     let state = state.lock().map_err(|e| e.to_string())?;
-    state.db.get_messages(&conversation_id).map_err(db_err)
+    llm::write_system_prompt(&state.app_data_dir, &kind, &content)
 }
 
 #[tauri::command]
 pub fn get_settings(state: State<'_, Mutex<AppState>>) -> Result<SettingsResponse, String> {
     let state = state.lock().map_err(|e| e.to_string())?;
     let config = config::load_config(&state.app_data_dir);
-    let preview = if config.openrouter_api_key.len() > 8 {
-        format!("{}...{}", &config.openrouter_api_key[..4], &config.openrouter_api_key[config.openrouter_api_key.len()-4..])
-    } else if !config.openrouter_api_key.is_empty() {
-        "****".to_string()
-    } else {
-        String::new()
-    };
-    let elevenlabs_preview = if config.elevenlabs_api_key.len() > 8 {
-        format!("{}...{}", &config.elevenlabs_api_key[..4], &config.elevenlabs_api_key[config.elevenlabs_api_key.len()-4..])
-    } else if !config.elevenlabs_api_key.is_empty() {
-        "****".to_string()
-    } else {
-        String::new()
-    };
     Ok(SettingsResponse {
         api_key_set: !config.openrouter_api_key.is_empty(),
-        api_key_preview: preview,
+        api_key_preview: key_preview(&config.openrouter_api_key),
+        llm_provider: config.llm_provider,
+        openai_api_key_set: !config.openai_api_key.is_empty(),
+        openai_api_key_preview: key_preview(&config.openai_api_key),
+        ollama_base_url: config.ollama_base_url,
         model: config.model,
         agent_models: config.agent_models,
+        default_moderator_model: config.default_moderator_model,
+        debate_default_model: config.debate_default_model,
         elevenlabs_api_key_set: !config.elevenlabs_api_key.is_empty(),
-        elevenlabs_api_key_preview: elevenlabs_preview,
+        elevenlabs_api_key_preview: key_preview(&config.elevenlabs_api_key),
         tts_provider: config.tts_provider,
+        tts_mode: config.tts_mode,
         elevenlabs_model: config.elevenlabs_model,
+        chat_max_tokens: config.chat_max_tokens,
+        debate_max_tokens: config.debate_max_tokens,
+        profile_versioning: config.profile_versioning,
+        request_timeout_secs: config.request_timeout_secs,
+        debate_temperature: config.debate_temperature,
+        min_debaters: config.min_debaters,
     })
 }
 
+/// Check a model id against OpenRouter's catalogue. Returns `Ok(true)` when the
+/// list can't be fetched so offline users aren't blocked, and for other providers,
+/// whose models that catalogue doesn't describe.
+#[tauri::command]
+pub async fn validate_model(state: State<'_, Mutex<AppState>>, model_id: String) -> Result<bool, String> {
+    let now = std::time::Instant::now();
+    let timeout_secs = {
+        let state = state.lock().map_err(|e| e.to_string())?;
+        let config = config::load_config(&state.app_data_dir);
+        if llm::LlmProvider::from_config_value(&config.llm_provider) != llm::LlmProvider::OpenRouter {
+            return Ok(true);
+        }
+        if let Some(known) = state.model_list_cache.as_ref().and_then(|c| c.lookup(&model_id, now)) {
+            return Ok(known);
+        }
+        config.request_timeout_secs
+    };
+
+    let models = match fetch_openrouter_models(timeout_secs).await {
+        Ok(models) => models,
+        Err(_) => return Ok(true),
+    };
+    let cache = llm::ModelListCache::new(models, now);
+    let known = cache.lookup(&model_id, now).unwrap_or(true);
+    let mut state = state.lock().map_err(|e| e.to_string())?;
+    state.model_list_cache = Some(cache);
+    Ok(known)
+}
+
+/// Test the saved OpenRouter and ElevenLabs keys. Failures are reported per
+/// service rather than returned as an error.
+#[tauri::command]
+pub async fn check_api_keys(state: State<'_, Mutex<AppState>>) -> Result<KeyHealth, String> {
+    let config = {
+        let state = state.lock().map_err(|e| e.to_string())?;
+        config::load_config(&state.app_data_dir)
+    };
+    let (openrouter, elevenlabs) = tokio::join!(
+        llm::check_openrouter_key(&config.openrouter_api_key),
+        tts::check_elevenlabs_key(&config.elevenlabs_api_key),
+    );
+    Ok(KeyHealth { openrouter, elevenlabs })
+}
+
+#[tauri::command]
+pub fn save_max_tokens(
+    state: State<'_, Mutex<AppState>>,
+    chat_max_tokens: Option<u32>,
+    debate_max_tokens: Option<u32>,
+) -> Result<(), String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    let mut config = config::load_config(&state.app_data_dir);
+    if let Some(value) = chat_max_tokens {
+        config.chat_max_tokens = config::validate_max_tokens(value)?;
+    }
+    if let Some(value) = debate_max_tokens {
+        config.debate_max_tokens = config::validate_max_tokens(value)?;
+    }
+    config::save_config(&state.app_data_dir, &config)
+}
+
+/// Models for the settings picker, served from the short-lived cache when fresh.
+/// Without an OpenRouter key, returns a curated list of common models instead.
+#[tauri::command]
+pub async fn list_models(state: State<'_, Mutex<AppState>>) -> Result<Vec<llm::ModelInfo>, String> {
+    let now = std::time::Instant::now();
+    let timeout_secs = {
+        let state = state.lock().map_err(|e| e.to_string())?;
+        let config = config::load_config(&state.app_data_dir);
+        if config.openrouter_api_key.trim().is_empty() {
+            return Ok(llm::fallback_models());
+        }
+        if let Some(models) = state.model_list_cache.as_ref().and_then(|c| c.models(now)) {
+            return Ok(models.to_vec());
+        }
+        config.request_timeout_secs
+    };
+
+    let models = fetch_openrouter_models(timeout_secs).await?;
+    let mut state = state.lock().map_err(|e| e.to_string())?;
+    state.model_list_cache = Some(llm::ModelListCache::new(models.clone(), now));
+    Ok(models)
+}
+
 #[tauri::command]
-pub async fn get_openrouter_models() -> Result<Vec<OpenRouterModelInfo>, String> {
-    let response = reqwest::Client::new()
+pub async fn get_openrouter_models(state: State<'_, Mutex<AppState>>) -> Result<Vec<llm::ModelInfo>, String> {
+    let timeout_secs = {
+        let state = state.lock().map_err(|e| e.to_string())?;
+        config::load_config(&state.app_data_dir).request_timeout_secs
+    };
+    fetch_openrouter_models(timeout_secs).await
+}
+
+/// Fetch OpenRouter's model catalogue, giving up after `timeout_secs`.
+async fn fetch_openrouter_models(timeout_secs: u64) -> Result<Vec<llm::ModelInfo>, String> {
+    let response = llm::http_client()
         .get("https://openrouter.ai/api/v1/models")
+        .timeout(std::time::Duration::from_secs(timeout_secs))
         .send()
         .await
-        .map_err(|e| format!("Failed to fetch OpenRouter models: {}", e))?;
+        .map_err(|e| llm::request_error("Failed to fetch OpenRouter models", e))?;
 
     if !response.status().is_success() {
         let status = response.status();
@@ -303,32 +661,8 @@ pub async fn get_openrouter_models() -> Result<Vec<OpenRouterModelInfo>, String>
         return Err(format!("OpenRouter models API error ({}): {}", status, body));
     }
 
-    let payload: OpenRouterModelsResponse = response
-        .json()
-        .await
-        .map_err(|e| format!("Invalid OpenRouter models response: {}", e))?;
-
-    let mut models: Vec<OpenRouterModelInfo> = payload
-        .data
-        .into_iter()
-        .map(|entry| {
-            let prompt_price = parse_price(entry.pricing.as_ref().and_then(|p| p.prompt.as_deref()));
-            let completion_price = parse_price(entry.pricing.as_ref().and_then(|p| p.completion.as_deref()));
-            let is_free = prompt_price.unwrap_or(0.0) == 0.0 && completion_price.unwrap_or(0.0) == 0.0;
-
-            OpenRouterModelInfo {
-                id: entry.id.clone(),
-                name: entry.name.unwrap_or_else(|| entry.id),
-                context_length: entry.context_length,
-                prompt_price_per_million: prompt_price,
-                completion_price_per_million: completion_price,
-                is_free,
-            }
-        })
-        .collect();
-
-    models.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
-    Ok(models)
+    let body = response.text().await.map_err(|e| llm::request_error("Read error", e))?;
+    llm::parse_models_response(&body)
 }
 
 #[tauri::command]
@@ -339,10 +673,39 @@ pub fn save_settings(
     elevenlabs_api_key: Option<String>,
     tts_provider: Option<String>,
     elevenlabs_model: Option<String>,
+    llm_provider: Option<String>,
+    openai_api_key: Option<String>,
+    ollama_base_url: Option<String>,
+    tts_mode: Option<String>,
+    profile_versioning: Option<bool>,
+    request_timeout_secs: Option<u64>,
+    debate_temperature: Option<f32>,
+    min_debaters: Option<usize>,
 ) -> Result<(), String> {
+    if let Some(ref provider) = llm_provider {
+        if !["openrouter", "openai", "ollama"].contains(&provider.as_str()) {
+            return Err(format!("Unknown LLM provider: {}", provider));
+        }
+    }
+    if let Some(ref mode) = tts_mode {
+        if !["off", "live", "post"].contains(&mode.as_str()) {
+            return Err(format!("Unknown TTS mode: {}", mode));
+        }
+    }
+    let request_timeout_secs = request_timeout_secs
+        .map(config::validate_request_timeout_secs)
+        .transpose()?;
+    let debate_temperature = debate_temperature
+        .map(|t| config::clamp_temperature(t).ok_or_else(|| "Debate temperature must be a number".to_string()))
+        .transpose()?;
+    let min_debaters = min_debaters.map(config::validate_min_debaters).transpose()?;
     let state = state.lock().map_err(|e| e.to_string())?;
     let existing = config::load_config(&state.app_data_dir);
     let final_key = if api_key.is_empty() { existing.openrouter_api_key } else { api_key };
+    let final_openai_key = match openai_api_key {
+        Some(k) if !k.is_empty() => k,
+        _ => existing.openai_api_key,
+    };
     let final_elevenlabs_key = match elevenlabs_api_key {
         Some(k) if !k.is_empty() => k,
         _ => existing.elevenlabs_api_key,
@@ -351,14 +714,36 @@ pub fn save_settings(
         Some(m) if !m.trim().is_empty() => m.trim().to_string(),
         _ => existing.elevenlabs_model,
     };
+    // Model ids are provider-specific; keep them valid for the provider being saved
+    let final_provider = llm_provider.unwrap_or(existing.llm_provider);
+    let provider = llm::LlmProvider::from_config_value(&final_provider);
+    let model = provider.model_for_provider(&model);
+    let agent_models = existing
+        .agent_models
+        .into_iter()
+        .map(|(agent, m)| (agent, provider.model_for_provider(&m)))
+        .collect();
     let config = AppConfig {
         openrouter_api_key: final_key,
+        llm_provider: final_provider,
+        openai_api_key: final_openai_key,
+        ollama_base_url: match ollama_base_url {
+            Some(url) if !url.trim().is_empty() => url.trim().to_string(),
+            _ => existing.ollama_base_url,
+        },
         model,
-        agent_models: existing.agent_models,
+        agent_models,
+        default_moderator_model: provider.model_for_provider(&existing.default_moderator_model),
+        debate_default_model: provider.model_for_provider(&existing.debate_default_model),
         elevenlabs_api_key: final_elevenlabs_key,
         tts_provider: tts_provider.unwrap_or(existing.tts_provider),
+        tts_mode: tts_mode.unwrap_or(existing.tts_mode),
+        profile_versioning: profile_versioning.unwrap_or(existing.profile_versioning),
+        request_timeout_secs: request_timeout_secs.unwrap_or(existing.request_timeout_secs),
+        debate_temperature: debate_temperature.unwrap_or(existing.debate_temperature),
+        min_debaters: min_debaters.unwrap_or(existing.min_debaters),
         elevenlabs_model: final_elevenlabs_model,
-        voices: existing.voices,
+        ..existing
     };
     config::save_config(&state.app_data_dir, &config)
 }
@@ -377,12 +762,55 @@ pub fn open_profile_folder(state: State<'_, Mutex<AppState>>) -> Result<String,
     Ok(dir.to_string_lossy().to_string())
 }
 
+/// Move a conversation to the trash; see `restore_conversation` and `purge_conversation`.
 #[tauri::command]
 pub fn delete_conversation(state: State<'_, Mutex<AppState>>, conversation_id: String) -> Result<(), String> {
     let state = state.lock().map_err(|e| e.to_string())?;
     state.db.delete_conversation(&conversation_id).map_err(db_err)
 }
 
+#[tauri::command]
+pub fn restore_conversation(state: State<'_, Mutex<AppState>>, conversation_id: String) -> Result<(), String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    if !state.db.restore_conversation(&conversation_id).map_err(db_err)? {
+        return Err("Conversation not found in trash".to_string());
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_deleted_conversations(state: State<'_, Mutex<AppState>>) -> Result<Vec<crate::db::Conversation>, String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    state.db.get_deleted_conversations().map_err(db_err)
+}
+
+/// Permanently delete a conversation and everything hanging off it.
+#[tauri::command]
+pub fn purge_conversation(state: State<'_, Mutex<AppState>>, conversation_id: String) -> Result<(), String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    state.db.purge_conversation(&conversation_id).map_err(db_err)
+}
+
+#[tauri::command]
+pub fn rename_conversation(
+    state: State<'_, Mutex<AppState>>,
+    conversation_id: String,
+    title: String,
+) -> Result<(), String> {
+    let title = title.trim();
+    if title.is_empty() {
+        return Err("Title cannot be empty".to_string());
+    }
+    if title.chars().count() > 120 {
+        return Err("Title must be 120 characters or fewer".to_string());
+    }
+    let state = state.lock().map_err(|e| e.to_string())?;
+    if state.db.get_conversation(&conversation_id).map_err(db_err)?.is_none() {
+        return Err("Conversation not found".to_string());
+    }
+    state.db.update_conversation_title(&conversation_id, title).map_err(db_err)
+}
+
 // ── Decision Commands ──
 
 #[tauri::command]
@@ -396,39 +824,233 @@ pub fn create_decision(state: State<'_, Mutex<AppState>>, title: String) -> Resu
     })
 }
 
+/// Attach a plain-text or markdown document to a decision; it goes into the debate brief.
 #[tauri::command]
-pub fn get_decisions(state: State<'_, Mutex<AppState>>) -> Result<Vec<Decision>, String> {
+pub fn add_decision_context(
+    state: State<'_, Mutex<AppState>>,
+    decision_id: String,
+    filename: String,
+    content: String,
+) -> Result<crate::decisions::ContextFile, String> {
     let state = state.lock().map_err(|e| e.to_string())?;
-    state.db.get_decisions().map_err(db_err)
+    state
+        .db
+        .get_decision(&decision_id)
+        .map_err(db_err)?
+        .ok_or_else(|| "Decision not found".to_string())?;
+    crate::decisions::add_context_file(&state.app_data_dir, &decision_id, &filename, &content)
 }
 
 #[tauri::command]
-pub fn get_decision(state: State<'_, Mutex<AppState>>, decision_id: String) -> Result<Decision, String> {
+pub fn list_decision_context(
+    state: State<'_, Mutex<AppState>>,
+    decision_id: String,
+) -> Result<Vec<crate::decisions::ContextFile>, String> {
     let state = state.lock().map_err(|e| e.to_string())?;
-    state.db.get_decision(&decision_id)
+    state
+        .db
+        .get_decision(&decision_id)
         .map_err(db_err)?
-        .ok_or_else(|| "Decision not found".to_string())
+        .ok_or_else(|| "Decision not found".to_string())?;
+    crate::decisions::list_context_files(&state.app_data_dir, &decision_id)
 }
 
 #[tauri::command]
-pub fn get_decision_by_conversation(state: State<'_, Mutex<AppState>>, conversation_id: String) -> Result<Decision, String> {
+pub fn remove_decision_context(
+    state: State<'_, Mutex<AppState>>,
+    decision_id: String,
+    filename: String,
+) -> Result<(), String> {
     let state = state.lock().map_err(|e| e.to_string())?;
-    state.db.get_decision_by_conversation(&conversation_id)
+    state
+        .db
+        .get_decision(&decision_id)
         .map_err(db_err)?
-        .ok_or_else(|| "Decision not found".to_string())
+        .ok_or_else(|| "Decision not found".to_string())?;
+    crate::decisions::remove_context_file(&state.app_data_dir, &decision_id, &filename)
 }
 
+/// Start a decision with a template's options and variables already in its summary.
 #[tauri::command]
-pub fn update_decision_status(
+pub fn create_decision_from_template(
+    state: State<'_, Mutex<AppState>>,
+    title: String,
+    template_name: String,
+) -> Result<CreateDecisionResponse, String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    let decision = templates::create_decision_from_template(&state.db, &state.app_data_dir, &title, &template_name)?;
+    Ok(CreateDecisionResponse {
+        conversation_id: decision.conversation_id,
+        decision_id: decision.id,
+    })
+}
+
+#[tauri::command]
+pub fn list_decision_templates(state: State<'_, Mutex<AppState>>) -> Result<Vec<templates::DecisionTemplate>, String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    Ok(templates::load_templates(&state.app_data_dir))
+}
+
+#[tauri::command]
+pub fn save_decision_template(
+    state: State<'_, Mutex<AppState>>,
+    template: templates::DecisionTemplate,
+) -> Result<templates::DecisionTemplate, String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    templates::save_template(&state.app_data_dir, template)
+}
+
+/// All decisions made on the same conversation as this one, oldest first.
+#[tauri::command]
+pub fn get_related_decisions(state: State<'_, Mutex<AppState>>, decision_id: String) -> Result<Vec<Decision>, String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    state.db.get_related_decisions(&decision_id).map_err(db_err)
+}
+
+/// Turn a plain chat into a decision, keeping its messages and title.
+#[tauri::command]
+pub fn promote_to_decision(state: State<'_, Mutex<AppState>>, conversation_id: String) -> Result<CreateDecisionResponse, String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    let conv = state
+        .db
+        .get_conversation(&conversation_id)
+        .map_err(db_err)?
+        .ok_or_else(|| "Conversation not found".to_string())?;
+    if conv.conv_type == "debate" {
+        return Err("Standalone debates cannot be turned into decisions".to_string());
+    }
+    let decision = state
+        .db
+        .promote_conversation_to_decision(&conv.id, &conv.title)
+        .map_err(db_err)?
+        .ok_or_else(|| "This conversation already has a decision".to_string())?;
+    Ok(CreateDecisionResponse {
+        conversation_id: conv.id,
+        decision_id: decision.id,
+    })
+}
+
+#[tauri::command]
+pub fn get_decisions(state: State<'_, Mutex<AppState>>) -> Result<Vec<DecisionWithTags>, String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    let decisions = state.db.get_decisions().map_err(db_err)?;
+    let mut tags = state.db.get_all_decision_tags().map_err(db_err)?;
+    Ok(decisions
+        .into_iter()
+        .map(|decision| {
+            let tags = tags.remove(&decision.id).unwrap_or_default();
+            DecisionWithTags { decision, tags }
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub fn get_decisions_by_status(state: State<'_, Mutex<AppState>>, status: String) -> Result<Vec<Decision>, String> {
+    if !crate::db::DECISION_STATUSES.contains(&status.as_str()) {
+        return Err(format!("Unknown decision status: {}", status));
+    }
+    let state = state.lock().map_err(|e| e.to_string())?;
+    state.db.get_decisions_by_status(&status).map_err(db_err)
+}
+
+/// Set (or with `None`/blank, clear) the deadline for a decision.
+#[tauri::command]
+pub fn set_decision_due_date(
+    state: State<'_, Mutex<AppState>>,
+    decision_id: String,
+    due_date: Option<String>,
+) -> Result<Decision, String> {
+    let due_date = crate::decisions::normalize_due_date(due_date.as_deref())?;
+    let state = state.lock().map_err(|e| e.to_string())?;
+    if !state.db.set_decision_due_date(&decision_id, due_date.as_deref()).map_err(db_err)? {
+        return Err("Decision not found".to_string());
+    }
+    state.db.get_decision(&decision_id)
+        .map_err(db_err)?
+        .ok_or_else(|| "Decision not found".to_string())
+}
+
+/// Open decisions due within `within_days` (overdue ones included), soonest first.
+#[tauri::command]
+pub fn get_decisions_due_soon(state: State<'_, Mutex<AppState>>, within_days: i64) -> Result<Vec<Decision>, String> {
+    if within_days < 0 {
+        return Err("within_days must not be negative".to_string());
+    }
+    let state = state.lock().map_err(|e| e.to_string())?;
+    state.db.get_decisions_due_soon(within_days).map_err(db_err)
+}
+
+#[tauri::command]
+pub fn get_decision_status_counts(state: State<'_, Mutex<AppState>>) -> Result<HashMap<String, u64>, String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    state.db.get_decision_status_counts().map_err(db_err)
+}
+
+#[tauri::command]
+pub fn get_decision(state: State<'_, Mutex<AppState>>, decision_id: String) -> Result<Decision, String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    state.db.get_decision(&decision_id)
+        .map_err(db_err)?
+        .ok_or_else(|| "Decision not found".to_string())
+}
+
+#[tauri::command]
+pub fn get_decisions_with_audio_flag(state: State<'_, Mutex<AppState>>) -> Result<Vec<crate::db::DecisionSummaryRow>, String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    state.db.get_decisions_with_audio_flag().map_err(db_err)
+}
+
+#[tauri::command]
+pub fn get_calibration_report(state: State<'_, Mutex<AppState>>) -> Result<crate::decisions::CalibrationReport, String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    let reviewed = state.db.get_decisions_by_status("reviewed").map_err(db_err)?;
+    Ok(crate::decisions::calibration_report(&reviewed))
+}
+
+#[tauri::command]
+pub fn compare_decisions(
+    state: State<'_, Mutex<AppState>>,
+    id_a: String,
+    id_b: String,
+) -> Result<crate::decisions::ComparisonResult, String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    let a = state.db.get_decision(&id_a)
+        .map_err(db_err)?
+        .ok_or_else(|| "Decision not found".to_string())?;
+    let b = state.db.get_decision(&id_b)
+        .map_err(db_err)?
+        .ok_or_else(|| "Decision not found".to_string())?;
+    Ok(crate::decisions::compare_decisions(&a, &b))
+}
+
+#[tauri::command]
+pub fn get_decision_by_conversation(state: State<'_, Mutex<AppState>>, conversation_id: String) -> Result<Decision, String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    state.db.get_decision_by_conversation(&conversation_id)
+        .map_err(db_err)?
+        .ok_or_else(|| "Decision not found".to_string())
+}
+
+#[tauri::command]
+pub fn update_decision_status(
     state: State<'_, Mutex<AppState>>,
     decision_id: String,
     status: String,
     user_choice: Option<String>,
     user_choice_reasoning: Option<String>,
     outcome: Option<String>,
+    outcome_score: Option<i64>,
+    force: Option<bool>,
 ) -> Result<Decision, String> {
     let state = state.lock().map_err(|e| e.to_string())?;
 
+    if !force.unwrap_or(false) {
+        let current = state.db.get_decision(&decision_id)
+            .map_err(db_err)?
+            .ok_or_else(|| "Decision not found".to_string())?;
+        crate::decisions::check_status_transition(&current.status, &status)?;
+    }
+
     match status.as_str() {
         "decided" => {
             let choice = user_choice.ok_or("user_choice is required when status is 'decided'")?;
@@ -436,7 +1058,7 @@ pub fn update_decision_status(
         }
         "reviewed" => {
             let outcome_text = outcome.ok_or("outcome is required when status is 'reviewed'")?;
-            state.db.update_decision_outcome(&decision_id, &outcome_text).map_err(db_err)?;
+            state.db.update_decision_outcome(&decision_id, &outcome_text, outcome_score).map_err(db_err)?;
         }
         _ => {
             state.db.update_decision_status(&decision_id, &status).map_err(db_err)?;
@@ -448,6 +1070,91 @@ pub fn update_decision_status(
         .ok_or_else(|| "Decision not found after update".to_string())
 }
 
+#[tauri::command]
+pub async fn reflect_on_outcome(
+    app_handle: tauri::AppHandle,
+    state: State<'_, Mutex<AppState>>,
+    decision_id: String,
+    outcome: String,
+    outcome_score: Option<i64>,
+    on_event: Channel<StreamEvent>,
+) -> Result<SendMessageResponse, String> {
+    let outcome = outcome.trim().to_string();
+    if outcome.is_empty() {
+        return Err("Describe what happened before logging the outcome.".to_string());
+    }
+
+    let conv_id = {
+        let state = state.lock().map_err(|e| e.to_string())?;
+        let decision = crate::decisions::log_outcome(&state.db, &decision_id, &outcome, outcome_score)?;
+
+        let content = crate::decisions::outcome_reflection_message(
+            &decision.title,
+            decision.summary_json.as_deref(),
+            decision.user_choice.as_deref(),
+            decision.user_choice_reasoning.as_deref(),
+            &outcome,
+        );
+        state.db.add_message(&decision.conversation_id, "user", &content).map_err(db_err)?;
+        decision.conversation_id
+    };
+
+    let _ = tauri::Emitter::emit(&app_handle, "outcome-reflection-started", json!({
+        "decision_id": decision_id,
+        "conversation_id": conv_id,
+    }));
+
+    let response_text = match run_assistant_turn(&app_handle, &state, &conv_id, &on_event, llm::CHAT_TEMPERATURE).await {
+        Ok(text) => text,
+        Err(e) => {
+            let _ = tauri::Emitter::emit(&app_handle, "outcome-reflection-error", json!({
+                "decision_id": decision_id,
+                "error": e,
+            }));
+            return Err(e);
+        }
+    };
+
+    let _ = tauri::Emitter::emit(&app_handle, "outcome-reflection-complete", json!({
+        "decision_id": decision_id,
+        "conversation_id": conv_id,
+    }));
+
+    Ok(SendMessageResponse {
+        conversation_id: conv_id,
+        response: response_text,
+    })
+}
+
+#[tauri::command]
+pub fn add_decision_tag(state: State<'_, Mutex<AppState>>, decision_id: String, tag: String) -> Result<Vec<String>, String> {
+    let tag = normalize_tag(&tag)?;
+    let state = state.lock().map_err(|e| e.to_string())?;
+    state.db.add_decision_tag(&decision_id, &tag).map_err(db_err)?;
+    state.db.get_tags_for_decision(&decision_id).map_err(db_err)
+}
+
+#[tauri::command]
+pub fn remove_decision_tag(state: State<'_, Mutex<AppState>>, decision_id: String, tag: String) -> Result<Vec<String>, String> {
+    let tag = normalize_tag(&tag)?;
+    let state = state.lock().map_err(|e| e.to_string())?;
+    state.db.remove_decision_tag(&decision_id, &tag).map_err(db_err)?;
+    state.db.get_tags_for_decision(&decision_id).map_err(db_err)
+}
+
+#[tauri::command]
+pub fn get_decision_tags(state: State<'_, Mutex<AppState>>, decision_id: String) -> Result<Vec<String>, String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    state.db.get_tags_for_decision(&decision_id).map_err(db_err)
+}
+
+#[tauri::command]
+pub fn get_decisions_by_tag(state: State<'_, Mutex<AppState>>, tag: String) -> Result<Vec<Decision>, String> {
+    let tag = normalize_tag(&tag)?;
+    let state = state.lock().map_err(|e| e.to_string())?;
+    state.db.get_decisions_by_tag(&tag).map_err(db_err)
+}
+
 // ── Profile Viewer Commands ──
 
 #[tauri::command]
@@ -469,12 +1176,22 @@ pub fn update_profile_file(state: State<'_, Mutex<AppState>>, filename: String,
         .to_string();
     Ok(ProfileFileInfo {
         filename,
+        word_count: profile::word_count(&content),
         content,
         modified_at,
         size_bytes: metadata.len(),
     })
 }
 
+#[tauri::command]
+pub fn validate_profile_file(
+    state: State<'_, Mutex<AppState>>,
+    filename: String,
+) -> Result<Vec<profile::LintWarning>, String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    profile::validate_profile_file(&state.app_data_dir, &filename)
+}
+
 #[tauri::command]
 pub fn remove_profile_file(state: State<'_, Mutex<AppState>>, filename: String) -> Result<(), String> {
     let state = state.lock().map_err(|e| e.to_string())?;
@@ -482,6 +1199,22 @@ pub fn remove_profile_file(state: State<'_, Mutex<AppState>>, filename: String)
     Ok(())
 }
 
+#[tauri::command]
+pub fn list_profile_versions(state: State<'_, Mutex<AppState>>, filename: String) -> Result<Vec<profile::ProfileVersion>, String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    profile::list_profile_versions(&state.app_data_dir, &filename)
+}
+
+#[tauri::command]
+pub fn restore_profile_version(
+    state: State<'_, Mutex<AppState>>,
+    filename: String,
+    timestamp: String,
+) -> Result<String, String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    profile::restore_profile_version(&state.app_data_dir, &filename, &timestamp)
+}
+
 // ── Committee Agent Commands ──
 
 #[tauri::command]
@@ -490,12 +1223,44 @@ pub fn get_agent_registry(state: State<'_, Mutex<AppState>>) -> Result<Vec<agent
     Ok(agents::load_registry(&state.app_data_dir))
 }
 
+/// Every agent alongside the model it will run on, so the settings UI needn't join them.
+#[tauri::command]
+pub fn get_agents_with_models(state: State<'_, Mutex<AppState>>) -> Result<Vec<agents::AgentWithModel>, String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    let config = config::load_config(&state.app_data_dir);
+    let registry = agents::load_registry(&state.app_data_dir);
+    Ok(agents::agents_with_models(&registry, &config))
+}
+
+/// Rewrite a malformed registry and restore missing built-ins. Unknown prompt files
+/// are reported; only those the user names in `adopt` are registered.
+#[tauri::command]
+pub fn repair_agents(
+    state: State<'_, Mutex<AppState>>,
+    adopt: Option<Vec<String>>,
+) -> Result<agents::RepairReport, String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    agents::repair_agents(&state.app_data_dir, &adopt.unwrap_or_default())
+}
+
 #[tauri::command]
 pub fn get_agent_files(state: State<'_, Mutex<AppState>>) -> Result<Vec<agents::AgentFileInfo>, String> {
     let state = state.lock().map_err(|e| e.to_string())?;
     agents::read_all_agent_files(&state.app_data_dir)
 }
 
+#[tauri::command]
+pub fn get_agent_prompt(state: State<'_, Mutex<AppState>>, agent_key: String) -> Result<String, String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    agents::get_agent_prompt(&state.app_data_dir, &agent_key)
+}
+
+#[tauri::command]
+pub fn reset_agent_prompt(state: State<'_, Mutex<AppState>>, agent_key: String) -> Result<String, String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    agents::reset_agent_prompt(&state.app_data_dir, &agent_key)
+}
+
 #[tauri::command]
 pub fn update_agent_file(state: State<'_, Mutex<AppState>>, filename: String, content: String) -> Result<agents::AgentFileInfo, String> {
     let state = state.lock().map_err(|e| e.to_string())?;
@@ -509,6 +1274,7 @@ pub fn update_agent_file(state: State<'_, Mutex<AppState>>, filename: String, co
         .to_string();
     Ok(agents::AgentFileInfo {
         filename,
+        word_count: profile::word_count(&content),
         content,
         modified_at,
         size_bytes: metadata.len(),
@@ -531,6 +1297,54 @@ pub fn save_agent_model(
     config::save_config(&state.app_data_dir, &config)
 }
 
+/// Save an agent's ElevenLabs voice tuning. Unset fields keep the persona
+/// defaults; passing no settings (or all fields unset) clears the override.
+#[tauri::command]
+pub fn save_agent_voice_settings(
+    state: State<'_, Mutex<AppState>>,
+    agent_key: String,
+    settings: Option<config::ElevenLabsVoiceSettings>,
+) -> Result<(), String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    let mut config = config::load_config(&state.app_data_dir);
+    match settings.filter(|s| !s.is_empty()) {
+        Some(settings) => {
+            settings.validate()?;
+            config.elevenlabs_voice_settings.insert(agent_key, settings);
+        }
+        None => {
+            config.elevenlabs_voice_settings.remove(&agent_key);
+        }
+    }
+    config::save_config(&state.app_data_dir, &config)
+}
+
+/// Set the moderator model used when no per-agent moderator override exists.
+/// An empty string falls back to the main chat model.
+#[tauri::command]
+pub fn save_default_moderator_model(
+    state: State<'_, Mutex<AppState>>,
+    model: String,
+) -> Result<(), String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    let mut config = config::load_config(&state.app_data_dir);
+    config.default_moderator_model = model.trim().to_string();
+    config::save_config(&state.app_data_dir, &config)
+}
+
+/// Set the model debaters use when they have no per-agent override.
+/// An empty string falls back to the main chat model.
+#[tauri::command]
+pub fn save_debate_default_model(
+    state: State<'_, Mutex<AppState>>,
+    model: String,
+) -> Result<(), String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    let mut config = config::load_config(&state.app_data_dir);
+    config.debate_default_model = model.trim().to_string();
+    config::save_config(&state.app_data_dir, &config)
+}
+
 #[tauri::command]
 pub fn open_agents_folder(state: State<'_, Mutex<AppState>>) -> Result<String, String> {
     let state = state.lock().map_err(|e| e.to_string())?;
@@ -539,6 +1353,8 @@ pub fn open_agents_folder(state: State<'_, Mutex<AppState>>) -> Result<String, S
     Ok(dir.to_string_lossy().to_string())
 }
 
+/// Add a committee member. With `prompt`, that persona is saved as written;
+/// otherwise one is generated from `description`.
 #[tauri::command]
 pub async fn create_custom_agent(
     state: State<'_, Mutex<AppState>>,
@@ -546,36 +1362,65 @@ pub async fn create_custom_agent(
     emoji: String,
     description: String,
     voice_gender: String,
+    prompt: Option<String>,
 ) -> Result<agents::AgentInfo, String> {
+    if let Some(prompt) = prompt {
+        agents::validate_custom_agent_input(&emoji, &prompt)?;
+        let state = state.lock().map_err(|e| e.to_string())?;
+        return agents::create_custom_agent(&state.app_data_dir, label.trim(), emoji.trim(), prompt.trim(), &voice_gender);
+    }
+
     // Generate prompt via LLM
-    let (api_key, model, app_data_dir) = {
+    let (endpoint, model, app_data_dir) = {
         let state = state.lock().map_err(|e| e.to_string())?;
         let config = config::load_config(&state.app_data_dir);
-        if config.openrouter_api_key.is_empty() {
-            return Err("API key not set. Please go to Settings to add your OpenRouter API key.".to_string());
-        }
-        (config.openrouter_api_key, config.model, state.app_data_dir.clone())
+        let endpoint = llm::LlmEndpoint::from_config(&config);
+        endpoint.require_api_key()?;
+        (endpoint, config.model, state.app_data_dir.clone())
     };
 
     let (system_prompt, user_prompt) = agents::agent_generation_prompt(&label, &description);
-    let generated_prompt = llm::call_llm_simple(&api_key, &model, &system_prompt, &user_prompt).await?;
+    let generated_prompt = llm::call_llm_simple(&endpoint, &model, &system_prompt, &user_prompt).await?;
+    agents::validate_custom_agent_input(&emoji, &generated_prompt)?;
 
     agents::create_custom_agent(&app_data_dir, &label, &emoji, &generated_prompt, &voice_gender)
 }
 
+/// Draft a persona prompt for a new agent, streaming it into the editor as it
+/// is written. Nothing is saved; the final text is returned for review.
+#[tauri::command]
+pub async fn generate_agent_prompt(
+    state: State<'_, Mutex<AppState>>,
+    label: String,
+    description: String,
+    on_event: Channel<StreamEvent>,
+) -> Result<String, String> {
+    let (endpoint, model) = {
+        let state = state.lock().map_err(|e| e.to_string())?;
+        let config = config::load_config(&state.app_data_dir);
+        let endpoint = llm::LlmEndpoint::from_config(&config);
+        endpoint.require_api_key()?;
+        (endpoint, config.model)
+    };
+
+    let (system_prompt, user_prompt) = agents::agent_generation_prompt(&label, &description);
+    llm::call_llm_simple_streaming(&endpoint, &model, &system_prompt, &user_prompt, &on_event).await
+}
+
 #[tauri::command]
 pub fn delete_custom_agent(
     state: State<'_, Mutex<AppState>>,
     agent_key: String,
 ) -> Result<(), String> {
     let state = state.lock().map_err(|e| e.to_string())?;
+    agents::delete_custom_agent(&state.app_data_dir, &agent_key)?;
 
-    // Also remove model override from config
+    // Drop any model override left behind for the removed agent
     let mut config = config::load_config(&state.app_data_dir);
-    config.agent_models.remove(&agent_key);
-    config::save_config(&state.app_data_dir, &config)?;
-
-    agents::delete_custom_agent(&state.app_data_dir, &agent_key)
+    if config.agent_models.remove(&agent_key).is_some() {
+        config::save_config(&state.app_data_dir, &config)?;
+    }
+    Ok(())
 }
 
 // ── Debate Commands ──
@@ -586,31 +1431,46 @@ pub async fn start_debate(
     state: State<'_, Mutex<AppState>>,
     decision_id: String,
     quick_mode: bool,
+    shape: Option<debate::DebateShape>,
+    selected_agents: Option<Vec<String>>,
+) -> Result<(), String> {
+    let shape = shape.unwrap_or_else(|| debate::DebateShape::from_quick_mode(quick_mode));
+    launch_committee_debate(app_handle, state, decision_id, shape, selected_agents, false)
+}
+
+/// Ask the committee one question: every debater gives a single opening take
+/// in parallel, then the moderator synthesizes. No rebuttal rounds.
+#[tauri::command]
+pub async fn quick_poll(
+    app_handle: tauri::AppHandle,
+    state: State<'_, Mutex<AppState>>,
+    decision_id: String,
     selected_agents: Option<Vec<String>>,
+) -> Result<(), String> {
+    launch_committee_debate(app_handle, state, decision_id, debate::DebateShape::QUICK, selected_agents, true)
+}
+
+fn launch_committee_debate(
+    app_handle: tauri::AppHandle,
+    state: State<'_, Mutex<AppState>>,
+    decision_id: String,
+    shape: debate::DebateShape,
+    selected_agents: Option<Vec<String>>,
+    quick_poll: bool,
 ) -> Result<(), String> {
     {
         let state = state.lock().map_err(|e| e.to_string())?;
         let decision = state.db.get_decision(&decision_id)
             .map_err(db_err)?
             .ok_or_else(|| "Decision not found".to_string())?;
-
-        if let Some(ref summary_json) = decision.summary_json {
-            let summary: serde_json::Value = serde_json::from_str(summary_json)
-                .map_err(|_| "Invalid summary JSON".to_string())?;
-            let has_options = summary.get("options")
-                .and_then(|v| v.as_array())
-                .map(|a| !a.is_empty())
-                .unwrap_or(false);
-            let has_variables = summary.get("variables")
-                .and_then(|v| v.as_array())
-                .map(|a| !a.is_empty())
-                .unwrap_or(false);
-            if !has_options || !has_variables {
-                return Err("Decision needs at least one option and one variable before starting a debate.".to_string());
-            }
-        } else {
-            return Err("Decision has no summary data. Chat with the AI first to build context.".to_string());
-        }
+        debate::check_debate_preconditions(&decision)?;
+        let registry_debaters: Vec<agents::AgentInfo> = agents::load_registry(&state.app_data_dir)
+            .into_iter()
+            .filter(|a| a.role == "debater")
+            .collect();
+        let debaters = debate::select_debaters(registry_debaters, selected_agents.as_deref());
+        let min_debaters = config::load_config(&state.app_data_dir).min_debaters;
+        debate::check_debater_count(debaters.len(), min_debaters)?;
     }
 
     let cancel_flag = Arc::new(AtomicBool::new(false));
@@ -622,47 +1482,367 @@ pub async fn start_debate(
     let dec_id = decision_id.clone();
     let selected = selected_agents.clone();
     tokio::spawn(async move {
-        if let Err(e) = debate::run_debate(
-            app_handle.clone(),
-            dec_id.clone(),
-            quick_mode,
-            cancel_flag,
-            selected,
-            None,
-            None,
-            None,
-            None,
-        ).await {
+        let request = debate::DebateRequest {
+            shape,
+            selected_agent_keys: selected,
+            quick_poll,
+            ..Default::default()
+        };
+        if let Err(e) = debate::run_debate(app_handle.clone(), dec_id.clone(), cancel_flag.clone(), request).await {
             eprintln!("Debate error: {}", e);
             let _ = tauri::Emitter::emit(&app_handle, "debate-error", serde_json::json!({
                 "decision_id": dec_id,
                 "error": e,
             }));
         }
+        release_cancel_flag(&app_handle, &dec_id, &cancel_flag);
     });
 
     Ok(())
 }
 
+/// Drop a finished debate's cancel flag so it no longer reads as running. Leaves the
+/// entry alone if a newer run has already registered its own flag.
+fn release_cancel_flag(app_handle: &tauri::AppHandle, decision_id: &str, flag: &Arc<AtomicBool>) {
+    let state: State<'_, Mutex<AppState>> = tauri::Manager::state(app_handle);
+    let Ok(mut state) = state.lock() else {
+        return;
+    };
+    if state.debate_cancel_flags.get(decision_id).is_some_and(|f| Arc::ptr_eq(f, flag)) {
+        state.debate_cancel_flags.remove(decision_id);
+        state.live_debate_buffers.clear_decision(decision_id);
+    }
+}
+
+/// Continue a failed or interrupted committee debate from the last saved round,
+/// keeping the rounds (and any live audio) generated so far.
+#[tauri::command]
+pub async fn resume_debate(
+    app_handle: tauri::AppHandle,
+    state: State<'_, Mutex<AppState>>,
+    decision_id: String,
+) -> Result<(), String> {
+    {
+        let state = state.lock().map_err(|e| e.to_string())?;
+        let decision = state.db.get_decision(&decision_id)
+            .map_err(db_err)?
+            .ok_or_else(|| "Decision not found".to_string())?;
+        let conv = state.db.get_conversation(&decision.conversation_id).map_err(db_err)?;
+        if conv.map(|c| c.conv_type == "debate").unwrap_or(false) {
+            return Err("Standalone debates cannot be resumed. Start a new debate instead.".to_string());
+        }
+        if decision.debate_completed_at.is_some() {
+            return Err("This debate is already complete; there is nothing to resume.".to_string());
+        }
+
+        let rounds = state.db.get_debate_rounds(&decision_id).map_err(db_err)?;
+        if rounds.is_empty() {
+            return Err("No saved rounds to resume from. Start a new debate instead.".to_string());
+        }
+        let setup = debate::load_debate_setup(&state.db, &decision_id)?.ok_or_else(|| {
+            "This debate was started before resume support and can't be resumed. Start a new debate instead.".to_string()
+        })?;
+        debate::remaining_committee_steps(&rounds, setup.shape, &setup.roster)?;
+    }
+
+    let cancel_flag = Arc::new(AtomicBool::new(false));
+    {
+        let mut state = state.lock().map_err(|e| e.to_string())?;
+        state.debate_cancel_flags.insert(decision_id.clone(), cancel_flag.clone());
+    }
+
+    let dec_id = decision_id.clone();
+    tokio::spawn(async move {
+        // The roster and shape come from the stored setup, not from the caller
+        let request = debate::DebateRequest {
+            resume: true,
+            ..Default::default()
+        };
+        if let Err(e) = debate::run_debate(app_handle.clone(), dec_id.clone(), cancel_flag.clone(), request).await {
+            eprintln!("Debate resume error: {}", e);
+            let _ = tauri::Emitter::emit(&app_handle, "debate-error", serde_json::json!({
+                "decision_id": dec_id,
+                "error": e,
+            }));
+        }
+        release_cancel_flag(&app_handle, &dec_id, &cancel_flag);
+    });
+
+    Ok(())
+}
+
+/// The moderator's full markdown synthesis, for showing the narrative next to
+/// the structured recommendation. `None` until the moderator has spoken.
+#[tauri::command]
+pub fn get_moderator_synthesis(
+    state: State<'_, Mutex<AppState>>,
+    decision_id: String,
+) -> Result<Option<String>, String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    debate::moderator_synthesis(&state.db, &decision_id)
+}
+
+/// Whether `start_debate` would accept this decision, and what is missing if not.
+#[tauri::command]
+pub fn can_start_debate(
+    state: State<'_, Mutex<AppState>>,
+    decision_id: String,
+) -> Result<debate::DebateReadiness, String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    let decision = state.db.get_decision(&decision_id)
+        .map_err(db_err)?
+        .ok_or_else(|| "Decision not found".to_string())?;
+    Ok(debate::debate_readiness(&decision))
+}
+
+/// Return the brief `start_debate` would send to the agents, without starting anything.
+#[tauri::command]
+pub fn preview_debate_brief(state: State<'_, Mutex<AppState>>, decision_id: String) -> Result<String, String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    let decision = state.db.get_decision(&decision_id)
+        .map_err(db_err)?
+        .ok_or_else(|| "Decision not found".to_string())?;
+    debate::check_debate_preconditions(&decision)?;
+    debate::compile_brief(&state.db, &state.app_data_dir, &decision_id)
+}
+
+/// The brief the committee actually debated, as saved when the debate started.
+#[tauri::command]
+pub fn get_debate_brief(state: State<'_, Mutex<AppState>>, decision_id: String) -> Result<Option<String>, String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    state.db.get_debate_brief(&decision_id).map_err(db_err)
+}
+
 #[tauri::command]
 pub fn get_debate(state: State<'_, Mutex<AppState>>, decision_id: String) -> Result<Vec<DebateRound>, String> {
     let state = state.lock().map_err(|e| e.to_string())?;
     state.db.get_debate_rounds(&decision_id).map_err(db_err)
 }
 
+/// Full debate transcript as a JSON document, for integrations beyond the Markdown view.
+#[tauri::command]
+pub fn export_debate_json(state: State<'_, Mutex<AppState>>, decision_id: String) -> Result<String, String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    let registry = agents::load_registry(&state.app_data_dir);
+    let exported = debate::export_debate_json(&state.db, &registry, &decision_id)?;
+    serde_json::to_string_pretty(&exported).map_err(|e| e.to_string())
+}
+
+/// Re-emit a finished debate as live events (`debate-agent-response`,
+/// `debate-round-complete`), paced by its audio at `speed`x, so the UI can
+/// replay it with the same rendering it uses for a live debate.
+#[tauri::command]
+pub async fn replay_debate(
+    app_handle: tauri::AppHandle,
+    state: State<'_, Mutex<AppState>>,
+    decision_id: String,
+    speed: f32,
+) -> Result<(), String> {
+    let (rounds, manifest) = {
+        let state = state.lock().map_err(|e| e.to_string())?;
+        state.db.get_decision(&decision_id)
+            .map_err(db_err)?
+            .ok_or_else(|| "Decision not found".to_string())?;
+        let rounds = state.db.get_debate_rounds(&decision_id).map_err(db_err)?;
+        let manifest = state.db.get_debate_audio(&decision_id)
+            .map_err(db_err)?
+            .and_then(|audio| serde_json::from_str::<tts::AudioManifest>(&audio.manifest_json).ok());
+        (rounds, manifest)
+    };
+    if rounds.is_empty() {
+        return Err("This decision has no debate to replay.".to_string());
+    }
+    if !speed.is_finite() || speed <= 0.0 {
+        return Err("Replay speed must be greater than zero".to_string());
+    }
+
+    tokio::spawn(async move {
+        let result = debate::replay_debate(&decision_id, &rounds, manifest.as_ref(), speed, |name, payload| {
+            let _ = tauri::Emitter::emit(&app_handle, name, payload);
+        })
+        .await;
+        if let Err(e) = result {
+            eprintln!("Debate replay failed: {}", e);
+        }
+    });
+    Ok(())
+}
+
+/// The debate summary and recommendation alone, without the rest of the decision.
+#[tauri::command]
+pub fn get_debate_summary(state: State<'_, Mutex<AppState>>, decision_id: String) -> Result<Option<serde_json::Value>, String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    let decision = state
+        .db
+        .get_decision(&decision_id)
+        .map_err(db_err)?
+        .ok_or_else(|| "Decision not found".to_string())?;
+    Ok(crate::decisions::debate_summary_view(decision.summary_json.as_deref()))
+}
+
+/// Roll the decision summary back to its state before the most recent update.
+#[tauri::command]
+pub fn undo_summary(
+    app_handle: tauri::AppHandle,
+    state: State<'_, Mutex<AppState>>,
+    decision_id: String,
+) -> Result<Decision, String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    if !state.db.undo_summary_update(&decision_id).map_err(db_err)? {
+        return Err("No earlier summary to restore".to_string());
+    }
+    let decision = state
+        .db
+        .get_decision(&decision_id)
+        .map_err(db_err)?
+        .ok_or_else(|| "Decision not found".to_string())?;
+    let _ = tauri::Emitter::emit(&app_handle, "decision-summary-updated", serde_json::json!({
+        "decision_id": decision_id,
+        "summary": decision.summary_json,
+        "status": decision.status,
+    }));
+    Ok(decision)
+}
+
+/// Recompute the debate summary and recommendation purely from the saved transcript.
+#[tauri::command]
+pub fn rebuild_summary_from_debate(
+    app_handle: tauri::AppHandle,
+    state: State<'_, Mutex<AppState>>,
+    decision_id: String,
+) -> Result<serde_json::Value, String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    let (summary, changes) = debate::rebuild_summary_from_debate(&state.db, &decision_id)?;
+    let decision = state
+        .db
+        .get_decision(&decision_id)
+        .map_err(db_err)?
+        .ok_or_else(|| "Decision not found".to_string())?;
+    let _ = tauri::Emitter::emit(&app_handle, "decision-summary-updated", serde_json::json!({
+        "decision_id": decision_id,
+        "summary": summary,
+        "status": decision.status,
+        "changes": changes,
+    }));
+    serde_json::from_str(&summary).map_err(|e| e.to_string())
+}
+
+/// Phase and progress of a decision's debate, so the UI can recover after a reload.
+#[tauri::command]
+pub fn get_debate_status(state: State<'_, Mutex<AppState>>, decision_id: String) -> Result<debate::DebateStatus, String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    let decision = state
+        .db
+        .get_decision(&decision_id)
+        .map_err(db_err)?
+        .ok_or_else(|| "Decision not found".to_string())?;
+    let rounds = state.db.get_debate_rounds(&decision_id).map_err(db_err)?;
+    let has_audio = state.db.get_debate_audio(&decision_id).map_err(db_err)?.is_some();
+    let running = state.debate_cancel_flags.contains_key(&decision_id);
+    Ok(debate::debate_status(&decision, &rounds, running, has_audio))
+}
+
+/// Partial text of every agent currently streaming in a live debate.
+#[tauri::command]
+pub fn get_live_debate_buffer(
+    state: State<'_, Mutex<AppState>>,
+    decision_id: String,
+) -> Result<Vec<llm::LiveBuffer>, String> {
+    let buffers = state.lock().map_err(|e| e.to_string())?.live_debate_buffers.clone();
+    Ok(buffers.for_decision(&decision_id))
+}
+
+/// Drop one debate turn (e.g. an agent that errored). The UI can resume from that point.
+#[tauri::command]
+pub fn delete_debate_round(state: State<'_, Mutex<AppState>>, round_id: String) -> Result<(), String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    let round = state
+        .db
+        .get_debate_round(&round_id)
+        .map_err(db_err)?
+        .ok_or_else(|| "Debate round not found".to_string())?;
+    let decision = state
+        .db
+        .get_decision(&round.decision_id)
+        .map_err(db_err)?
+        .ok_or_else(|| "Decision not found".to_string())?;
+    debate::ensure_round_deletable(&round, decision.summary_json.as_deref())?;
+    state.db.delete_debate_round(&round_id).map_err(db_err)?;
+    Ok(())
+}
+
+/// Manually correct a round's text. The response says whether existing audio is now stale.
+#[tauri::command]
+pub fn update_debate_round_content(
+    state: State<'_, Mutex<AppState>>,
+    round_id: String,
+    content: String,
+) -> Result<debate::RoundEdit, String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    debate::edit_round_content(&state.db, &round_id, &content)
+}
+
+/// Ask a running debate to stop. The flag stays registered (so the debate still
+/// reads as running) until the task itself exits and releases it.
 #[tauri::command]
 pub fn cancel_debate(state: State<'_, Mutex<AppState>>, decision_id: String) -> Result<(), String> {
-    let mut state = state.lock().map_err(|e| e.to_string())?;
+    let state = state.lock().map_err(|e| e.to_string())?;
     if let Some(flag) = state.debate_cancel_flags.get(&decision_id) {
         flag.store(true, std::sync::atomic::Ordering::Relaxed);
     }
-    state.db.update_decision_status(&decision_id, "analyzing").map_err(db_err)?;
-    state.debate_cancel_flags.remove(&decision_id);
-    Ok(())
+    state.db.update_decision_status(&decision_id, "analyzing").map_err(db_err)
+}
+
+/// Clear a decision's debate (rounds, audio, on-disk files) so it can be re-run.
+#[tauri::command]
+pub fn reset_debate(state: State<'_, Mutex<AppState>>, decision_id: String) -> Result<debate::DebateReset, String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    if state.debate_cancel_flags.contains_key(&decision_id) {
+        return Err("Cannot reset a debate while it is running".to_string());
+    }
+    debate::reset_debate(&state.db, &state.app_data_dir, &decision_id)
 }
 
 // ── Audio Commands ──
 
+/// Generate a short clip in an agent's voice and return the MP3 path.
+#[tauri::command]
+pub async fn preview_voice(
+    state: State<'_, Mutex<AppState>>,
+    agent_key: String,
+    sample_text: Option<String>,
+) -> Result<String, String> {
+    let (config, registry, app_data_dir) = {
+        let state = state.lock().map_err(|e| e.to_string())?;
+        (
+            config::load_config(&state.app_data_dir),
+            agents::load_registry(&state.app_data_dir),
+            state.app_data_dir.clone(),
+        )
+    };
+    let path = tts::generate_voice_preview(&config, &registry, &app_data_dir, &agent_key, sample_text.as_deref()).await?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Generate one clip of arbitrary text in an agent's voice (the moderator's by
+/// default) to check TTS settings. Returns the temp MP3 path.
+#[tauri::command]
+pub async fn tts_test(
+    state: State<'_, Mutex<AppState>>,
+    text: String,
+    agent_key: Option<String>,
+) -> Result<String, String> {
+    let (config, registry) = {
+        let state = state.lock().map_err(|e| e.to_string())?;
+        (
+            config::load_config(&state.app_data_dir),
+            agents::load_registry(&state.app_data_dir),
+        )
+    };
+    let path = tts::generate_test_clip(&config, &registry, agent_key.as_deref(), &text).await?;
+    Ok(path.to_string_lossy().to_string())
+}
+
 #[tauri::command]
 pub async fn generate_debate_audio(
     app_handle: tauri::AppHandle,
@@ -691,19 +1871,65 @@ pub async fn generate_debate_audio(
         &app_data_dir,
     ).await?;
 
-    // Save to DB
-    let manifest_json = serde_json::to_string_pretty(&manifest).map_err(|e| e.to_string())?;
-    {
+    debate::finish_debate_audio(&app_handle, &manifest)?;
+    Ok(manifest)
+}
+
+/// Narrate an already-finished debate in the background. Returns once the job is
+/// queued; progress and the final manifest arrive via `audio-generation-*` events.
+/// Does nothing if audio already exists, unless `force` is set.
+#[tauri::command]
+pub fn generate_audio_for_debate(
+    app_handle: tauri::AppHandle,
+    state: State<'_, Mutex<AppState>>,
+    decision_id: String,
+    force: Option<bool>,
+) -> Result<(), String> {
+    let (app_data_dir, rounds) = {
         let state = state.lock().map_err(|e| e.to_string())?;
-        state.db.save_debate_audio(
-            &decision_id,
-            &manifest_json,
-            manifest.total_duration_ms as i64,
-            &app_data_dir.join("debates").join(&decision_id).to_string_lossy(),
-        ).map_err(db_err)?;
+        if state.db.get_debate_audio(&decision_id).map_err(db_err)?.is_some() && !force.unwrap_or(false) {
+            return Ok(());
+        }
+        let rounds = state.db.get_debate_rounds(&decision_id).map_err(db_err)?;
+        (state.app_data_dir.clone(), rounds)
+    };
+
+    let config = config::load_config(&app_data_dir);
+    tts::tts_api_key(&config)?;
+
+    if rounds.is_empty() {
+        return Err("No debate rounds found for this decision.".into());
     }
 
-    Ok(manifest)
+    let registry = agents::load_registry(&app_data_dir);
+
+    tokio::spawn(async move {
+        let result = tts::generate_debate_audio(
+            &app_handle,
+            &decision_id,
+            &rounds,
+            &config,
+            &registry,
+            &app_data_dir,
+        ).await;
+
+        match result {
+            Ok(manifest) => {
+                if let Err(e) = debate::finish_debate_audio(&app_handle, &manifest) {
+                    eprintln!("[TTS] Failed to save audio for {}: {}", decision_id, e);
+                }
+            }
+            Err(e) => {
+                eprintln!("[TTS] Audio generation failed for {}: {}", decision_id, e);
+                let _ = tauri::Emitter::emit(&app_handle, "audio-generation-error", json!({
+                    "decision_id": decision_id,
+                    "error": e,
+                }));
+            }
+        }
+    });
+
+    Ok(())
 }
 
 #[tauri::command]
@@ -715,6 +1941,61 @@ pub fn get_debate_audio(
     state.db.get_debate_audio(&decision_id).map_err(db_err)
 }
 
+/// Live audio recorded so far for a debate that never finished (e.g. the app crashed).
+#[tauri::command]
+pub fn get_partial_debate_audio(
+    state: State<'_, Mutex<AppState>>,
+    decision_id: String,
+) -> Result<Option<tts::AudioManifest>, String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    Ok(tts::load_partial_manifest(&state.app_data_dir, &decision_id))
+}
+
+/// Bytes of narrated audio stored for one decision.
+#[tauri::command]
+pub fn get_debate_audio_size(state: State<'_, Mutex<AppState>>, decision_id: String) -> Result<u64, String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    Ok(tts::debate_audio_size(&state.app_data_dir, &decision_id))
+}
+
+/// Bytes of narrated audio stored across all debates.
+#[tauri::command]
+pub fn get_total_audio_size(state: State<'_, Mutex<AppState>>) -> Result<u64, String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    Ok(tts::total_audio_size(&state.app_data_dir))
+}
+
+/// Delete a decision's narrated audio (files and manifest) to free disk space.
+/// The transcript is kept, so audio can be generated again later.
+#[tauri::command]
+pub fn clear_debate_audio(state: State<'_, Mutex<AppState>>, decision_id: String) -> Result<(), String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    state
+        .db
+        .get_decision(&decision_id)
+        .map_err(db_err)?
+        .ok_or_else(|| "Decision not found".to_string())?;
+    tts::remove_debate_audio_dir(&state.app_data_dir, &decision_id)?;
+    state.db.delete_debate_audio(&decision_id).map_err(db_err)?;
+    Ok(())
+}
+
+/// Audio folders left behind by decisions that no longer exist.
+#[tauri::command]
+pub fn find_orphaned_audio(state: State<'_, Mutex<AppState>>) -> Result<Vec<String>, String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    let decision_ids = state.db.get_decision_ids().map_err(db_err)?;
+    Ok(tts::find_orphaned_audio(&state.app_data_dir, &decision_ids))
+}
+
+/// Delete orphaned audio folders and return the bytes reclaimed.
+#[tauri::command]
+pub fn prune_orphaned_audio(state: State<'_, Mutex<AppState>>) -> Result<u64, String> {
+    let state = state.lock().map_err(|e| e.to_string())?;
+    let decision_ids = state.db.get_decision_ids().map_err(db_err)?;
+    tts::prune_orphaned_audio(&state.app_data_dir, &decision_ids)
+}
+
 // ── Standalone Debate Commands ──
 
 #[tauri::command]
@@ -828,28 +2109,27 @@ pub async fn start_standalone_debate(
         .filter(|agent| agent.role == "debater")
         .map(|agent| agent.key.clone())
         .collect::<Vec<_>>();
-    let participants = sandbox.participants.clone();
-    let model_map = sandbox.model_map.clone();
-    let standalone_config = normalized_config.clone();
+    let request = debate::DebateRequest {
+        shape: debate::DebateShape::from_quick_mode(quick_mode),
+        selected_agent_keys: Some(selected),
+        standalone: Some(debate::StandaloneDebate {
+            brief,
+            participants: sandbox.participants.clone(),
+            model_map: sandbox.model_map.clone(),
+            config: normalized_config.clone(),
+        }),
+        ..Default::default()
+    };
 
     tokio::spawn(async move {
-        if let Err(e) = debate::run_debate(
-            app_handle.clone(),
-            dec_id.clone(),
-            quick_mode,
-            cancel_flag,
-            Some(selected),
-            Some(brief),
-            Some(participants),
-            Some(model_map),
-            Some(standalone_config),
-        ).await {
+        if let Err(e) = debate::run_debate(app_handle.clone(), dec_id.clone(), cancel_flag.clone(), request).await {
             eprintln!("Standalone debate error: {}", e);
             let _ = tauri::Emitter::emit(&app_handle, "debate-error", serde_json::json!({
                 "decision_id": dec_id,
                 "error": e,
             }));
         }
+        release_cancel_flag(&app_handle, &dec_id, &cancel_flag);
     });
 
     Ok(())
@@ -1,24 +1,138 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AppConfig {
     #[serde(default)]
     pub openrouter_api_key: String,
+    #[serde(default = "default_llm_provider")]
+    pub llm_provider: String, // "openrouter", "openai", or "ollama"
+    #[serde(default)]
+    pub openai_api_key: String,
+    #[serde(default = "default_ollama_base_url")]
+    pub ollama_base_url: String,
     #[serde(default = "default_model")]
     pub model: String,
     #[serde(default)]
     pub agent_models: HashMap<String, String>,
     #[serde(default)]
+    pub default_moderator_model: String, // used when agent_models has no "moderator" entry; empty = model
+    #[serde(default)]
     pub elevenlabs_api_key: String,
     #[serde(default = "default_tts_provider")]
     pub tts_provider: String, // "elevenlabs" or "openai"
+    #[serde(default = "default_tts_mode")]
+    pub tts_mode: String, // "off", "live", or "post"
     #[serde(default = "default_elevenlabs_model")]
     pub elevenlabs_model: String,
     #[serde(default)]
     pub voices: HashMap<String, String>, // agent_key -> voice_id overrides
+    #[serde(default)]
+    pub elevenlabs_voice_settings: HashMap<String, ElevenLabsVoiceSettings>, // agent_key -> ElevenLabs tuning overrides
+    #[serde(default = "default_segment_gap_ms")]
+    pub segment_gap_ms: u64, // silence between debate audio segments
+    #[serde(default = "default_chat_max_tokens")]
+    pub chat_max_tokens: u32,
+    #[serde(default = "default_debate_max_tokens")]
+    pub debate_max_tokens: u32,
+    #[serde(default)]
+    pub profile_versioning: bool, // snapshot profile files before overwriting
+    #[serde(default = "default_request_timeout_secs")]
+    pub request_timeout_secs: u64, // per-request limit for LLM and TTS calls
+    #[serde(default = "default_parallel_opening")]
+    pub parallel_opening: bool, // run Round 1 opening statements concurrently
+    #[serde(default = "default_context_token_budget")]
+    pub context_token_budget: u32, // estimated tokens of chat history sent per turn
+    #[serde(default = "default_debate_temperature")]
+    pub debate_temperature: f32, // sampling temperature for debate turns; chat keeps its own
+    #[serde(default)]
+    pub inline_reasoning: bool, // show reasoning-model thinking as part of the reply text
+    #[serde(default)]
+    pub shuffle_speaking_order: bool, // shuffle debaters after the opening round instead of rotating
+    #[serde(default = "default_min_response_chars")]
+    pub min_response_chars: usize, // shorter debate replies (after cleanup) count as failures and are retried
+    #[serde(default)]
+    pub auto_retitle_decisions: bool, // let the assistant's `title` in update_decision_summary rename the decision
+    #[serde(default)]
+    pub compress_long_transcripts: bool, // summarize rounds before the moderator when the transcript is too long
+    #[serde(default = "default_moderator_transcript_budget")]
+    pub moderator_transcript_budget: u32, // estimated transcript tokens the moderator gets before compression kicks in
+    #[serde(default)]
+    pub transcript_summary_model: String, // model used to summarize rounds; empty = model
+    #[serde(default = "default_tts_concurrency")]
+    pub tts_concurrency: usize, // live TTS requests allowed in flight at once
+    #[serde(default = "default_tts_retry_attempts")]
+    pub tts_retry_attempts: u32, // tries per TTS request before a segment is given up on; retries only 429/5xx
+    #[serde(default)]
+    pub models_without_tools: Vec<String>, // chat models that can't do function calling; they get the JSON action-block prompt instead
+    #[serde(default)]
+    pub token_flush_interval_ms: u64, // coalesce chat tokens for this long before emitting; 0 sends every token as it arrives
+    #[serde(default = "default_min_debaters")]
+    pub min_debaters: usize, // fewest debaters a committee debate may start with; 1 allows a single-perspective review
+    #[serde(default)]
+    pub debate_default_model: String, // fallback for debaters without an agent_models entry; empty = model
+    #[serde(default = "default_brief_conversation_char_limit")]
+    pub brief_conversation_char_limit: usize, // conversation context in the debate brief keeps the most recent messages within this many chars; 0 = no limit
+    #[serde(default = "default_apply_spoken_overlay")]
+    pub apply_spoken_overlay: bool, // append the spoken-style overlay to debater prompts; off leaves custom personas' formatting alone
+}
+
+/// Per-agent ElevenLabs tuning. Unset fields keep the persona defaults.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct ElevenLabsVoiceSettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub voice_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stability: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub similarity_boost: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub style: Option<f32>,
+}
+
+impl ElevenLabsVoiceSettings {
+    /// Reject tuning values outside ElevenLabs' 0.0-1.0 range.
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, value) in [
+            ("stability", self.stability),
+            ("similarity_boost", self.similarity_boost),
+            ("style", self.style),
+        ] {
+            if let Some(v) = value {
+                if !(0.0..=1.0).contains(&v) {
+                    return Err(format!("{} must be between 0 and 1", name));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.voice_id.is_none() && self.stability.is_none() && self.similarity_boost.is_none() && self.style.is_none()
+    }
+}
+
+/// Smallest max_tokens we accept; anything lower truncates even short replies.
+pub const MIN_MAX_TOKENS: u32 = 256;
+
+/// Debate sampling temperature when the config doesn't hold a usable one.
+pub const DEFAULT_DEBATE_TEMPERATURE: f32 = 0.8;
+/// Highest sampling temperature providers accept.
+pub const MAX_TEMPERATURE: f32 = 2.0;
+
+/// Shortest request timeout we accept; streamed debate turns routinely take longer.
+pub const MIN_REQUEST_TIMEOUT_SECS: u64 = 10;
+
+fn default_llm_provider() -> String {
+    "openrouter".to_string()
+}
+
+fn default_ollama_base_url() -> String {
+    "http://localhost:11434/v1/chat/completions".to_string()
 }
 
 fn default_model() -> String {
@@ -29,20 +143,135 @@ fn default_tts_provider() -> String {
     "elevenlabs".to_string()
 }
 
+fn default_tts_mode() -> String {
+    "live".to_string()
+}
+
 fn default_elevenlabs_model() -> String {
     "eleven_flash_v2_5".to_string()
 }
 
+fn default_segment_gap_ms() -> u64 {
+    400
+}
+
+fn default_chat_max_tokens() -> u32 {
+    4096
+}
+
+fn default_debate_max_tokens() -> u32 {
+    2048
+}
+
+pub fn default_request_timeout_secs() -> u64 {
+    120
+}
+
+fn default_parallel_opening() -> bool {
+    true
+}
+
+fn default_context_token_budget() -> u32 {
+    100_000
+}
+
+fn default_moderator_transcript_budget() -> u32 {
+    24_000
+}
+
+fn default_min_response_chars() -> usize {
+    20
+}
+
+fn default_tts_concurrency() -> usize {
+    2
+}
+
+fn default_tts_retry_attempts() -> u32 {
+    3
+}
+
+fn default_min_debaters() -> usize {
+    2
+}
+
+fn default_brief_conversation_char_limit() -> usize {
+    40_000
+}
+
+fn default_apply_spoken_overlay() -> bool {
+    true
+}
+
+fn default_debate_temperature() -> f32 {
+    DEFAULT_DEBATE_TEMPERATURE
+}
+
+/// Clamp a temperature into 0..=MAX_TEMPERATURE, or None if it isn't a number.
+pub fn clamp_temperature(value: f32) -> Option<f32> {
+    value.is_finite().then(|| value.clamp(0.0, MAX_TEMPERATURE))
+}
+
+pub fn validate_max_tokens(value: u32) -> Result<u32, String> {
+    if value < MIN_MAX_TOKENS {
+        return Err(format!("max_tokens must be at least {}", MIN_MAX_TOKENS));
+    }
+    Ok(value)
+}
+
+pub fn validate_request_timeout_secs(value: u64) -> Result<u64, String> {
+    if value < MIN_REQUEST_TIMEOUT_SECS {
+        return Err(format!("request timeout must be at least {} seconds", MIN_REQUEST_TIMEOUT_SECS));
+    }
+    Ok(value)
+}
+
+pub fn validate_min_debaters(value: usize) -> Result<usize, String> {
+    if value < 1 {
+        return Err("the minimum number of debaters must be at least 1".to_string());
+    }
+    Ok(value)
+}
+
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
             openrouter_api_key: String::new(),
+            llm_provider: default_llm_provider(),
+            openai_api_key: String::new(),
+            ollama_base_url: default_ollama_base_url(),
             model: default_model(),
             agent_models: HashMap::new(),
+            default_moderator_model: String::new(),
             elevenlabs_api_key: String::new(),
             tts_provider: default_tts_provider(),
+            tts_mode: default_tts_mode(),
             elevenlabs_model: default_elevenlabs_model(),
             voices: HashMap::new(),
+            elevenlabs_voice_settings: HashMap::new(),
+            segment_gap_ms: default_segment_gap_ms(),
+            chat_max_tokens: default_chat_max_tokens(),
+            debate_max_tokens: default_debate_max_tokens(),
+            profile_versioning: false,
+            request_timeout_secs: default_request_timeout_secs(),
+            parallel_opening: default_parallel_opening(),
+            context_token_budget: default_context_token_budget(),
+            debate_temperature: default_debate_temperature(),
+            inline_reasoning: false,
+            shuffle_speaking_order: false,
+            min_response_chars: default_min_response_chars(),
+            auto_retitle_decisions: false,
+            compress_long_transcripts: false,
+            moderator_transcript_budget: default_moderator_transcript_budget(),
+            transcript_summary_model: String::new(),
+            tts_concurrency: default_tts_concurrency(),
+            tts_retry_attempts: default_tts_retry_attempts(),
+            models_without_tools: Vec::new(),
+            token_flush_interval_ms: 0,
+            min_debaters: default_min_debaters(),
+            debate_default_model: String::new(),
+            brief_conversation_char_limit: default_brief_conversation_char_limit(),
+            apply_spoken_overlay: default_apply_spoken_overlay(),
         }
     }
 }
@@ -63,7 +292,35 @@ pub fn save_config(app_data_dir: &PathBuf, config: &AppConfig) -> Result<(), Str
     let path = get_config_path(app_data_dir);
     fs::create_dir_all(app_data_dir).map_err(|e| e.to_string())?;
     let content = serde_json::to_string_pretty(config).map_err(|e| e.to_string())?;
-    fs::write(&path, content).map_err(|e| e.to_string())?;
+    write_atomic(&path, content.as_bytes())
+}
+
+/// Write `contents` to a temp file beside `path`, then rename it over the target,
+/// so a crash mid-write leaves the old file intact instead of truncated JSON.
+pub fn write_atomic(path: &Path, contents: &[u8]) -> Result<(), String> {
+    static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+    let dir = path.parent().ok_or_else(|| format!("No parent directory for {}", path.display()))?;
+    let name = path
+        .file_name()
+        .ok_or_else(|| format!("No file name in {}", path.display()))?
+        .to_string_lossy();
+    let temp_path = dir.join(format!(
+        ".{}.{}-{}.tmp",
+        name,
+        std::process::id(),
+        TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    let result = fs::File::create(&temp_path)
+        .and_then(|mut file| {
+            file.write_all(contents)?;
+            file.sync_all()
+        })
+        .and_then(|_| fs::rename(&temp_path, path));
+    if let Err(e) = result {
+        let _ = fs::remove_file(&temp_path);
+        return Err(e.to_string());
+    }
     Ok(())
 }
 
@@ -80,12 +337,41 @@ mod tests {
         let loaded = load_config(&app_data_dir);
 
         assert!(loaded.openrouter_api_key.is_empty());
+        assert_eq!(loaded.llm_provider, "openrouter");
+        assert!(loaded.openai_api_key.is_empty());
+        assert_eq!(loaded.ollama_base_url, "http://localhost:11434/v1/chat/completions");
         assert_eq!(loaded.model, "anthropic/claude-sonnet-4-5");
         assert!(loaded.agent_models.is_empty());
+        assert!(loaded.default_moderator_model.is_empty());
         assert!(loaded.elevenlabs_api_key.is_empty());
         assert_eq!(loaded.tts_provider, "elevenlabs");
+        assert_eq!(loaded.tts_mode, "live");
         assert_eq!(loaded.elevenlabs_model, "eleven_flash_v2_5");
         assert!(loaded.voices.is_empty());
+        assert!(loaded.elevenlabs_voice_settings.is_empty());
+        assert_eq!(loaded.segment_gap_ms, 400);
+        assert_eq!(loaded.chat_max_tokens, 4096);
+        assert_eq!(loaded.debate_max_tokens, 2048);
+        assert!(!loaded.profile_versioning);
+        assert_eq!(loaded.request_timeout_secs, 120);
+        assert!(loaded.parallel_opening);
+        assert_eq!(loaded.context_token_budget, 100_000);
+        assert_eq!(loaded.debate_temperature, DEFAULT_DEBATE_TEMPERATURE);
+        assert!(!loaded.inline_reasoning);
+        assert!(!loaded.shuffle_speaking_order);
+        assert_eq!(loaded.min_response_chars, 20);
+        assert!(!loaded.auto_retitle_decisions);
+        assert!(!loaded.compress_long_transcripts);
+        assert_eq!(loaded.moderator_transcript_budget, 24_000);
+        assert!(loaded.transcript_summary_model.is_empty());
+        assert_eq!(loaded.tts_concurrency, 2);
+        assert_eq!(loaded.tts_retry_attempts, 3);
+        assert!(loaded.models_without_tools.is_empty());
+        assert_eq!(loaded.token_flush_interval_ms, 0);
+        assert_eq!(loaded.min_debaters, 2);
+        assert!(loaded.debate_default_model.is_empty());
+        assert_eq!(loaded.brief_conversation_char_limit, 40_000);
+        assert!(loaded.apply_spoken_overlay);
     }
 
     #[test]
@@ -98,26 +384,87 @@ mod tests {
 
         let config = AppConfig {
             openrouter_api_key: "sk-test-key".to_string(),
+            llm_provider: "openai".to_string(),
+            openai_api_key: "sk-openai-test".to_string(),
+            ollama_base_url: "http://127.0.0.1:11434/v1/chat/completions".to_string(),
             model: "anthropic/claude-sonnet-4-5".to_string(),
             agent_models,
+            default_moderator_model: "anthropic/claude-opus-4".to_string(),
             elevenlabs_api_key: "sk-eleven-test".to_string(),
             tts_provider: "openai".to_string(),
+            tts_mode: "post".to_string(),
             elevenlabs_model: "eleven_turbo_v2_5".to_string(),
             voices: HashMap::new(),
+            elevenlabs_voice_settings: HashMap::from([(
+                "contrarian".to_string(),
+                ElevenLabsVoiceSettings { stability: Some(0.2), ..Default::default() },
+            )]),
+            segment_gap_ms: 250,
+            chat_max_tokens: 8192,
+            debate_max_tokens: 1024,
+            profile_versioning: true,
+            request_timeout_secs: 45,
+            parallel_opening: false,
+            context_token_budget: 32_000,
+            debate_temperature: 1.1,
+            inline_reasoning: true,
+            shuffle_speaking_order: true,
+            min_response_chars: 40,
+            auto_retitle_decisions: true,
+            compress_long_transcripts: true,
+            moderator_transcript_budget: 8_000,
+            transcript_summary_model: "openai/gpt-4o-mini".to_string(),
+            tts_concurrency: 4,
+            tts_retry_attempts: 5,
+            models_without_tools: vec!["meta-llama/llama-3-8b-instruct".to_string()],
+            token_flush_interval_ms: 30,
+            min_debaters: 1,
+            debate_default_model: "openai/gpt-4o-mini".to_string(),
+            brief_conversation_char_limit: 12_000,
+            apply_spoken_overlay: false,
         };
 
         save_config(&app_data_dir, &config).expect("config should save");
         let loaded = load_config(&app_data_dir);
 
         assert_eq!(loaded.openrouter_api_key, "sk-test-key");
+        assert_eq!(loaded.llm_provider, "openai");
+        assert_eq!(loaded.openai_api_key, "sk-openai-test");
         assert_eq!(loaded.model, "anthropic/claude-sonnet-4-5");
         assert_eq!(
             loaded.agent_models.get("moderator").map(String::as_str),
             Some("anthropic/custom-model")
         );
+        assert_eq!(loaded.default_moderator_model, "anthropic/claude-opus-4");
         assert_eq!(loaded.elevenlabs_api_key, "sk-eleven-test");
         assert_eq!(loaded.tts_provider, "openai");
+        assert_eq!(loaded.tts_mode, "post");
         assert_eq!(loaded.elevenlabs_model, "eleven_turbo_v2_5");
+        assert_eq!(loaded.segment_gap_ms, 250);
+        assert_eq!(loaded.chat_max_tokens, 8192);
+        assert_eq!(loaded.debate_max_tokens, 1024);
+        assert!(loaded.profile_versioning);
+        assert_eq!(loaded.request_timeout_secs, 45);
+        assert!(!loaded.parallel_opening);
+        assert_eq!(loaded.context_token_budget, 32_000);
+        assert_eq!(loaded.debate_temperature, 1.1);
+        assert!(loaded.inline_reasoning);
+        assert!(loaded.shuffle_speaking_order);
+        assert_eq!(loaded.min_response_chars, 40);
+        assert!(loaded.auto_retitle_decisions);
+        assert_eq!(loaded.elevenlabs_voice_settings["contrarian"].stability, Some(0.2));
+        assert_eq!(loaded.elevenlabs_voice_settings["contrarian"].style, None);
+        assert!(loaded.compress_long_transcripts);
+        assert_eq!(loaded.moderator_transcript_budget, 8_000);
+        assert_eq!(loaded.transcript_summary_model, "openai/gpt-4o-mini");
+        assert_eq!(loaded.tts_concurrency, 4);
+        assert_eq!(loaded.tts_retry_attempts, 5);
+        assert_eq!(loaded.models_without_tools, vec!["meta-llama/llama-3-8b-instruct"]);
+        assert_eq!(loaded.token_flush_interval_ms, 30);
+        assert_eq!(loaded.min_debaters, 1);
+        assert_eq!(loaded.debate_default_model, "openai/gpt-4o-mini");
+        assert_eq!(loaded.brief_conversation_char_limit, 12_000);
+        assert!(!loaded.apply_spoken_overlay);
     }
 
     #[test]
@@ -135,5 +482,42 @@ mod tests {
         assert!(loaded.elevenlabs_api_key.is_empty());
         assert_eq!(loaded.tts_provider, "elevenlabs");
         assert_eq!(loaded.elevenlabs_model, "eleven_flash_v2_5");
+        assert_eq!(loaded.chat_max_tokens, 4096);
+        assert_eq!(loaded.debate_max_tokens, 2048);
+    }
+
+    #[test]
+    fn integration_atomic_writes_leave_no_temp_files() {
+        let dir = tempdir().expect("temp directory should exist");
+        let app_data_dir = dir.path().to_path_buf();
+
+        let mut config = AppConfig::default();
+        save_config(&app_data_dir, &config).expect("first save should succeed");
+        config.model = "openai/gpt-4o".to_string();
+        save_config(&app_data_dir, &config).expect("overwrite should succeed");
+        assert_eq!(load_config(&app_data_dir).model, "openai/gpt-4o");
+
+        let entries: Vec<String> = std::fs::read_dir(&app_data_dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(entries, vec!["config.json".to_string()]);
+
+        let missing_dir = app_data_dir.join("missing").join("config.json");
+        assert!(write_atomic(&missing_dir, b"{}").is_err());
+    }
+
+    #[test]
+    fn unit_validate_max_tokens_enforces_minimum() {
+        assert!(validate_max_tokens(MIN_MAX_TOKENS - 1).is_err());
+        assert_eq!(validate_max_tokens(MIN_MAX_TOKENS), Ok(MIN_MAX_TOKENS));
+        assert_eq!(validate_max_tokens(4096), Ok(4096));
+    }
+
+    #[test]
+    fn unit_validate_min_debaters_allows_a_single_perspective() {
+        assert!(validate_min_debaters(0).is_err());
+        assert_eq!(validate_min_debaters(1), Ok(1));
+        assert_eq!(validate_min_debaters(3), Ok(3));
     }
 }
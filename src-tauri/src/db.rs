@@ -1,5 +1,6 @@
 use rusqlite::{Connection, params};
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::sync::Mutex;
 use uuid::Uuid;
 use chrono::Utc;
@@ -34,6 +35,9 @@ pub struct Decision {
     pub user_choice_reasoning: Option<String>,
     pub outcome: Option<String>,
     pub outcome_date: Option<String>,
+    pub outcome_score: Option<i64>,
+    /// Deadline for making the call, RFC3339 in UTC.
+    pub due_date: Option<String>,
     pub debate_brief: Option<String>,
     pub debate_started_at: Option<String>,
     pub debate_completed_at: Option<String>,
@@ -62,6 +66,111 @@ pub struct DebateAudio {
     pub audio_dir: String,
 }
 
+/// Lightweight decision row for list views.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DecisionSummaryRow {
+    pub id: String,
+    pub conversation_id: String,
+    pub title: String,
+    pub status: String,
+    pub updated_at: String,
+    pub has_audio: bool,
+}
+
+/// How many earlier summaries each decision keeps for `undo_summary_update`.
+pub const SUMMARY_HISTORY_DEPTH: i64 = 10;
+
+/// Current layout version of `DataBundle`; bump when its shape changes.
+pub const DATA_BUNDLE_VERSION: u32 = 3;
+
+/// A conversation row as it appears in an export, including its prompt override.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExportedConversation {
+    #[serde(flatten)]
+    pub conversation: Conversation,
+    #[serde(default)]
+    pub system_prompt_override: Option<String>,
+    #[serde(default)]
+    pub deleted_at: Option<String>,
+}
+
+/// A decision row as it appears in an export, including the roster and shape a
+/// debate was started with, so an interrupted debate can still be resumed.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExportedDecision {
+    #[serde(flatten)]
+    pub decision: Decision,
+    #[serde(default)]
+    pub debate_setup_json: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExportedTag {
+    pub decision_id: String,
+    pub tag: String,
+}
+
+/// An earlier decision summary kept for undo. Exported oldest first without its
+/// row id, so importing alongside existing rows can't collide.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExportedSummaryHistory {
+    pub decision_id: String,
+    pub summary_json: Option<String>,
+    pub created_at: String,
+}
+
+/// Every row in the database, for backup and migration between machines.
+/// Ids are kept as-is so foreign keys still line up after import.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DataBundle {
+    pub version: u32,
+    pub exported_at: String,
+    pub conversations: Vec<ExportedConversation>,
+    pub messages: Vec<Message>,
+    pub decisions: Vec<ExportedDecision>,
+    pub debate_rounds: Vec<DebateRound>,
+    pub debate_audio: Vec<DebateAudio>,
+    #[serde(default)]
+    pub tags: Vec<ExportedTag>,
+    #[serde(default)]
+    pub summary_history: Vec<ExportedSummaryHistory>,
+}
+
+/// Columns read into a `Decision`, in the order `row_to_decision` expects.
+/// Queries select them from `decisions` aliased as `d`.
+const DECISION_COLUMNS: &str = "d.id, d.conversation_id, d.title, d.status, d.summary_json, d.user_choice, d.user_choice_reasoning, d.outcome, d.outcome_date, d.debate_brief, d.debate_started_at, d.debate_completed_at, d.created_at, d.updated_at, d.outcome_score, d.due_date";
+
+fn row_to_decision(row: &rusqlite::Row) -> rusqlite::Result<Decision> {
+    Ok(Decision {
+        id: row.get(0)?,
+        conversation_id: row.get(1)?,
+        title: row.get(2)?,
+        status: row.get(3)?,
+        summary_json: row.get(4)?,
+        user_choice: row.get(5)?,
+        user_choice_reasoning: row.get(6)?,
+        outcome: row.get(7)?,
+        outcome_date: row.get(8)?,
+        debate_brief: row.get(9)?,
+        debate_started_at: row.get(10)?,
+        debate_completed_at: row.get(11)?,
+        created_at: row.get(12)?,
+        updated_at: row.get(13)?,
+        outcome_score: row.get(14)?,
+        due_date: row.get(15)?,
+    })
+}
+
+/// Lifecycle statuses a decision (not a standalone debate) can be in.
+pub const DECISION_STATUSES: [&str; 6] = [
+    "exploring",
+    "analyzing",
+    "debating",
+    "recommended",
+    "decided",
+    "reviewed",
+];
+
 pub struct Database {
     conn: Mutex<Connection>,
 }
@@ -79,6 +188,8 @@ impl Database {
                 id TEXT PRIMARY KEY,
                 title TEXT NOT NULL,
                 type TEXT NOT NULL DEFAULT 'chat',
+                system_prompt_override TEXT,
+                deleted_at TEXT,
                 created_at TEXT NOT NULL,
                 updated_at TEXT NOT NULL
             );
@@ -100,6 +211,8 @@ impl Database {
                 user_choice_reasoning TEXT,
                 outcome TEXT,
                 outcome_date TEXT,
+                outcome_score INTEGER,
+                due_date TEXT,
                 debate_brief TEXT,
                 debate_started_at TEXT,
                 debate_completed_at TEXT,
@@ -126,6 +239,21 @@ impl Database {
                 audio_dir TEXT NOT NULL,
                 FOREIGN KEY (decision_id) REFERENCES decisions(id)
             );
+            CREATE TABLE IF NOT EXISTS tags (
+                decision_id TEXT NOT NULL,
+                tag TEXT NOT NULL,
+                PRIMARY KEY (decision_id, tag),
+                FOREIGN KEY (decision_id) REFERENCES decisions(id)
+            );
+            CREATE TABLE IF NOT EXISTS summary_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                decision_id TEXT NOT NULL,
+                summary_json TEXT,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (decision_id) REFERENCES decisions(id)
+            );
+            CREATE INDEX IF NOT EXISTS idx_decisions_status ON decisions(status);
+            CREATE INDEX IF NOT EXISTS idx_summary_history_decision ON summary_history(decision_id, id);
         ")?;
 
         // Migration: add type column if missing (existing databases)
@@ -138,6 +266,26 @@ impl Database {
             conn.execute_batch("ALTER TABLE conversations ADD COLUMN type TEXT NOT NULL DEFAULT 'chat';")?;
         }
 
+        // Migration: add per-conversation system prompt override if missing
+        let has_prompt_override: bool = conn
+            .prepare("SELECT COUNT(*) FROM pragma_table_info('conversations') WHERE name='system_prompt_override'")
+            .and_then(|mut s| s.query_row([], |r| r.get::<_, i64>(0)))
+            .map(|c| c > 0)
+            .unwrap_or(false);
+        if !has_prompt_override {
+            conn.execute_batch("ALTER TABLE conversations ADD COLUMN system_prompt_override TEXT;")?;
+        }
+
+        // Migration: add soft-delete timestamp to conversations if missing
+        let has_deleted_at: bool = conn
+            .prepare("SELECT COUNT(*) FROM pragma_table_info('conversations') WHERE name='deleted_at'")
+            .and_then(|mut s| s.query_row([], |r| r.get::<_, i64>(0)))
+            .map(|c| c > 0)
+            .unwrap_or(false);
+        if !has_deleted_at {
+            conn.execute_batch("ALTER TABLE conversations ADD COLUMN deleted_at TEXT;")?;
+        }
+
         // Migration: add debate columns to decisions table if missing
         let has_debate_brief: bool = conn
             .prepare("SELECT COUNT(*) FROM pragma_table_info('decisions') WHERE name='debate_brief'")
@@ -152,6 +300,43 @@ impl Database {
             ")?;
         }
 
+        // Migration: add outcome_score to decisions table if missing
+        let has_outcome_score: bool = conn
+            .prepare("SELECT COUNT(*) FROM pragma_table_info('decisions') WHERE name='outcome_score'")
+            .and_then(|mut s| s.query_row([], |r| r.get::<_, i64>(0)))
+            .map(|c| c > 0)
+            .unwrap_or(false);
+        if !has_outcome_score {
+            conn.execute_batch("ALTER TABLE decisions ADD COLUMN outcome_score INTEGER;")?;
+        }
+
+        // Migration: add due_date to decisions table if missing
+        let has_due_date: bool = conn
+            .prepare("SELECT COUNT(*) FROM pragma_table_info('decisions') WHERE name='due_date'")
+            .and_then(|mut s| s.query_row([], |r| r.get::<_, i64>(0)))
+            .map(|c| c > 0)
+            .unwrap_or(false);
+        if !has_due_date {
+            conn.execute_batch("ALTER TABLE decisions ADD COLUMN due_date TEXT;")?;
+        }
+
+        // Migration: add debate_setup_json to decisions table if missing
+        let has_debate_setup: bool = conn
+            .prepare("SELECT COUNT(*) FROM pragma_table_info('decisions') WHERE name='debate_setup_json'")
+            .and_then(|mut s| s.query_row([], |r| r.get::<_, i64>(0)))
+            .map(|c| c > 0)
+            .unwrap_or(false);
+        if !has_debate_setup {
+            conn.execute_batch("ALTER TABLE decisions ADD COLUMN debate_setup_json TEXT;")?;
+        }
+
+        // Foreign-key lookup indexes. Created after the column migrations so databases
+        // from before these existed pick them up on the next launch.
+        conn.execute_batch("
+            CREATE INDEX IF NOT EXISTS idx_messages_conv ON messages(conversation_id, created_at);
+            CREATE INDEX IF NOT EXISTS idx_rounds_decision ON debate_rounds(decision_id, round_number, exchange_number);
+        ")?;
+
         // Migration: repair rows written with generated_at/audio_dir swapped.
         conn.execute_batch(
             r#"
@@ -183,7 +368,7 @@ impl Database {
 
     pub fn get_conversations(&self) -> Result<Vec<Conversation>, rusqlite::Error> {
         let conn = self.conn.lock().unwrap();
-        let mut stmt = conn.prepare("SELECT id, title, type, created_at, updated_at FROM conversations ORDER BY updated_at DESC")?;
+        let mut stmt = conn.prepare("SELECT id, title, type, created_at, updated_at FROM conversations WHERE deleted_at IS NULL ORDER BY updated_at DESC")?;
         let rows = stmt.query_map([], |row| {
             Ok(Conversation {
                 id: row.get(0)?,
@@ -198,7 +383,7 @@ impl Database {
 
     pub fn get_conversations_by_type(&self, conv_type: &str) -> Result<Vec<Conversation>, rusqlite::Error> {
         let conn = self.conn.lock().unwrap();
-        let mut stmt = conn.prepare("SELECT id, title, type, created_at, updated_at FROM conversations WHERE type = ?1 ORDER BY updated_at DESC")?;
+        let mut stmt = conn.prepare("SELECT id, title, type, created_at, updated_at FROM conversations WHERE type = ?1 AND deleted_at IS NULL ORDER BY updated_at DESC")?;
         let rows = stmt.query_map(params![conv_type], |row| {
             Ok(Conversation {
                 id: row.get(0)?,
@@ -229,6 +414,27 @@ impl Database {
         }
     }
 
+    /// Set or clear (`None`) the extra system instructions for one conversation.
+    /// Returns whether the conversation exists.
+    pub fn set_conversation_system_prompt(&self, conversation_id: &str, prompt: Option<&str>) -> Result<bool, rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        let updated = conn.execute(
+            "UPDATE conversations SET system_prompt_override = ?1 WHERE id = ?2",
+            params![prompt, conversation_id],
+        )?;
+        Ok(updated > 0)
+    }
+
+    pub fn get_conversation_system_prompt(&self, conversation_id: &str) -> Result<Option<String>, rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT system_prompt_override FROM conversations WHERE id = ?1")?;
+        let mut rows = stmt.query_map(params![conversation_id], |row| row.get::<_, Option<String>>(0))?;
+        match rows.next() {
+            Some(row) => row,
+            None => Ok(None),
+        }
+    }
+
     pub fn add_message(&self, conversation_id: &str, role: &str, content: &str) -> Result<Message, rusqlite::Error> {
         let conn = self.conn.lock().unwrap();
         let id = Uuid::new_v4().to_string();
@@ -259,10 +465,222 @@ impl Database {
         rows.collect()
     }
 
+    /// The `limit` most recent messages, oldest first. With `before` (a message id),
+    /// only messages older than that one are considered, so the UI can page backwards.
+    /// Returns None when `before` isn't a message in this conversation.
+    pub fn get_messages_page(
+        &self,
+        conversation_id: &str,
+        limit: u32,
+        before: Option<&str>,
+    ) -> Result<Option<Vec<Message>>, rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        let cursor: Option<(String, i64)> = match before {
+            Some(before) => {
+                let mut stmt = conn.prepare(
+                    "SELECT created_at, rowid FROM messages WHERE id = ?2 AND conversation_id = ?1"
+                )?;
+                let mut rows = stmt.query_map(params![conversation_id, before], |row| Ok((row.get(0)?, row.get(1)?)))?;
+                match rows.next() {
+                    Some(row) => Some(row?),
+                    None => return Ok(None),
+                }
+            }
+            None => None,
+        };
+        let (cursor_created_at, cursor_rowid) = cursor.unzip();
+        let mut stmt = conn.prepare(
+            "SELECT id, conversation_id, role, content, created_at FROM messages
+             WHERE conversation_id = ?1
+               AND (?2 IS NULL OR (created_at, rowid) < (?2, ?3))
+             ORDER BY created_at DESC, rowid DESC
+             LIMIT ?4"
+        )?;
+        let rows = stmt.query_map(params![conversation_id, cursor_created_at, cursor_rowid, limit], |row| {
+            Ok(Message {
+                id: row.get(0)?,
+                conversation_id: row.get(1)?,
+                role: row.get(2)?,
+                content: row.get(3)?,
+                created_at: row.get(4)?,
+            })
+        })?;
+        let mut page = rows.collect::<Result<Vec<_>, _>>()?;
+        page.reverse();
+        Ok(Some(page))
+    }
+
+    /// The conversation's most recent message, if any.
+    pub fn get_last_message(&self, conversation_id: &str) -> Result<Option<Message>, rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, conversation_id, role, content, created_at FROM messages WHERE conversation_id = ?1 ORDER BY created_at DESC, rowid DESC LIMIT 1"
+        )?;
+        let mut rows = stmt.query_map(params![conversation_id], |row| {
+            Ok(Message {
+                id: row.get(0)?,
+                conversation_id: row.get(1)?,
+                role: row.get(2)?,
+                content: row.get(3)?,
+                created_at: row.get(4)?,
+            })
+        })?;
+        match rows.next() {
+            Some(row) => Ok(Some(row?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Swap the conversation's most recent message for `content` in one transaction,
+    /// but only if that message has the given role. Returns the new message, or
+    /// `None` (leaving the conversation untouched) if the latest message belongs to someone else.
+    pub fn replace_last_message(&self, conversation_id: &str, role: &str, content: &str) -> Result<Option<Message>, rusqlite::Error> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        let last: Option<(String, String)> = {
+            let mut stmt = tx.prepare(
+                "SELECT id, role FROM messages WHERE conversation_id = ?1 ORDER BY created_at DESC, rowid DESC LIMIT 1"
+            )?;
+            let mut rows = stmt.query_map(params![conversation_id], |row| Ok((row.get(0)?, row.get(1)?)))?;
+            rows.next().transpose()?
+        };
+        match last {
+            Some((_, ref last_role)) if last_role != role => return Ok(None),
+            None => return Ok(None),
+            Some((last_id, _)) => {
+                tx.execute("DELETE FROM messages WHERE id = ?1", params![last_id])?;
+            }
+        }
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now().to_rfc3339();
+        tx.execute(
+            "INSERT INTO messages (id, conversation_id, role, content, created_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![id, conversation_id, role, content, now],
+        )?;
+        tx.execute(
+            "UPDATE conversations SET updated_at = ?1 WHERE id = ?2",
+            params![now, conversation_id],
+        )?;
+        tx.commit()?;
+        Ok(Some(Message { id, conversation_id: conversation_id.to_string(), role: role.to_string(), content: content.to_string(), created_at: now }))
+    }
+
+    pub fn get_message(&self, message_id: &str) -> Result<Option<Message>, rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT id, conversation_id, role, content, created_at FROM messages WHERE id = ?1")?;
+        let mut rows = stmt.query_map(params![message_id], |row| {
+            Ok(Message {
+                id: row.get(0)?,
+                conversation_id: row.get(1)?,
+                role: row.get(2)?,
+                content: row.get(3)?,
+                created_at: row.get(4)?,
+            })
+        })?;
+        match rows.next() {
+            Some(row) => Ok(Some(row?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Rewrite `message_id`, drop every later message in its conversation, and append
+    /// `reply` as the assistant's answer, all in one transaction. Returns the saved
+    /// reply, or `None` (changing nothing) if the message doesn't exist.
+    pub fn rewrite_message_with_reply(&self, message_id: &str, content: &str, reply: &str) -> Result<Option<Message>, rusqlite::Error> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        let conversation_id: Option<String> = {
+            let mut stmt = tx.prepare("SELECT conversation_id FROM messages WHERE id = ?1")?;
+            let mut rows = stmt.query_map(params![message_id], |row| row.get(0))?;
+            rows.next().transpose()?
+        };
+        let Some(conversation_id) = conversation_id else {
+            return Ok(None);
+        };
+        tx.execute(
+            "UPDATE messages SET content = ?1 WHERE id = ?2",
+            params![content, message_id],
+        )?;
+        tx.execute(
+            "DELETE FROM messages
+             WHERE conversation_id = ?1
+               AND (created_at, rowid) > (SELECT created_at, rowid FROM messages WHERE id = ?2)",
+            params![conversation_id, message_id],
+        )?;
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now().to_rfc3339();
+        tx.execute(
+            "INSERT INTO messages (id, conversation_id, role, content, created_at) VALUES (?1, ?2, 'assistant', ?3, ?4)",
+            params![id, conversation_id, reply, now],
+        )?;
+        tx.execute(
+            "UPDATE conversations SET updated_at = ?1 WHERE id = ?2",
+            params![now, conversation_id],
+        )?;
+        tx.commit()?;
+        Ok(Some(Message { id, conversation_id, role: "assistant".to_string(), content: reply.to_string(), created_at: now }))
+    }
+
+    /// Rename a conversation. Decision conversations keep their decision's title in sync.
+    pub fn update_conversation_title(&self, conversation_id: &str, title: &str) -> Result<(), rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        let now = Utc::now().to_rfc3339();
+        conn.execute(
+            "UPDATE conversations SET title = ?1, updated_at = ?2 WHERE id = ?3",
+            params![title, now, conversation_id],
+        )?;
+        conn.execute(
+            "UPDATE decisions SET title = ?1, updated_at = ?2 WHERE conversation_id = ?3 AND conversation_id IN (SELECT id FROM conversations WHERE type = 'decision')",
+            params![title, now, conversation_id],
+        )?;
+        Ok(())
+    }
+
+    /// Move a conversation to the trash. It disappears from listings but keeps
+    /// its messages, decision, and debate until restored or purged.
     pub fn delete_conversation(&self, conversation_id: &str) -> Result<(), rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        let now = Utc::now().to_rfc3339();
+        conn.execute(
+            "UPDATE conversations SET deleted_at = ?1 WHERE id = ?2 AND deleted_at IS NULL",
+            params![now, conversation_id],
+        )?;
+        Ok(())
+    }
+
+    /// Take a conversation back out of the trash. Returns whether it was trashed.
+    pub fn restore_conversation(&self, conversation_id: &str) -> Result<bool, rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        let updated = conn.execute(
+            "UPDATE conversations SET deleted_at = NULL WHERE id = ?1 AND deleted_at IS NOT NULL",
+            params![conversation_id],
+        )?;
+        Ok(updated > 0)
+    }
+
+    /// Conversations currently in the trash, most recently deleted first.
+    pub fn get_deleted_conversations(&self) -> Result<Vec<Conversation>, rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT id, title, type, created_at, updated_at FROM conversations WHERE deleted_at IS NOT NULL ORDER BY deleted_at DESC")?;
+        let rows = stmt.query_map([], |row| {
+            Ok(Conversation {
+                id: row.get(0)?,
+                title: row.get(1)?,
+                conv_type: row.get(2)?,
+                created_at: row.get(3)?,
+                updated_at: row.get(4)?,
+            })
+        })?;
+        rows.collect()
+    }
+
+    /// Permanently delete a conversation with its messages, decision, and debate data.
+    pub fn purge_conversation(&self, conversation_id: &str) -> Result<(), rusqlite::Error> {
         let conn = self.conn.lock().unwrap();
         conn.execute("DELETE FROM debate_audio WHERE decision_id IN (SELECT id FROM decisions WHERE conversation_id = ?1)", params![conversation_id])?;
         conn.execute("DELETE FROM debate_rounds WHERE decision_id IN (SELECT id FROM decisions WHERE conversation_id = ?1)", params![conversation_id])?;
+        conn.execute("DELETE FROM tags WHERE decision_id IN (SELECT id FROM decisions WHERE conversation_id = ?1)", params![conversation_id])?;
+        conn.execute("DELETE FROM summary_history WHERE decision_id IN (SELECT id FROM decisions WHERE conversation_id = ?1)", params![conversation_id])?;
         conn.execute("DELETE FROM messages WHERE conversation_id = ?1", params![conversation_id])?;
         conn.execute("DELETE FROM decisions WHERE conversation_id = ?1", params![conversation_id])?;
         conn.execute("DELETE FROM conversations WHERE id = ?1", params![conversation_id])?;
@@ -289,6 +707,8 @@ impl Database {
             user_choice_reasoning: None,
             outcome: None,
             outcome_date: None,
+            outcome_score: None,
+            due_date: None,
             debate_brief: None,
             debate_started_at: None,
             debate_completed_at: None,
@@ -297,81 +717,90 @@ impl Database {
         })
     }
 
+    /// Turn an existing conversation into a decision conversation with a linked
+    /// decision, in one transaction. Returns None if the conversation already has a decision.
+    pub fn promote_conversation_to_decision(&self, conversation_id: &str, title: &str) -> Result<Option<Decision>, rusqlite::Error> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        let has_decision: bool = tx.query_row(
+            "SELECT EXISTS(SELECT 1 FROM decisions WHERE conversation_id = ?1)",
+            params![conversation_id],
+            |row| row.get(0),
+        )?;
+        if has_decision {
+            return Ok(None);
+        }
+        let decision_id = Uuid::new_v4().to_string();
+        let now = Utc::now().to_rfc3339();
+        tx.execute(
+            "UPDATE conversations SET type = 'decision', updated_at = ?1 WHERE id = ?2",
+            params![now, conversation_id],
+        )?;
+        tx.execute(
+            "INSERT INTO decisions (id, conversation_id, title, status, created_at, updated_at) VALUES (?1, ?2, ?3, 'exploring', ?4, ?5)",
+            params![decision_id, conversation_id, title, now, now],
+        )?;
+        let decision = tx.query_row(
+            &format!("SELECT {} FROM decisions d WHERE d.id = ?1", DECISION_COLUMNS),
+            params![decision_id],
+            row_to_decision,
+        )?;
+        tx.commit()?;
+        Ok(Some(decision))
+    }
+
+    /// Create a decision conversation and its decision with `summary_json` already
+    /// filled in, in one transaction so a failure part-way leaves nothing behind.
+    pub fn create_decision_with_summary(&self, title: &str, summary_json: &str) -> Result<Decision, rusqlite::Error> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        let conversation_id = Uuid::new_v4().to_string();
+        let decision_id = Uuid::new_v4().to_string();
+        let now = Utc::now().to_rfc3339();
+        tx.execute(
+            "INSERT INTO conversations (id, title, type, created_at, updated_at) VALUES (?1, ?2, 'decision', ?3, ?4)",
+            params![conversation_id, title, now, now],
+        )?;
+        tx.execute(
+            "INSERT INTO decisions (id, conversation_id, title, status, summary_json, created_at, updated_at) VALUES (?1, ?2, ?3, 'exploring', ?4, ?5, ?6)",
+            params![decision_id, conversation_id, title, summary_json, now, now],
+        )?;
+        let decision = tx.query_row(
+            &format!("SELECT {} FROM decisions d WHERE d.id = ?1", DECISION_COLUMNS),
+            params![decision_id],
+            row_to_decision,
+        )?;
+        tx.commit()?;
+        Ok(decision)
+    }
+
     pub fn get_decisions(&self) -> Result<Vec<Decision>, rusqlite::Error> {
         let conn = self.conn.lock().unwrap();
-        let mut stmt = conn.prepare(
-            "SELECT d.id, d.conversation_id, d.title, d.status, d.summary_json, d.user_choice, d.user_choice_reasoning, d.outcome, d.outcome_date, d.debate_brief, d.debate_started_at, d.debate_completed_at, d.created_at, d.updated_at FROM decisions d JOIN conversations c ON d.conversation_id = c.id WHERE c.type != 'debate' ORDER BY d.updated_at DESC"
-        )?;
-        let rows = stmt.query_map([], |row| {
-            Ok(Decision {
-                id: row.get(0)?,
-                conversation_id: row.get(1)?,
-                title: row.get(2)?,
-                status: row.get(3)?,
-                summary_json: row.get(4)?,
-                user_choice: row.get(5)?,
-                user_choice_reasoning: row.get(6)?,
-                outcome: row.get(7)?,
-                outcome_date: row.get(8)?,
-                debate_brief: row.get(9)?,
-                debate_started_at: row.get(10)?,
-                debate_completed_at: row.get(11)?,
-                created_at: row.get(12)?,
-                updated_at: row.get(13)?,
-            })
-        })?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM decisions d JOIN conversations c ON d.conversation_id = c.id WHERE c.type != 'debate' AND c.deleted_at IS NULL ORDER BY d.updated_at DESC",
+            DECISION_COLUMNS,
+        ))?;
+        let rows = stmt.query_map([], row_to_decision)?;
         rows.collect()
     }
 
     pub fn get_standalone_debates(&self) -> Result<Vec<Decision>, rusqlite::Error> {
         let conn = self.conn.lock().unwrap();
-        let mut stmt = conn.prepare(
-            "SELECT d.id, d.conversation_id, d.title, d.status, d.summary_json, d.user_choice, d.user_choice_reasoning, d.outcome, d.outcome_date, d.debate_brief, d.debate_started_at, d.debate_completed_at, d.created_at, d.updated_at FROM decisions d JOIN conversations c ON d.conversation_id = c.id WHERE c.type = 'debate' ORDER BY d.updated_at DESC"
-        )?;
-        let rows = stmt.query_map([], |row| {
-            Ok(Decision {
-                id: row.get(0)?,
-                conversation_id: row.get(1)?,
-                title: row.get(2)?,
-                status: row.get(3)?,
-                summary_json: row.get(4)?,
-                user_choice: row.get(5)?,
-                user_choice_reasoning: row.get(6)?,
-                outcome: row.get(7)?,
-                outcome_date: row.get(8)?,
-                debate_brief: row.get(9)?,
-                debate_started_at: row.get(10)?,
-                debate_completed_at: row.get(11)?,
-                created_at: row.get(12)?,
-                updated_at: row.get(13)?,
-            })
-        })?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM decisions d JOIN conversations c ON d.conversation_id = c.id WHERE c.type = 'debate' AND c.deleted_at IS NULL ORDER BY d.updated_at DESC",
+            DECISION_COLUMNS,
+        ))?;
+        let rows = stmt.query_map([], row_to_decision)?;
         rows.collect()
     }
 
     pub fn get_decision(&self, decision_id: &str) -> Result<Option<Decision>, rusqlite::Error> {
         let conn = self.conn.lock().unwrap();
-        let mut stmt = conn.prepare(
-            "SELECT id, conversation_id, title, status, summary_json, user_choice, user_choice_reasoning, outcome, outcome_date, debate_brief, debate_started_at, debate_completed_at, created_at, updated_at FROM decisions WHERE id = ?1"
-        )?;
-        let mut rows = stmt.query_map(params![decision_id], |row| {
-            Ok(Decision {
-                id: row.get(0)?,
-                conversation_id: row.get(1)?,
-                title: row.get(2)?,
-                status: row.get(3)?,
-                summary_json: row.get(4)?,
-                user_choice: row.get(5)?,
-                user_choice_reasoning: row.get(6)?,
-                outcome: row.get(7)?,
-                outcome_date: row.get(8)?,
-                debate_brief: row.get(9)?,
-                debate_started_at: row.get(10)?,
-                debate_completed_at: row.get(11)?,
-                created_at: row.get(12)?,
-                updated_at: row.get(13)?,
-            })
-        })?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM decisions d WHERE d.id = ?1",
+            DECISION_COLUMNS,
+        ))?;
+        let mut rows = stmt.query_map(params![decision_id], row_to_decision)?;
         match rows.next() {
             Some(row) => Ok(Some(row?)),
             None => Ok(None),
@@ -379,72 +808,246 @@ impl Database {
     }
 
     pub fn get_decision_by_conversation(&self, conversation_id: &str) -> Result<Option<Decision>, rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM decisions d WHERE d.conversation_id = ?1",
+            DECISION_COLUMNS,
+        ))?;
+        let mut rows = stmt.query_map(params![conversation_id], row_to_decision)?;
+        match rows.next() {
+            Some(row) => Ok(Some(row?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Every decision on the same conversation as `decision_id` (itself included),
+    /// oldest first. Empty if the decision doesn't exist.
+    pub fn get_related_decisions(&self, decision_id: &str) -> Result<Vec<Decision>, rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM decisions d
+             WHERE d.conversation_id = (SELECT conversation_id FROM decisions WHERE id = ?1)
+             ORDER BY d.created_at ASC, d.rowid ASC",
+            DECISION_COLUMNS,
+        ))?;
+        let rows = stmt.query_map(params![decision_id], row_to_decision)?;
+        rows.collect()
+    }
+
+    /// Replace a decision's summary, first pushing the previous value onto its
+    /// `summary_history` (capped at `SUMMARY_HISTORY_DEPTH`) so it can be undone.
+    /// Writing the same summary again adds no history entry.
+    pub fn update_decision_summary(&self, decision_id: &str, summary_json: &str) -> Result<(), rusqlite::Error> {
+        let mut conn = self.conn.lock().unwrap();
+        let now = Utc::now().to_rfc3339();
+        let tx = conn.transaction()?;
+        tx.execute(
+            "INSERT INTO summary_history (decision_id, summary_json, created_at)
+             SELECT id, summary_json, ?1 FROM decisions WHERE id = ?2 AND summary_json IS NOT ?3",
+            params![now, decision_id, summary_json],
+        )?;
+        tx.execute(
+            "DELETE FROM summary_history WHERE decision_id = ?1 AND id NOT IN (
+                SELECT id FROM summary_history WHERE decision_id = ?1 ORDER BY id DESC LIMIT ?2
+            )",
+            params![decision_id, SUMMARY_HISTORY_DEPTH],
+        )?;
+        tx.execute(
+            "UPDATE decisions SET summary_json = ?1, updated_at = ?2 WHERE id = ?3",
+            params![summary_json, now, decision_id],
+        )?;
+        tx.commit()
+    }
+
+    /// Restore the summary from before the most recent update and drop that history
+    /// entry. Returns false when there is nothing to undo.
+    pub fn undo_summary_update(&self, decision_id: &str) -> Result<bool, rusqlite::Error> {
+        let mut conn = self.conn.lock().unwrap();
+        let now = Utc::now().to_rfc3339();
+        let tx = conn.transaction()?;
+        let previous: Option<(i64, Option<String>)> = {
+            let mut stmt = tx.prepare(
+                "SELECT id, summary_json FROM summary_history WHERE decision_id = ?1 ORDER BY id DESC LIMIT 1",
+            )?;
+            let mut rows = stmt.query_map(params![decision_id], |row| Ok((row.get(0)?, row.get(1)?)))?;
+            rows.next().transpose()?
+        };
+        let Some((history_id, summary_json)) = previous else {
+            return Ok(false);
+        };
+        tx.execute(
+            "UPDATE decisions SET summary_json = ?1, updated_at = ?2 WHERE id = ?3",
+            params![summary_json, now, decision_id],
+        )?;
+        tx.execute("DELETE FROM summary_history WHERE id = ?1", params![history_id])?;
+        tx.commit()?;
+        Ok(true)
+    }
+
+    pub fn update_decision_status(&self, decision_id: &str, status: &str) -> Result<(), rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        let now = Utc::now().to_rfc3339();
+        conn.execute(
+            "UPDATE decisions SET status = ?1, updated_at = ?2 WHERE id = ?3",
+            params![status, now, decision_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn update_decision_choice(&self, decision_id: &str, user_choice: &str, reasoning: Option<&str>) -> Result<(), rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        let now = Utc::now().to_rfc3339();
+        conn.execute(
+            "UPDATE decisions SET status = 'decided', user_choice = ?1, user_choice_reasoning = ?2, updated_at = ?3 WHERE id = ?4",
+            params![user_choice, reasoning.unwrap_or(""), now, decision_id],
+        )?;
+        Ok(())
+    }
+
+    /// Set or clear a decision's deadline. Returns false if the decision doesn't exist.
+    pub fn set_decision_due_date(&self, decision_id: &str, due_date: Option<&str>) -> Result<bool, rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        let now = Utc::now().to_rfc3339();
+        let updated = conn.execute(
+            "UPDATE decisions SET due_date = ?1, updated_at = ?2 WHERE id = ?3",
+            params![due_date, now, decision_id],
+        )?;
+        Ok(updated > 0)
+    }
+
+    /// Decisions not yet reviewed whose deadline falls within `within_days` from
+    /// now, soonest first. Overdue decisions are included.
+    pub fn get_decisions_due_soon(&self, within_days: i64) -> Result<Vec<Decision>, rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        let cutoff = (Utc::now() + chrono::Duration::days(within_days))
+            .to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM decisions d JOIN conversations c ON d.conversation_id = c.id
+             WHERE c.type != 'debate' AND c.deleted_at IS NULL AND d.status != 'reviewed'
+               AND d.due_date IS NOT NULL AND d.due_date <= ?1
+             ORDER BY d.due_date ASC",
+            DECISION_COLUMNS,
+        ))?;
+        let rows = stmt.query_map(params![cutoff], row_to_decision)?;
+        rows.collect()
+    }
+
+    /// Record what happened. `score` is a 1–5 "how did it go" rating, clamped into range.
+    pub fn update_decision_outcome(&self, decision_id: &str, outcome: &str, score: Option<i64>) -> Result<(), rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        let now = Utc::now().to_rfc3339();
+        let score = score.map(|s| s.clamp(1, 5));
+        conn.execute(
+            "UPDATE decisions SET status = 'reviewed', outcome = ?1, outcome_date = ?2, outcome_score = ?3, updated_at = ?4 WHERE id = ?5",
+            params![outcome, now, score, now, decision_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_decisions_with_audio_flag(&self) -> Result<Vec<DecisionSummaryRow>, rusqlite::Error> {
         let conn = self.conn.lock().unwrap();
         let mut stmt = conn.prepare(
-            "SELECT id, conversation_id, title, status, summary_json, user_choice, user_choice_reasoning, outcome, outcome_date, debate_brief, debate_started_at, debate_completed_at, created_at, updated_at FROM decisions WHERE conversation_id = ?1"
+            "SELECT d.id, d.conversation_id, d.title, d.status, d.updated_at, EXISTS(SELECT 1 FROM debate_audio a WHERE a.decision_id = d.id) FROM decisions d JOIN conversations c ON d.conversation_id = c.id WHERE c.type != 'debate' AND c.deleted_at IS NULL ORDER BY d.updated_at DESC"
         )?;
-        let mut rows = stmt.query_map(params![conversation_id], |row| {
-            Ok(Decision {
+        let rows = stmt.query_map([], |row| {
+            Ok(DecisionSummaryRow {
                 id: row.get(0)?,
                 conversation_id: row.get(1)?,
                 title: row.get(2)?,
                 status: row.get(3)?,
-                summary_json: row.get(4)?,
-                user_choice: row.get(5)?,
-                user_choice_reasoning: row.get(6)?,
-                outcome: row.get(7)?,
-                outcome_date: row.get(8)?,
-                debate_brief: row.get(9)?,
-                debate_started_at: row.get(10)?,
-                debate_completed_at: row.get(11)?,
-                created_at: row.get(12)?,
-                updated_at: row.get(13)?,
+                updated_at: row.get(4)?,
+                has_audio: row.get(5)?,
             })
         })?;
-        match rows.next() {
-            Some(row) => Ok(Some(row?)),
-            None => Ok(None),
+        rows.collect()
+    }
+
+    pub fn get_decisions_by_status(&self, status: &str) -> Result<Vec<Decision>, rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM decisions d JOIN conversations c ON d.conversation_id = c.id WHERE c.type != 'debate' AND c.deleted_at IS NULL AND d.status = ?1 ORDER BY d.updated_at DESC",
+            DECISION_COLUMNS,
+        ))?;
+        let rows = stmt.query_map(params![status], row_to_decision)?;
+        rows.collect()
+    }
+
+    /// Ids of every decision, including standalone debates and those in the trash.
+    pub fn get_decision_ids(&self) -> Result<HashSet<String>, rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT id FROM decisions")?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+        rows.collect()
+    }
+
+    /// Decision counts for every known status. Statuses with no rows are reported as 0.
+    pub fn get_decision_status_counts(&self) -> Result<HashMap<String, u64>, rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        let mut counts: HashMap<String, u64> = DECISION_STATUSES
+            .iter()
+            .map(|s| (s.to_string(), 0))
+            .collect();
+        let mut stmt = conn.prepare(
+            "SELECT d.status, COUNT(*) FROM decisions d JOIN conversations c ON d.conversation_id = c.id WHERE c.type != 'debate' AND c.deleted_at IS NULL GROUP BY d.status"
+        )?;
+        let rows = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)))?;
+        for row in rows {
+            let (status, count) = row?;
+            if let Some(slot) = counts.get_mut(&status) {
+                *slot = count as u64;
+            }
         }
+        Ok(counts)
     }
 
-    pub fn update_decision_summary(&self, decision_id: &str, summary_json: &str) -> Result<(), rusqlite::Error> {
+    // ── Tag methods ──
+
+    pub fn add_decision_tag(&self, decision_id: &str, tag: &str) -> Result<(), rusqlite::Error> {
         let conn = self.conn.lock().unwrap();
-        let now = Utc::now().to_rfc3339();
         conn.execute(
-            "UPDATE decisions SET summary_json = ?1, updated_at = ?2 WHERE id = ?3",
-            params![summary_json, now, decision_id],
+            "INSERT OR IGNORE INTO tags (decision_id, tag) VALUES (?1, ?2)",
+            params![decision_id, tag],
         )?;
         Ok(())
     }
 
-    pub fn update_decision_status(&self, decision_id: &str, status: &str) -> Result<(), rusqlite::Error> {
+    pub fn remove_decision_tag(&self, decision_id: &str, tag: &str) -> Result<(), rusqlite::Error> {
         let conn = self.conn.lock().unwrap();
-        let now = Utc::now().to_rfc3339();
         conn.execute(
-            "UPDATE decisions SET status = ?1, updated_at = ?2 WHERE id = ?3",
-            params![status, now, decision_id],
+            "DELETE FROM tags WHERE decision_id = ?1 AND tag = ?2",
+            params![decision_id, tag],
         )?;
         Ok(())
     }
 
-    pub fn update_decision_choice(&self, decision_id: &str, user_choice: &str, reasoning: Option<&str>) -> Result<(), rusqlite::Error> {
+    pub fn get_tags_for_decision(&self, decision_id: &str) -> Result<Vec<String>, rusqlite::Error> {
         let conn = self.conn.lock().unwrap();
-        let now = Utc::now().to_rfc3339();
-        conn.execute(
-            "UPDATE decisions SET status = 'decided', user_choice = ?1, user_choice_reasoning = ?2, updated_at = ?3 WHERE id = ?4",
-            params![user_choice, reasoning.unwrap_or(""), now, decision_id],
-        )?;
-        Ok(())
+        let mut stmt = conn.prepare("SELECT tag FROM tags WHERE decision_id = ?1 ORDER BY tag ASC")?;
+        let rows = stmt.query_map(params![decision_id], |row| row.get(0))?;
+        rows.collect()
     }
 
-    pub fn update_decision_outcome(&self, decision_id: &str, outcome: &str) -> Result<(), rusqlite::Error> {
+    /// All tags keyed by decision id, loaded in one query for list views.
+    pub fn get_all_decision_tags(&self) -> Result<HashMap<String, Vec<String>>, rusqlite::Error> {
         let conn = self.conn.lock().unwrap();
-        let now = Utc::now().to_rfc3339();
-        conn.execute(
-            "UPDATE decisions SET status = 'reviewed', outcome = ?1, outcome_date = ?2, updated_at = ?3 WHERE id = ?4",
-            params![outcome, now, now, decision_id],
-        )?;
-        Ok(())
+        let mut stmt = conn.prepare("SELECT decision_id, tag FROM tags ORDER BY tag ASC")?;
+        let rows = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?;
+        let mut tags: HashMap<String, Vec<String>> = HashMap::new();
+        for row in rows {
+            let (decision_id, tag) = row?;
+            tags.entry(decision_id).or_default().push(tag);
+        }
+        Ok(tags)
+    }
+
+    pub fn get_decisions_by_tag(&self, tag: &str) -> Result<Vec<Decision>, rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM decisions d JOIN tags t ON t.decision_id = d.id JOIN conversations c ON d.conversation_id = c.id WHERE t.tag = ?1 AND c.deleted_at IS NULL ORDER BY d.updated_at DESC",
+            DECISION_COLUMNS,
+        ))?;
+        let rows = stmt.query_map(params![tag], row_to_decision)?;
+        rows.collect()
     }
 
     // ── Debate methods ──
@@ -494,10 +1097,49 @@ impl Database {
         rows.collect()
     }
 
-    pub fn delete_debate_rounds(&self, decision_id: &str) -> Result<(), rusqlite::Error> {
+    pub fn get_debate_round(&self, round_id: &str) -> Result<Option<DebateRound>, rusqlite::Error> {
         let conn = self.conn.lock().unwrap();
-        conn.execute("DELETE FROM debate_rounds WHERE decision_id = ?1", params![decision_id])?;
-        Ok(())
+        let mut stmt = conn.prepare(
+            "SELECT id, decision_id, round_number, exchange_number, agent, content, created_at FROM debate_rounds WHERE id = ?1"
+        )?;
+        let mut rows = stmt.query_map(params![round_id], |row| {
+            Ok(DebateRound {
+                id: row.get(0)?,
+                decision_id: row.get(1)?,
+                round_number: row.get(2)?,
+                exchange_number: row.get(3)?,
+                agent: row.get(4)?,
+                content: row.get(5)?,
+                created_at: row.get(6)?,
+            })
+        })?;
+        match rows.next() {
+            Some(row) => Ok(Some(row?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Replace a round's content. Returns whether a row was updated.
+    pub fn update_debate_round_content(&self, round_id: &str, content: &str) -> Result<bool, rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        let updated = conn.execute(
+            "UPDATE debate_rounds SET content = ?1 WHERE id = ?2",
+            params![content, round_id],
+        )?;
+        Ok(updated > 0)
+    }
+
+    /// Delete a single debate round by id. Returns whether a row was removed.
+    pub fn delete_debate_round(&self, round_id: &str) -> Result<bool, rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        let deleted = conn.execute("DELETE FROM debate_rounds WHERE id = ?1", params![round_id])?;
+        Ok(deleted > 0)
+    }
+
+    /// Delete every round of a decision's debate. Returns how many rows were removed.
+    pub fn delete_debate_rounds(&self, decision_id: &str) -> Result<usize, rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute("DELETE FROM debate_rounds WHERE decision_id = ?1", params![decision_id])
     }
 
     pub fn update_debate_brief(&self, decision_id: &str, brief: &str) -> Result<(), rusqlite::Error> {
@@ -510,6 +1152,36 @@ impl Database {
         Ok(())
     }
 
+    pub fn get_debate_brief(&self, decision_id: &str) -> Result<Option<String>, rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT debate_brief FROM decisions WHERE id = ?1")?;
+        let mut rows = stmt.query_map(params![decision_id], |row| row.get::<_, Option<String>>(0))?;
+        match rows.next() {
+            Some(row) => row,
+            None => Ok(None),
+        }
+    }
+
+    /// Store the roster and shape a debate was started with, so it can be resumed faithfully.
+    pub fn update_debate_setup(&self, decision_id: &str, setup_json: &str) -> Result<(), rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE decisions SET debate_setup_json = ?1 WHERE id = ?2",
+            params![setup_json, decision_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_debate_setup(&self, decision_id: &str) -> Result<Option<String>, rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT debate_setup_json FROM decisions WHERE id = ?1")?;
+        let mut rows = stmt.query_map(params![decision_id], |row| row.get::<_, Option<String>>(0))?;
+        match rows.next() {
+            Some(row) => row,
+            None => Ok(None),
+        }
+    }
+
     pub fn update_debate_started(&self, decision_id: &str) -> Result<(), rusqlite::Error> {
         let conn = self.conn.lock().unwrap();
         let now = Utc::now().to_rfc3339();
@@ -589,10 +1261,191 @@ impl Database {
         }
     }
 
-    pub fn delete_debate_audio(&self, decision_id: &str) -> Result<(), rusqlite::Error> {
+    /// Delete a decision's audio record. Returns whether one existed.
+    pub fn delete_debate_audio(&self, decision_id: &str) -> Result<bool, rusqlite::Error> {
         let conn = self.conn.lock().unwrap();
-        conn.execute("DELETE FROM debate_audio WHERE decision_id = ?1", params![decision_id])?;
-        Ok(())
+        let deleted = conn.execute("DELETE FROM debate_audio WHERE decision_id = ?1", params![decision_id])?;
+        Ok(deleted > 0)
+    }
+
+    // ── Backup methods ──
+
+    /// True if any conversation outside the trash exists (every other table hangs off conversations).
+    pub fn has_data(&self) -> Result<bool, rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row("SELECT EXISTS (SELECT 1 FROM conversations WHERE deleted_at IS NULL)", [], |r| r.get(0))
+    }
+
+    /// Snapshot every table into a `DataBundle`.
+    pub fn export_bundle(&self) -> Result<DataBundle, rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+
+        let conversations = conn
+            .prepare("SELECT id, title, type, created_at, updated_at, system_prompt_override, deleted_at FROM conversations ORDER BY created_at")?
+            .query_map([], |row| {
+                Ok(ExportedConversation {
+                    conversation: Conversation {
+                        id: row.get(0)?,
+                        title: row.get(1)?,
+                        conv_type: row.get(2)?,
+                        created_at: row.get(3)?,
+                        updated_at: row.get(4)?,
+                    },
+                    system_prompt_override: row.get(5)?,
+                    deleted_at: row.get(6)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let messages = conn
+            .prepare("SELECT id, conversation_id, role, content, created_at FROM messages ORDER BY created_at, rowid")?
+            .query_map([], |row| {
+                Ok(Message {
+                    id: row.get(0)?,
+                    conversation_id: row.get(1)?,
+                    role: row.get(2)?,
+                    content: row.get(3)?,
+                    created_at: row.get(4)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let decisions = conn
+            .prepare(&format!("SELECT {}, d.debate_setup_json FROM decisions d ORDER BY d.created_at", DECISION_COLUMNS))?
+            .query_map([], |row| {
+                Ok(ExportedDecision {
+                    decision: row_to_decision(row)?,
+                    debate_setup_json: row.get(16)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let debate_rounds = conn
+            .prepare("SELECT id, decision_id, round_number, exchange_number, agent, content, created_at FROM debate_rounds ORDER BY created_at, rowid")?
+            .query_map([], |row| {
+                Ok(DebateRound {
+                    id: row.get(0)?,
+                    decision_id: row.get(1)?,
+                    round_number: row.get(2)?,
+                    exchange_number: row.get(3)?,
+                    agent: row.get(4)?,
+                    content: row.get(5)?,
+                    created_at: row.get(6)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let debate_audio = conn
+            .prepare("SELECT id, decision_id, manifest_json, total_duration_ms, generated_at, audio_dir FROM debate_audio ORDER BY generated_at")?
+            .query_map([], |row| {
+                Ok(DebateAudio {
+                    id: row.get(0)?,
+                    decision_id: row.get(1)?,
+                    manifest_json: row.get(2)?,
+                    total_duration_ms: row.get(3)?,
+                    generated_at: row.get(4)?,
+                    audio_dir: row.get(5)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let tags = conn
+            .prepare("SELECT decision_id, tag FROM tags ORDER BY decision_id, tag")?
+            .query_map([], |row| Ok(ExportedTag { decision_id: row.get(0)?, tag: row.get(1)? }))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let summary_history = conn
+            .prepare("SELECT decision_id, summary_json, created_at FROM summary_history ORDER BY id")?
+            .query_map([], |row| {
+                Ok(ExportedSummaryHistory {
+                    decision_id: row.get(0)?,
+                    summary_json: row.get(1)?,
+                    created_at: row.get(2)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(DataBundle {
+            version: DATA_BUNDLE_VERSION,
+            exported_at: Utc::now().to_rfc3339(),
+            conversations,
+            messages,
+            decisions,
+            debate_rounds,
+            debate_audio,
+            tags,
+            summary_history,
+        })
+    }
+
+    /// Restore a `DataBundle` in a single transaction. With `overwrite`, existing
+    /// rows are wiped first; otherwise rows are inserted alongside what's there.
+    pub fn import_bundle(&self, bundle: &DataBundle, overwrite: bool) -> Result<(), rusqlite::Error> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+
+        if overwrite {
+            tx.execute_batch("
+                DELETE FROM tags;
+                DELETE FROM summary_history;
+                DELETE FROM debate_audio;
+                DELETE FROM debate_rounds;
+                DELETE FROM decisions;
+                DELETE FROM messages;
+                DELETE FROM conversations;
+            ")?;
+        }
+
+        for c in &bundle.conversations {
+            tx.execute(
+                "INSERT INTO conversations (id, title, type, system_prompt_override, deleted_at, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![c.conversation.id, c.conversation.title, c.conversation.conv_type, c.system_prompt_override, c.deleted_at, c.conversation.created_at, c.conversation.updated_at],
+            )?;
+        }
+        for m in &bundle.messages {
+            tx.execute(
+                "INSERT INTO messages (id, conversation_id, role, content, created_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![m.id, m.conversation_id, m.role, m.content, m.created_at],
+            )?;
+        }
+        for exported in &bundle.decisions {
+            let d = &exported.decision;
+            tx.execute(
+                "INSERT INTO decisions (id, conversation_id, title, status, summary_json, user_choice, user_choice_reasoning, outcome, outcome_date, outcome_score, debate_brief, debate_started_at, debate_completed_at, created_at, updated_at, due_date, debate_setup_json) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)",
+                params![
+                    d.id, d.conversation_id, d.title, d.status, d.summary_json, d.user_choice,
+                    d.user_choice_reasoning, d.outcome, d.outcome_date, d.outcome_score, d.debate_brief,
+                    d.debate_started_at, d.debate_completed_at, d.created_at, d.updated_at, d.due_date,
+                    exported.debate_setup_json
+                ],
+            )?;
+        }
+        for r in &bundle.debate_rounds {
+            tx.execute(
+                "INSERT INTO debate_rounds (id, decision_id, round_number, exchange_number, agent, content, created_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![r.id, r.decision_id, r.round_number, r.exchange_number, r.agent, r.content, r.created_at],
+            )?;
+        }
+        for a in &bundle.debate_audio {
+            tx.execute(
+                "INSERT INTO debate_audio (id, decision_id, manifest_json, total_duration_ms, generated_at, audio_dir) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![a.id, a.decision_id, a.manifest_json, a.total_duration_ms, a.generated_at, a.audio_dir],
+            )?;
+        }
+        for t in &bundle.tags {
+            tx.execute(
+                "INSERT INTO tags (decision_id, tag) VALUES (?1, ?2)",
+                params![t.decision_id, t.tag],
+            )?;
+        }
+        for h in &bundle.summary_history {
+            tx.execute(
+                "INSERT INTO summary_history (decision_id, summary_json, created_at) VALUES (?1, ?2, ?3)",
+                params![h.decision_id, h.summary_json, h.created_at],
+            )?;
+        }
+
+        tx.commit()
     }
 }
 
@@ -627,7 +1480,120 @@ mod tests {
     }
 
     #[test]
-    fn integration_delete_conversation_removes_messages_decision_and_debate_rounds() {
+    fn integration_export_import_round_trip_preserves_rows_and_ids() {
+        let source = new_test_db();
+        let conv = source.create_conversation("Move to Lisbon?").unwrap();
+        source.add_message(&conv.id, "user", "Should I move?").unwrap();
+        source.add_message(&conv.id, "assistant", "Let's look at the tradeoffs").unwrap();
+        source.set_conversation_system_prompt(&conv.id, Some("Be blunt")).unwrap();
+        let decision = source.create_decision(&conv.id, "Move to Lisbon").unwrap();
+        source.update_decision_outcome(&decision.id, "Moved, happy", Some(4)).unwrap();
+        source.add_decision_tag(&decision.id, "relocation").unwrap();
+        source.save_debate_round(&decision.id, 1, 1, "rationalist", "Numbers say yes").unwrap();
+        source.save_debate_round(&decision.id, 99, 1, "moderator", "Verdict").unwrap();
+        source.save_debate_audio(&decision.id, "{\"segments\":[]}", 1234, "/tmp/audio").unwrap();
+        source.update_decision_summary(&decision.id, r#"{"n":1}"#).unwrap();
+        source.update_decision_summary(&decision.id, r#"{"n":2}"#).unwrap();
+        let setup_json = r#"{"roster":["rationalist"],"shape":{"round2_exchanges":2,"include_round3":true}}"#;
+        source.update_debate_setup(&decision.id, setup_json).unwrap();
+        source.create_conversation("Empty chat").unwrap();
+
+        let bundle = source.export_bundle().unwrap();
+        assert_eq!(bundle.version, DATA_BUNDLE_VERSION);
+        let json = serde_json::to_string(&bundle).unwrap();
+        let parsed: DataBundle = serde_json::from_str(&json).unwrap();
+
+        let target = new_test_db();
+        assert!(!target.has_data().unwrap());
+        target.import_bundle(&parsed, false).unwrap();
+        assert!(target.has_data().unwrap());
+
+        let copy = target.export_bundle().unwrap();
+        assert_eq!(copy.conversations.len(), 2);
+        assert_eq!(copy.messages.len(), 2);
+        assert_eq!(copy.decisions.len(), 1);
+        assert_eq!(copy.debate_rounds.len(), 2);
+        assert_eq!(copy.debate_audio.len(), 1);
+        assert_eq!(copy.tags.len(), 1);
+        assert_eq!(copy.summary_history.len(), 2);
+
+        let restored = target.get_decision(&decision.id).unwrap().expect("decision id preserved");
+        assert_eq!(restored.conversation_id, conv.id);
+        assert_eq!(restored.outcome_score, Some(4));
+        assert_eq!(target.get_messages(&conv.id).unwrap()[1].content, "Let's look at the tradeoffs");
+        assert_eq!(target.get_conversation_system_prompt(&conv.id).unwrap().as_deref(), Some("Be blunt"));
+        assert_eq!(target.get_debate_audio(&decision.id).unwrap().unwrap().total_duration_ms, 1234);
+        assert_eq!(target.get_debate_setup(&decision.id).unwrap().as_deref(), Some(setup_json), "resume setup survives import");
+        assert!(target.undo_summary_update(&decision.id).unwrap(), "summary history survives import");
+        assert_eq!(target.get_decision(&decision.id).unwrap().unwrap().summary_json.as_deref(), Some(r#"{"n":1}"#));
+
+        // Overwrite replaces rather than duplicates
+        target.import_bundle(&parsed, true).unwrap();
+        assert_eq!(target.export_bundle().unwrap().messages.len(), 2);
+
+        // Conversations sitting in the trash don't count as existing data
+        let trashed_only = new_test_db();
+        let trashed = trashed_only.create_conversation("Old chat").unwrap();
+        trashed_only.delete_conversation(&trashed.id).unwrap();
+        assert!(!trashed_only.has_data().unwrap());
+    }
+
+    #[test]
+    fn integration_messages_page_walks_backwards_with_cursor() {
+        let db = new_test_db();
+        let conversation = db.create_conversation("Long chat").unwrap();
+        let other = db.create_conversation("Other").unwrap();
+        for i in 1..=5 {
+            db.add_message(&conversation.id, "user", &format!("m{}", i)).unwrap();
+        }
+        db.add_message(&other.id, "user", "elsewhere").unwrap();
+        let contents = |page: &[Message]| page.iter().map(|m| m.content.clone()).collect::<Vec<_>>();
+
+        let latest = db.get_messages_page(&conversation.id, 2, None).unwrap().unwrap();
+        assert_eq!(contents(&latest), vec!["m4", "m5"]);
+
+        let older = db.get_messages_page(&conversation.id, 2, Some(&latest[0].id)).unwrap().unwrap();
+        assert_eq!(contents(&older), vec!["m2", "m3"]);
+
+        let oldest = db.get_messages_page(&conversation.id, 2, Some(&older[0].id)).unwrap().unwrap();
+        assert_eq!(contents(&oldest), vec!["m1"]);
+        assert!(db.get_messages_page(&conversation.id, 2, Some(&oldest[0].id)).unwrap().unwrap().is_empty());
+
+        assert_eq!(db.get_messages(&conversation.id).unwrap().len(), 5);
+    }
+
+    #[test]
+    fn integration_messages_page_rejects_unknown_or_foreign_cursor() {
+        let db = new_test_db();
+        let conversation = db.create_conversation("Long chat").unwrap();
+        let other = db.create_conversation("Other").unwrap();
+        db.add_message(&conversation.id, "user", "mine").unwrap();
+        let foreign = db.add_message(&other.id, "user", "elsewhere").unwrap();
+
+        assert!(db.get_messages_page(&conversation.id, 2, Some("missing")).unwrap().is_none());
+        assert!(db.get_messages_page(&conversation.id, 2, Some(&foreign.id)).unwrap().is_none());
+        assert!(db.get_messages_page(&other.id, 2, Some(&foreign.id)).unwrap().unwrap().is_empty());
+    }
+
+    #[test]
+    fn integration_get_conversation_returns_row_or_none() {
+        let db = new_test_db();
+        let conversation = db
+            .create_conversation_with_type("Job offer", "decision")
+            .expect("conversation should be created");
+
+        let loaded = db
+            .get_conversation(&conversation.id)
+            .expect("lookup should succeed")
+            .expect("conversation should exist");
+        assert_eq!(loaded.title, "Job offer");
+        assert_eq!(loaded.conv_type, "decision");
+        assert_eq!(loaded.created_at, conversation.created_at);
+        assert!(db.get_conversation("missing").expect("lookup should succeed").is_none());
+    }
+
+    #[test]
+    fn integration_purge_conversation_removes_messages_decision_and_debate_rounds() {
         let db = new_test_db();
         let conversation = db
             .create_conversation_with_type("Move cities?", "decision")
@@ -641,8 +1607,8 @@ mod tests {
         db.save_debate_round(&decision.id, 1, 1, "rationalist", "Opening take")
             .expect("debate round should save");
 
-        db.delete_conversation(&conversation.id)
-            .expect("conversation should delete");
+        db.purge_conversation(&conversation.id)
+            .expect("conversation should purge");
 
         assert!(
             db.get_conversation(&conversation.id)
@@ -668,6 +1634,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn integration_delete_conversation_moves_to_trash_and_restore_brings_it_back() {
+        let db = new_test_db();
+        let chat = db.create_conversation("Weekend plans").unwrap();
+        let conversation = db.create_conversation_with_type("Move cities?", "decision").unwrap();
+        let decision = db.create_decision(&conversation.id, "Move cities?").unwrap();
+        db.add_message(&conversation.id, "user", "Thinking about relocating").unwrap();
+        db.save_debate_round(&decision.id, 1, 1, "rationalist", "Opening take").unwrap();
+
+        db.delete_conversation(&conversation.id).unwrap();
+
+        let visible: Vec<String> = db.get_conversations().unwrap().into_iter().map(|c| c.id).collect();
+        assert_eq!(visible, vec![chat.id.clone()]);
+        assert!(db.get_conversations_by_type("decision").unwrap().is_empty());
+        assert!(db.get_decisions().unwrap().is_empty());
+        assert!(db.get_decision_status_counts().unwrap().values().all(|&n| n == 0));
+        let trash = db.get_deleted_conversations().unwrap();
+        assert_eq!(trash.len(), 1);
+        assert_eq!(trash[0].id, conversation.id);
+
+        // Nothing is actually gone yet
+        assert_eq!(db.get_messages(&conversation.id).unwrap().len(), 1);
+        assert_eq!(db.get_debate_rounds(&decision.id).unwrap().len(), 1);
+
+        assert!(db.restore_conversation(&conversation.id).unwrap());
+        assert!(!db.restore_conversation(&conversation.id).unwrap());
+        assert!(!db.restore_conversation(&chat.id).unwrap());
+        assert_eq!(db.get_conversations().unwrap().len(), 2);
+        assert_eq!(db.get_decisions().unwrap().len(), 1);
+        assert!(db.get_deleted_conversations().unwrap().is_empty());
+    }
+
     #[test]
     fn e2e_decision_lifecycle_from_exploring_to_reviewed() {
         let db = new_test_db();
@@ -711,7 +1709,7 @@ mod tests {
 
         db.update_decision_choice(&decision.id, "Leave", Some("Better long-term growth"))
             .expect("choice should save");
-        db.update_decision_outcome(&decision.id, "Took offer and it improved trajectory")
+        db.update_decision_outcome(&decision.id, "Took offer and it improved trajectory", None)
             .expect("outcome should save");
 
         let final_decision = db
@@ -737,6 +1735,480 @@ mod tests {
         assert_eq!(rounds[1].round_number, 99);
     }
 
+    #[test]
+    fn integration_decision_tags_add_remove_and_filter() {
+        let db = new_test_db();
+        let career_conv = db
+            .create_conversation_with_type("New job?", "decision")
+            .expect("conversation should be created");
+        let career = db
+            .create_decision(&career_conv.id, "New job?")
+            .expect("decision should be created");
+        let finance_conv = db
+            .create_conversation_with_type("Buy a house?", "decision")
+            .expect("conversation should be created");
+        let finance = db
+            .create_decision(&finance_conv.id, "Buy a house?")
+            .expect("decision should be created");
+
+        db.add_decision_tag(&career.id, "career").expect("tag should add");
+        db.add_decision_tag(&career.id, "finance").expect("tag should add");
+        db.add_decision_tag(&career.id, "finance").expect("duplicate tag should be ignored");
+        db.add_decision_tag(&finance.id, "finance").expect("tag should add");
+
+        assert_eq!(
+            db.get_tags_for_decision(&career.id).expect("tags should load"),
+            vec!["career", "finance"]
+        );
+
+        let finance_decisions = db.get_decisions_by_tag("finance").expect("filter should succeed");
+        assert_eq!(finance_decisions.len(), 2);
+        let career_decisions = db.get_decisions_by_tag("career").expect("filter should succeed");
+        assert_eq!(career_decisions.len(), 1);
+        assert_eq!(career_decisions[0].id, career.id);
+
+        db.remove_decision_tag(&career.id, "finance").expect("tag should remove");
+        assert_eq!(
+            db.get_tags_for_decision(&career.id).expect("tags should load"),
+            vec!["career"]
+        );
+        assert_eq!(db.get_decisions_by_tag("finance").expect("filter should succeed").len(), 1);
+
+        let all_tags = db.get_all_decision_tags().expect("all tags should load");
+        assert_eq!(all_tags.get(&finance.id).map(Vec::len), Some(1));
+    }
+
+    #[test]
+    fn integration_decision_status_counts_include_empty_statuses() {
+        let db = new_test_db();
+        let seed = [
+            ("A", "exploring"),
+            ("B", "exploring"),
+            ("C", "exploring"),
+            ("D", "debating"),
+            ("E", "reviewed"),
+        ];
+        for (title, status) in seed {
+            let conv = db
+                .create_conversation_with_type(title, "decision")
+                .expect("conversation should be created");
+            let decision = db
+                .create_decision(&conv.id, title)
+                .expect("decision should be created");
+            db.update_decision_status(&decision.id, status)
+                .expect("status should update");
+        }
+        let standalone = db
+            .create_conversation_with_type("Standalone", "debate")
+            .expect("debate conversation should be created");
+        db.create_decision(&standalone.id, "Standalone")
+            .expect("standalone decision should be created");
+
+        let counts = db.get_decision_status_counts().expect("counts should load");
+        assert_eq!(counts.len(), DECISION_STATUSES.len());
+        assert_eq!(counts["exploring"], 3);
+        assert_eq!(counts["debating"], 1);
+        assert_eq!(counts["reviewed"], 1);
+        assert_eq!(counts["analyzing"], 0);
+        assert_eq!(counts["recommended"], 0);
+        assert_eq!(counts["decided"], 0);
+
+        let exploring = db.get_decisions_by_status("exploring").expect("filter should succeed");
+        assert_eq!(exploring.len(), 3);
+        assert!(exploring.iter().all(|d| d.status == "exploring"));
+        assert!(db.get_decisions_by_status("decided").expect("filter should succeed").is_empty());
+    }
+
+    #[test]
+    fn integration_outcome_reflection_message_persists_as_user_turn() {
+        let db = new_test_db();
+        let conversation = db
+            .create_conversation_with_type("Move cities?", "decision")
+            .expect("decision conversation should be created");
+        let decision = db
+            .create_decision(&conversation.id, "Move cities?")
+            .expect("decision should be created");
+        let summary = decisions::merge_summary(
+            None,
+            &json!({"recommendation": {"choice": "Move", "confidence": "medium", "reasoning": "Better market"}}),
+        );
+        db.update_decision_summary(&decision.id, &summary)
+            .expect("summary should update");
+        db.update_decision_choice(&decision.id, "Stay", Some("Family nearby"))
+            .expect("choice should save");
+        db.update_decision_outcome(&decision.id, "Stayed and found a remote role", None)
+            .expect("outcome should save");
+
+        let updated = db
+            .get_decision(&decision.id)
+            .expect("decision query should succeed")
+            .expect("decision should exist");
+        let content = decisions::outcome_reflection_message(
+            &updated.title,
+            updated.summary_json.as_deref(),
+            updated.user_choice.as_deref(),
+            updated.user_choice_reasoning.as_deref(),
+            updated.outcome.as_deref().unwrap_or_default(),
+        );
+        db.add_message(&conversation.id, "user", &content)
+            .expect("reflection message should save");
+
+        let messages = db.get_messages(&conversation.id).expect("messages should load");
+        let last = messages.last().expect("reflection message should exist");
+        assert_eq!(last.role, "user");
+        assert!(last.content.starts_with("[DECISION OUTCOME LOGGED]"));
+        assert!(last.content.contains("Your recommendation: Move (medium confidence)"));
+        assert!(last.content.contains("What I chose: Stay"));
+        assert!(last.content.contains("Stayed and found a remote role"));
+    }
+
+    #[test]
+    fn integration_outcome_score_migration_adds_nullable_column() {
+        let dir = tempfile::tempdir().expect("temp directory should exist");
+        let path = dir.path().join("old.sqlite");
+        {
+            let conn = Connection::open(&path).unwrap();
+            conn.execute_batch("
+                CREATE TABLE conversations (id TEXT PRIMARY KEY, title TEXT NOT NULL, type TEXT NOT NULL DEFAULT 'chat', created_at TEXT NOT NULL, updated_at TEXT NOT NULL);
+                CREATE TABLE decisions (id TEXT PRIMARY KEY, conversation_id TEXT NOT NULL, title TEXT NOT NULL, status TEXT NOT NULL DEFAULT 'exploring', summary_json TEXT, user_choice TEXT, user_choice_reasoning TEXT, outcome TEXT, outcome_date TEXT, debate_brief TEXT, debate_started_at TEXT, debate_completed_at TEXT, created_at TEXT NOT NULL, updated_at TEXT NOT NULL);
+                INSERT INTO conversations VALUES ('c1', 'Old', 'decision', '2024-01-01T00:00:00Z', '2024-01-01T00:00:00Z');
+                INSERT INTO decisions (id, conversation_id, title, status, outcome, created_at, updated_at) VALUES ('d1', 'c1', 'Old', 'reviewed', 'Went fine', '2024-01-01T00:00:00Z', '2024-01-01T00:00:00Z');
+            ").unwrap();
+        }
+
+        let db = Database::new(path.to_str().unwrap()).expect("old database should migrate");
+        let old = db.get_decision("d1").unwrap().expect("existing row should load");
+        assert_eq!(old.outcome.as_deref(), Some("Went fine"));
+        assert_eq!(old.outcome_score, None);
+        assert_eq!(db.get_decisions().unwrap()[0].outcome_score, None);
+
+        db.update_decision_outcome("d1", "Went great", Some(9)).unwrap();
+        assert_eq!(db.get_decision("d1").unwrap().unwrap().outcome_score, Some(5));
+        db.update_decision_outcome("d1", "Went badly", Some(0)).unwrap();
+        assert_eq!(db.get_decision("d1").unwrap().unwrap().outcome_score, Some(1));
+    }
+
+    #[test]
+    fn integration_due_date_migration_adds_nullable_column() {
+        let dir = tempfile::tempdir().expect("temp directory should exist");
+        let path = dir.path().join("old.sqlite");
+        {
+            let conn = Connection::open(&path).unwrap();
+            conn.execute_batch("
+                CREATE TABLE conversations (id TEXT PRIMARY KEY, title TEXT NOT NULL, type TEXT NOT NULL DEFAULT 'chat', created_at TEXT NOT NULL, updated_at TEXT NOT NULL);
+                CREATE TABLE decisions (id TEXT PRIMARY KEY, conversation_id TEXT NOT NULL, title TEXT NOT NULL, status TEXT NOT NULL DEFAULT 'exploring', summary_json TEXT, user_choice TEXT, user_choice_reasoning TEXT, outcome TEXT, outcome_date TEXT, outcome_score INTEGER, debate_brief TEXT, debate_started_at TEXT, debate_completed_at TEXT, created_at TEXT NOT NULL, updated_at TEXT NOT NULL);
+                INSERT INTO conversations VALUES ('c1', 'Old', 'decision', '2024-01-01T00:00:00Z', '2024-01-01T00:00:00Z');
+                INSERT INTO decisions (id, conversation_id, title, status, created_at, updated_at) VALUES ('d1', 'c1', 'Old', 'exploring', '2024-01-01T00:00:00Z', '2024-01-01T00:00:00Z');
+            ").unwrap();
+        }
+
+        let db = Database::new(path.to_str().unwrap()).expect("old database should migrate");
+        assert_eq!(db.get_decision("d1").unwrap().unwrap().due_date, None);
+        assert_eq!(db.get_decisions().unwrap()[0].due_date, None);
+
+        assert!(db.set_decision_due_date("d1", Some("2024-02-01T17:00:00Z")).unwrap());
+        assert_eq!(db.get_decision("d1").unwrap().unwrap().due_date.as_deref(), Some("2024-02-01T17:00:00Z"));
+        assert!(db.set_decision_due_date("d1", None).unwrap());
+        assert_eq!(db.get_decision("d1").unwrap().unwrap().due_date, None);
+        assert!(!db.set_decision_due_date("missing", None).unwrap());
+    }
+
+    #[test]
+    fn integration_due_soon_returns_open_decisions_inside_window_soonest_first() {
+        let db = Database::new(":memory:").expect("in-memory database should initialize");
+        let days = |n: i64| (Utc::now() + chrono::Duration::days(n)).to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+        let mut ids = HashMap::new();
+        for (title, due, status) in [
+            ("Offer expires", Some(days(5)), "exploring"),
+            ("Lease renewal", Some(days(2)), "decided"),
+            ("Overdue", Some(days(-1)), "analyzing"),
+            ("Next year", Some(days(40)), "exploring"),
+            ("Already reviewed", Some(days(1)), "reviewed"),
+            ("No deadline", None, "exploring"),
+        ] {
+            let conv = db.create_conversation_with_type(title, "decision").unwrap();
+            let decision = db.create_decision(&conv.id, title).unwrap();
+            db.update_decision_status(&decision.id, status).unwrap();
+            db.set_decision_due_date(&decision.id, due.as_deref()).unwrap();
+            ids.insert(title, decision.id);
+        }
+
+        let titles = |within: i64| -> Vec<String> {
+            db.get_decisions_due_soon(within).unwrap().into_iter().map(|d| d.title).collect()
+        };
+        assert_eq!(titles(7), vec!["Overdue", "Lease renewal", "Offer expires"]);
+        assert_eq!(titles(3), vec!["Overdue", "Lease renewal"]);
+        assert_eq!(titles(60).len(), 4);
+        assert!(db.get_decisions().unwrap().iter().any(|d| d.id == ids["Next year"] && d.due_date.is_some()));
+    }
+
+    #[test]
+    fn integration_lookup_indexes_exist_on_new_and_migrated_databases() {
+        fn index_names(db: &Database, table: &str) -> Vec<String> {
+            let conn = db.conn.lock().unwrap();
+            let mut stmt = conn.prepare(&format!("PRAGMA index_list('{}')", table)).unwrap();
+            let names = stmt.query_map([], |r| r.get::<_, String>(1)).unwrap();
+            names.collect::<Result<_, _>>().unwrap()
+        }
+
+        let db = new_test_db();
+        assert!(index_names(&db, "messages").contains(&"idx_messages_conv".to_string()));
+        assert!(index_names(&db, "debate_rounds").contains(&"idx_rounds_decision".to_string()));
+
+        let dir = tempfile::tempdir().expect("temp directory should exist");
+        let path = dir.path().join("old.sqlite");
+        {
+            let conn = Connection::open(&path).unwrap();
+            conn.execute_batch("
+                CREATE TABLE messages (id TEXT PRIMARY KEY, conversation_id TEXT NOT NULL, role TEXT NOT NULL, content TEXT NOT NULL, created_at TEXT NOT NULL);
+                CREATE TABLE debate_rounds (id TEXT PRIMARY KEY, decision_id TEXT NOT NULL, round_number INTEGER NOT NULL, exchange_number INTEGER DEFAULT 1, agent TEXT NOT NULL, content TEXT NOT NULL, created_at TEXT NOT NULL);
+            ").unwrap();
+        }
+        let migrated = Database::new(path.to_str().unwrap()).expect("old database should migrate");
+        assert!(index_names(&migrated, "messages").contains(&"idx_messages_conv".to_string()));
+        assert!(index_names(&migrated, "debate_rounds").contains(&"idx_rounds_decision".to_string()));
+    }
+
+    #[test]
+    fn integration_system_prompt_override_migrates_and_round_trips() {
+        let dir = tempfile::tempdir().expect("temp directory should exist");
+        let path = dir.path().join("old.sqlite");
+        {
+            let conn = Connection::open(&path).unwrap();
+            conn.execute_batch("
+                CREATE TABLE conversations (id TEXT PRIMARY KEY, title TEXT NOT NULL, type TEXT NOT NULL DEFAULT 'chat', created_at TEXT NOT NULL, updated_at TEXT NOT NULL);
+                INSERT INTO conversations VALUES ('c1', 'Old', 'chat', '2024-01-01T00:00:00Z', '2024-01-01T00:00:00Z');
+            ").unwrap();
+        }
+
+        let db = Database::new(path.to_str().unwrap()).expect("old database should migrate");
+        assert_eq!(db.get_conversation_system_prompt("c1").unwrap(), None);
+
+        assert!(db.set_conversation_system_prompt("c1", Some("Be extremely terse.")).unwrap());
+        assert_eq!(
+            db.get_conversation_system_prompt("c1").unwrap().as_deref(),
+            Some("Be extremely terse.")
+        );
+        assert!(db.set_conversation_system_prompt("c1", None).unwrap());
+        assert_eq!(db.get_conversation_system_prompt("c1").unwrap(), None);
+        assert!(!db.set_conversation_system_prompt("missing", Some("x")).unwrap());
+    }
+
+    #[test]
+    fn integration_undo_summary_update_restores_previous_summary() {
+        let db = Database::new(":memory:").expect("in-memory database should initialize");
+        let conv = db.create_conversation_with_type("Adopt a dog?", "decision").unwrap();
+        let decision = db.create_decision(&conv.id, "Adopt a dog?").unwrap();
+        assert!(!db.undo_summary_update(&decision.id).unwrap(), "nothing to undo yet");
+
+        db.update_decision_summary(&decision.id, r#"{"recommendation":{"choice":"Adopt"}}"#).unwrap();
+        db.update_decision_summary(&decision.id, r#"{"recommendation":{"choice":"Wait"}}"#).unwrap();
+        db.update_decision_summary(&decision.id, r#"{"recommendation":{"choice":"Wait"}}"#).unwrap();
+        assert!(db.undo_summary_update(&decision.id).unwrap(), "an unchanged rewrite adds no history");
+        let restored = db.get_decision(&decision.id).unwrap().unwrap();
+        assert_eq!(restored.summary_json.as_deref(), Some(r#"{"recommendation":{"choice":"Adopt"}}"#));
+
+        assert!(db.undo_summary_update(&decision.id).unwrap());
+        assert_eq!(db.get_decision(&decision.id).unwrap().unwrap().summary_json, None);
+        assert!(!db.undo_summary_update(&decision.id).unwrap());
+
+        for i in 0..SUMMARY_HISTORY_DEPTH + 5 {
+            db.update_decision_summary(&decision.id, &format!(r#"{{"n":{}}}"#, i)).unwrap();
+        }
+        let mut undone = 0;
+        while db.undo_summary_update(&decision.id).unwrap() {
+            undone += 1;
+        }
+        assert_eq!(undone, SUMMARY_HISTORY_DEPTH, "history depth is capped");
+        assert_eq!(db.get_decision(&decision.id).unwrap().unwrap().summary_json.as_deref(), Some(r#"{"n":4}"#));
+    }
+
+    #[test]
+    fn integration_delete_debate_round_removes_only_that_round() {
+        let db = new_test_db();
+        let conversation = db.create_conversation_with_type("Rounds", "decision").unwrap();
+        let decision = db.create_decision(&conversation.id, "Rounds").unwrap();
+        let keep = db.save_debate_round(&decision.id, 1, 1, "rationalist", "Opening A").unwrap();
+        let bad = db.save_debate_round(&decision.id, 1, 1, "advocate", "<<garbage>>").unwrap();
+        let later = db.save_debate_round(&decision.id, 2, 1, "rationalist", "Rebuttal A").unwrap();
+
+        assert!(db.delete_debate_round(&bad.id).expect("delete should succeed"));
+        assert!(!db.delete_debate_round(&bad.id).unwrap(), "second delete is a no-op");
+        assert!(db.get_debate_round(&bad.id).unwrap().is_none());
+
+        let remaining = db.get_debate_rounds(&decision.id).unwrap();
+        let ids: Vec<&str> = remaining.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec![keep.id.as_str(), later.id.as_str()]);
+        assert_eq!(remaining[0].content, "Opening A");
+        assert_eq!(remaining[1].content, "Rebuttal A");
+    }
+
+    #[test]
+    fn integration_get_debate_brief_returns_saved_brief() {
+        let db = new_test_db();
+        let conversation = db.create_conversation_with_type("Brief", "decision").unwrap();
+        let decision = db.create_decision(&conversation.id, "Brief").unwrap();
+
+        assert_eq!(db.get_debate_brief(&decision.id).unwrap(), None);
+        db.update_debate_brief(&decision.id, "## Decision\nBrief").expect("brief should save");
+        assert_eq!(
+            db.get_debate_brief(&decision.id).unwrap().as_deref(),
+            Some("## Decision\nBrief")
+        );
+        assert_eq!(db.get_debate_brief("missing").unwrap(), None);
+    }
+
+    #[test]
+    fn integration_decisions_with_audio_flag_marks_only_narrated_debates() {
+        let db = new_test_db();
+        let narrated_conv = db.create_conversation_with_type("Narrated", "decision").unwrap();
+        let narrated = db.create_decision(&narrated_conv.id, "Narrated").unwrap();
+        let silent_conv = db.create_conversation_with_type("Silent", "decision").unwrap();
+        let silent = db.create_decision(&silent_conv.id, "Silent").unwrap();
+        db.save_debate_audio(&narrated.id, "{}", 1200, "/tmp/debates/narrated")
+            .expect("audio should save");
+
+        let rows = db.get_decisions_with_audio_flag().expect("rows should load");
+        assert_eq!(rows.len(), 2);
+        let flag = |id: &str| rows.iter().find(|r| r.id == id).map(|r| r.has_audio);
+        assert_eq!(flag(&narrated.id), Some(true));
+        assert_eq!(flag(&silent.id), Some(false));
+    }
+
+    #[test]
+    fn integration_rename_conversation_updates_title_and_linked_decision() {
+        let db = new_test_db();
+        let conversation = db
+            .create_conversation_with_type("Untitled decision", "decision")
+            .expect("decision conversation should be created");
+        let decision = db
+            .create_decision(&conversation.id, "Untitled decision")
+            .expect("decision should be created");
+
+        db.update_conversation_title(&conversation.id, "Take the Berlin offer?")
+            .expect("rename should succeed");
+
+        let renamed = db
+            .get_conversation(&conversation.id)
+            .expect("conversation query should succeed")
+            .expect("conversation should exist");
+        assert_eq!(renamed.title, "Take the Berlin offer?");
+        assert!(renamed.updated_at >= conversation.updated_at);
+        let decision = db.get_decision(&decision.id).unwrap().unwrap();
+        assert_eq!(decision.title, "Take the Berlin offer?");
+
+        let debate = db.create_conversation_with_type("Debate", "debate").unwrap();
+        let debate_decision = db.create_decision(&debate.id, "Debate prompt").unwrap();
+        db.update_conversation_title(&debate.id, "Renamed debate").unwrap();
+        assert_eq!(db.get_conversation(&debate.id).unwrap().unwrap().title, "Renamed debate");
+        assert_eq!(db.get_decision(&debate_decision.id).unwrap().unwrap().title, "Debate prompt");
+    }
+
+    #[test]
+    fn integration_related_decisions_share_a_conversation() {
+        let db = new_test_db();
+        let conversation = db.create_conversation_with_type("Berlin offer", "decision").unwrap();
+        let first = db.create_decision(&conversation.id, "Take the Berlin offer?").unwrap();
+        let second = db.create_decision(&conversation.id, "Negotiate remote work instead?").unwrap();
+        let other_conv = db.create_conversation_with_type("Car", "decision").unwrap();
+        db.create_decision(&other_conv.id, "Buy a car?").unwrap();
+
+        let related = db.get_related_decisions(&second.id).expect("query should succeed");
+        let ids: Vec<&str> = related.iter().map(|d| d.id.as_str()).collect();
+        assert_eq!(ids, vec![first.id.as_str(), second.id.as_str()]);
+        assert_eq!(db.get_related_decisions(&first.id).unwrap().len(), 2);
+        assert!(db.get_related_decisions("missing").unwrap().is_empty());
+    }
+
+    #[test]
+    fn integration_promote_conversation_to_decision_links_once() {
+        let db = new_test_db();
+        let conversation = db.create_conversation("Should I take the Berlin offer?").unwrap();
+        db.add_message(&conversation.id, "user", "I got an offer in Berlin.").unwrap();
+
+        let decision = db
+            .promote_conversation_to_decision(&conversation.id, &conversation.title)
+            .expect("promotion should succeed")
+            .expect("first promotion should create a decision");
+        assert_eq!(decision.title, "Should I take the Berlin offer?");
+        assert_eq!(decision.status, "exploring");
+        assert_eq!(db.get_conversation(&conversation.id).unwrap().unwrap().conv_type, "decision");
+        assert_eq!(
+            db.get_decision_by_conversation(&conversation.id).unwrap().unwrap().id,
+            decision.id
+        );
+        assert_eq!(db.get_messages(&conversation.id).unwrap().len(), 1);
+
+        let again = db
+            .promote_conversation_to_decision(&conversation.id, &conversation.title)
+            .expect("second promotion should not error");
+        assert!(again.is_none());
+        assert_eq!(db.get_decisions().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn integration_replace_last_message_swaps_only_trailing_assistant_reply() {
+        let db = new_test_db();
+        let conversation = db
+            .create_conversation("Regenerate")
+            .expect("conversation should be created");
+        db.add_message(&conversation.id, "user", "First question").unwrap();
+        db.add_message(&conversation.id, "assistant", "First answer").unwrap();
+        db.add_message(&conversation.id, "user", "Follow-up").unwrap();
+        let weak = db.add_message(&conversation.id, "assistant", "Weak answer").unwrap();
+
+        assert_eq!(db.get_last_message(&conversation.id).unwrap().unwrap().id, weak.id);
+        let replaced = db
+            .replace_last_message(&conversation.id, "assistant", "Better answer")
+            .expect("replace should succeed")
+            .expect("trailing assistant reply should be replaced");
+        assert_eq!(replaced.content, "Better answer");
+
+        let messages = db.get_messages(&conversation.id).expect("messages should load");
+        assert_eq!(messages.len(), 4);
+        assert_eq!(messages[2].content, "Follow-up");
+        assert_eq!(messages[3].content, "Better answer");
+        assert!(db.get_message(&weak.id).unwrap().is_none());
+
+        db.add_message(&conversation.id, "user", "Another question").unwrap();
+        let refused = db
+            .replace_last_message(&conversation.id, "assistant", "Unwanted")
+            .expect("replace should succeed");
+        assert!(refused.is_none());
+        let messages = db.get_messages(&conversation.id).unwrap();
+        assert_eq!(messages.len(), 5);
+        assert_eq!(messages[4].content, "Another question");
+    }
+
+    #[test]
+    fn integration_edit_message_truncates_later_messages() {
+        let db = new_test_db();
+        let conversation = db.create_conversation("Edit").expect("conversation should be created");
+        db.add_message(&conversation.id, "user", "First question").unwrap();
+        db.add_message(&conversation.id, "assistant", "First answer").unwrap();
+        let typo = db.add_message(&conversation.id, "user", "Folow-up wiht typos").unwrap();
+        db.add_message(&conversation.id, "assistant", "Confused answer").unwrap();
+        db.add_message(&conversation.id, "user", "Never mind").unwrap();
+
+        let reply = db
+            .rewrite_message_with_reply(&typo.id, "Follow-up with no typos", "Clear answer")
+            .expect("rewrite should succeed")
+            .expect("edited message should exist");
+        assert_eq!(reply.role, "assistant");
+
+        let messages = db.get_messages(&conversation.id).expect("messages should load");
+        assert_eq!(messages.len(), 4);
+        assert_eq!(messages[2].id, typo.id);
+        assert_eq!(messages[2].content, "Follow-up with no typos");
+        assert_eq!(messages[3].id, reply.id);
+        assert_eq!(messages[3].content, "Clear answer");
+        assert_eq!(db.get_message(&typo.id).unwrap().unwrap().content, "Follow-up with no typos");
+
+        assert!(db.rewrite_message_with_reply("missing", "text", "reply").unwrap().is_none());
+        assert_eq!(db.get_messages(&conversation.id).unwrap().len(), 4);
+        assert!(db.get_message("missing").unwrap().is_none());
+    }
+
     #[test]
     fn integration_debate_audio_persists_audio_dir_and_repairs_swapped_values() {
         let db = new_test_db();
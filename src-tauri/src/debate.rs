@@ -1,6 +1,7 @@
 use crate::agents::{self, AgentInfo};
 use crate::commands::AppState;
 use crate::config;
+use crate::db::{Database, Decision};
 use crate::decisions;
 use crate::llm;
 use crate::profile;
@@ -8,6 +9,7 @@ use crate::tts;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use tauri::{Emitter, Manager};
@@ -24,8 +26,73 @@ pub struct StandaloneDebateConfig {
     pub max_exchanges: Option<u32>,
 }
 
+/// Verbal tics some models open every turn with. Matched case-insensitively at the
+/// very start of a statement, including the trailing comma.
+const FILLER_OPENERS: &[&str] = &[
+    "look, ",
+    "honestly, ",
+    "here's the thing, ",
+    "here's the thing: ",
+    "okay, so ",
+    "ok, so ",
+    "so, ",
+    "well, ",
+    "listen, ",
+    "frankly, ",
+];
+
+/// Drop leading filler openers (repeatedly, so "Look, honestly, ..." loses both)
+/// as long as something follows, and re-capitalize what remains.
+fn strip_filler_openers(text: &str) -> String {
+    let mut rest = text;
+    loop {
+        let lower = rest.to_ascii_lowercase();
+        let Some(opener) = FILLER_OPENERS.iter().find(|o| lower.starts_with(*o)) else {
+            break;
+        };
+        let remainder = rest[opener.len()..].trim_start();
+        if remainder.is_empty() {
+            break;
+        }
+        rest = remainder;
+    }
+    if rest.len() == text.len() {
+        return text.to_string();
+    }
+    let mut chars = rest.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Drop a leading "<Label>:" or "The <Label>:" naming the speaker. Models pick this
+/// up from the transcript format, and `format_transcript` adds the label itself.
+fn strip_speaker_label<'a>(line: &'a str, label: &str) -> &'a str {
+    let label = label.trim();
+    let bare = match label.get(..4) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("the ") => label[4..].trim_start(),
+        _ => label,
+    };
+    if bare.is_empty() {
+        return line;
+    }
+    for candidate in [bare.to_string(), format!("The {}", bare)] {
+        let matches = line
+            .get(..candidate.len())
+            .is_some_and(|head| head.eq_ignore_ascii_case(&candidate));
+        if matches {
+            if let Some(rest) = line[candidate.len()..].strip_prefix(':') {
+                return rest.trim_start();
+            }
+        }
+    }
+    line
+}
+
 /// Normalize model output so spoken debate feels conversational in UI + TTS.
-fn normalize_spoken_debate_output(text: &str) -> String {
+/// When `speaker_label` is given, a leading echo of it ("The Rationalist:") is removed.
+pub fn normalize_spoken_debate_output(text: &str, speaker_label: Option<&str>) -> String {
     let labels = [
         "position:",
         "key argument:",
@@ -63,6 +130,10 @@ fn normalize_spoken_debate_output(text: &str) -> String {
             .replace("__", "")
             .replace('`', "");
 
+        if let (true, Some(label)) = (parts.is_empty(), speaker_label) {
+            cleaned = strip_speaker_label(&cleaned, label).to_string();
+        }
+
         let lower = cleaned.to_ascii_lowercase();
         for label in labels {
             if lower.starts_with(label) {
@@ -105,18 +176,55 @@ fn normalize_spoken_debate_output(text: &str) -> String {
     if compact.is_empty() {
         text.trim().to_string()
     } else {
-        compact
+        strip_filler_openers(&compact)
     }
 }
 
 /// Shared state for live TTS generation during debate.
 struct LiveTtsState {
     enabled: bool,
+    /// Generate all audio in one pass after the debate instead of per segment.
+    post_generate: bool,
     config: config::AppConfig,
     registry: Vec<AgentInfo>,
     app_data_dir: std::path::PathBuf,
     segment_counter: Arc<AtomicUsize>,
     handles: Arc<Mutex<Vec<tokio::task::JoinHandle<Option<tts::AudioSegment>>>>>,
+    /// Caps concurrent provider requests at `config.tts_concurrency`.
+    permits: Arc<tokio::sync::Semaphore>,
+}
+
+impl LiveTtsState {
+    fn new(
+        config: config::AppConfig,
+        registry: Vec<AgentInfo>,
+        app_data_dir: std::path::PathBuf,
+        first_segment_index: usize,
+    ) -> Self {
+        let has_key = tts::has_tts_key(&config);
+        let mode = tts::TtsMode::from_config_value(&config.tts_mode);
+        let permits = Arc::new(tokio::sync::Semaphore::new(config.tts_concurrency.max(1)));
+        Self {
+            enabled: has_key && mode == tts::TtsMode::Live,
+            post_generate: has_key && mode == tts::TtsMode::Post,
+            config,
+            registry,
+            app_data_dir,
+            segment_counter: Arc::new(AtomicUsize::new(first_segment_index)),
+            handles: Arc::new(Mutex::new(Vec::new())),
+            permits,
+        }
+    }
+}
+
+/// Run one live TTS request while holding a permit from `permits`. Retries
+/// happen inside `tts`, so the permit stays held while a segment backs off.
+async fn throttled_tts<T, Fut>(permits: Arc<tokio::sync::Semaphore>, request: Fut) -> Result<T, String>
+where
+    Fut: std::future::Future<Output = Result<T, String>>,
+{
+    let _permit = permits.acquire_owned().await.map_err(|e| e.to_string())?;
+    request.await
 }
 
 /// Spawn a TTS generation task for a single debate round segment.
@@ -136,14 +244,21 @@ fn spawn_segment_tts(
     let reg = tts_state.registry.clone();
     let add = tts_state.app_data_dir.clone();
     let handles = Arc::clone(&tts_state.handles);
+    let permits = Arc::clone(&tts_state.permits);
+    let gap_ms = tts_state.config.segment_gap_ms;
 
     let handle = tokio::spawn(async move {
         let mut spoken_round = round_clone;
-        spoken_round.content = normalize_spoken_debate_output(&spoken_round.content);
-        match tts::generate_segment_audio(
-            &did, segment_index, &spoken_round, &cfg, &reg, &add,
-        ).await {
+        spoken_round.content = normalize_spoken_debate_output(&spoken_round.content, None);
+        let result = throttled_tts(
+            permits,
+            tts::generate_segment_audio(&did, segment_index, &spoken_round, &cfg, &reg, &add),
+        ).await;
+        match result {
             Ok(segment) => {
+                if let Err(e) = tts::append_partial_manifest(&add, &did, segment.clone(), gap_ms) {
+                    eprintln!("Failed to record segment {} in the partial manifest: {}", segment_index, e);
+                }
                 let audio_dir = add.join("debates").join(&did);
                 let _ = ah.emit("debate-segment-audio-ready", json!({
                     "decision_id": did,
@@ -173,41 +288,185 @@ fn spawn_segment_tts(
     let _ = handles.lock().map(|mut h| h.push(handle));
 }
 
+/// Whether a decision can be debated yet, and if not, what the user still needs to add.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DebateReadiness {
+    pub ready: bool,
+    pub missing: Vec<String>,
+}
+
+fn summary_list_is_empty(summary: &Value, key: &str) -> bool {
+    summary.get(key)
+        .and_then(|v| v.as_array())
+        .map(|a| a.is_empty())
+        .unwrap_or(true)
+}
+
+/// What a decision's summary still lacks before it can be debated, phrased as
+/// actions ("add at least one key variable"). A missing or unreadable summary lacks both.
+pub fn debate_readiness(decision: &Decision) -> DebateReadiness {
+    let summary = decision.summary_json.as_deref()
+        .and_then(|s| serde_json::from_str::<Value>(s).ok())
+        .unwrap_or(Value::Null);
+    let mut missing = Vec::new();
+    if summary_list_is_empty(&summary, "options") {
+        missing.push("add at least one option".to_string());
+    }
+    if summary_list_is_empty(&summary, "variables") {
+        missing.push("add at least one key variable".to_string());
+    }
+    DebateReadiness { ready: missing.is_empty(), missing }
+}
+
+/// Check that a decision has enough structure to be debated:
+/// at least one option and one variable in its summary.
+pub fn check_debate_preconditions(decision: &Decision) -> Result<(), String> {
+    let Some(ref summary_json) = decision.summary_json else {
+        return Err("Decision has no summary data. Chat with the AI first to build context.".to_string());
+    };
+    serde_json::from_str::<Value>(summary_json)
+        .map_err(|_| "Invalid summary JSON".to_string())?;
+    let readiness = debate_readiness(decision);
+    if !readiness.ready {
+        return Err(format!(
+            "Decision isn't ready for a debate yet: {}.",
+            readiness.missing.join(" and ")
+        ));
+    }
+    Ok(())
+}
+
+/// Rank options by average `alignment_score` across their pros_cons entries,
+/// highest first. Options with no score keep summary order at the end.
+pub fn pre_read_ranking(summary: &Value) -> Vec<(String, Option<f64>)> {
+    let mut names: Vec<String> = Vec::new();
+    let mut scores: HashMap<String, Vec<f64>> = HashMap::new();
+
+    if let Some(options) = summary.get("options").and_then(|v| v.as_array()) {
+        for label in options.iter().filter_map(|o| o["label"].as_str()) {
+            if !names.iter().any(|n| n == label) {
+                names.push(label.to_string());
+            }
+        }
+    }
+    if let Some(pc) = summary.get("pros_cons").and_then(|v| v.as_array()) {
+        for entry in pc {
+            let Some(option) = entry["option"].as_str() else { continue };
+            if !names.iter().any(|n| n == option) {
+                names.push(option.to_string());
+            }
+            if let Some(score) = entry["alignment_score"].as_f64() {
+                scores.entry(option.to_string()).or_default().push(score);
+            }
+        }
+    }
+
+    let mut ranking: Vec<(String, Option<f64>)> = names
+        .into_iter()
+        .map(|name| {
+            let avg = scores
+                .get(&name)
+                .map(|s| s.iter().sum::<f64>() / s.len() as f64);
+            (name, avg)
+        })
+        .collect();
+    // Stable sort: scored options by descending score, unscored after in original order
+    ranking.sort_by(|a, b| match (a.1, b.1) {
+        (Some(x), Some(y)) => y.total_cmp(&x),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    });
+    ranking
+}
+
+/// The "Committee Pre-Read Ranking" brief section, or None when there are no options.
+fn format_pre_read_ranking(summary: &Value) -> Option<String> {
+    let ranking = pre_read_ranking(summary);
+    if ranking.is_empty() {
+        return None;
+    }
+    let lines: Vec<String> = ranking
+        .iter()
+        .enumerate()
+        .map(|(i, (name, score))| match score {
+            Some(s) => format!("{}. **{}** (alignment: {:.1}/10)", i + 1, name, s),
+            None => format!("{}. **{}** (not yet scored)", i + 1, name),
+        })
+        .collect();
+    Some(format!("## Committee Pre-Read Ranking\n{}", lines.join("\n")))
+}
+
+const CONVERSATION_OMITTED_MARKER: &str = "[earlier conversation omitted]";
+
+/// Join conversation lines for the brief, keeping only the most recent ones that
+/// fit in `char_limit` characters (0 = no limit). When anything is dropped the
+/// result starts with `[earlier conversation omitted]`; a single message longer
+/// than the whole budget keeps only its tail.
+fn truncate_conversation(lines: &[String], char_limit: usize) -> String {
+    let full = lines.join("\n\n");
+    if char_limit == 0 || full.chars().count() <= char_limit {
+        return full;
+    }
+
+    let mut kept: Vec<&str> = Vec::new();
+    let mut used = 0;
+    for line in lines.iter().rev() {
+        let cost = line.chars().count() + if kept.is_empty() { 0 } else { 2 };
+        if used + cost > char_limit {
+            break;
+        }
+        used += cost;
+        kept.push(line);
+    }
+    kept.reverse();
+
+    let recent = if kept.is_empty() {
+        let last = lines.last().map(|l| l.as_str()).unwrap_or("");
+        let skip = last.chars().count().saturating_sub(char_limit);
+        last.chars().skip(skip).collect::<String>()
+    } else {
+        kept.join("\n\n")
+    };
+    format!("{}\n\n{}", CONVERSATION_OMITTED_MARKER, recent)
+}
+
 /// Build the decision brief from profile files + decision data + conversation messages.
-fn compile_brief(
-    app_handle: &tauri::AppHandle,
+pub fn compile_brief(
+    db: &Database,
+    app_data_dir: &PathBuf,
     decision_id: &str,
 ) -> Result<String, String> {
-    let state: tauri::State<'_, Mutex<AppState>> = app_handle.state();
-    let state_guard = state.lock().map_err(|e| e.to_string())?;
-
-    let decision = state_guard.db
+    let decision = db
         .get_decision(decision_id)
         .map_err(|e| e.to_string())?
         .ok_or("Decision not found")?;
 
-    // Read profile files
-    let profiles = profile::read_all_profiles(&state_guard.app_data_dir)
+    // Read profile files (sorted so the brief is stable between preview and run)
+    let profiles = profile::read_all_profiles(app_data_dir)
         .unwrap_or_default();
+    let mut profile_names: Vec<&String> = profiles.keys().collect();
+    profile_names.sort();
     let profile_text = if profiles.is_empty() {
         "No profile information available.".to_string()
     } else {
-        profiles
+        profile_names
             .iter()
-            .map(|(name, content)| format!("### {}\n{}", name, content))
+            .map(|name| format!("### {}\n{}", name, profiles[*name]))
             .collect::<Vec<_>>()
             .join("\n\n")
     };
 
     // Get conversation messages for context
-    let messages = state_guard.db
+    let messages = db
         .get_messages(&decision.conversation_id)
         .map_err(|e| e.to_string())?;
-    let conversation_summary = messages
+    let conversation_lines: Vec<String> = messages
         .iter()
         .map(|m| format!("{}: {}", if m.role == "user" { "User" } else { "AI" }, m.content))
-        .collect::<Vec<_>>()
-        .join("\n\n");
+        .collect();
+    let char_limit = config::load_config(app_data_dir).brief_conversation_char_limit;
+    let conversation_summary = truncate_conversation(&conversation_lines, char_limit);
 
     // Parse summary
     let summary_text = if let Some(ref sj) = decision.summary_json {
@@ -238,12 +497,16 @@ fn compile_brief(
                     let option = p["option"].as_str().unwrap_or("?");
                     let pros = p["pros"].as_array().map(|arr| arr.iter().filter_map(|v| v.as_str()).map(|s| format!("  + {}", s)).collect::<Vec<_>>().join("\n")).unwrap_or_default();
                     let cons = p["cons"].as_array().map(|arr| arr.iter().filter_map(|v| v.as_str()).map(|s| format!("  - {}", s)).collect::<Vec<_>>().join("\n")).unwrap_or_default();
-                    let score = p["alignment_score"].as_i64().map(|s| format!(" (alignment: {}/10)", s)).unwrap_or_default();
+                    let score = p["alignment_score"].as_f64().map(|s| format!(" (alignment: {}/10)", s)).unwrap_or_default();
                     format!("### {}{}\nPros:\n{}\nCons:\n{}", option, score, pros, cons)
                 }).collect();
                 parts.push(format!("## Initial Analysis\n{}", analysis.join("\n\n")));
             }
 
+            if let Some(ranking) = format_pre_read_ranking(&summary) {
+                parts.push(ranking);
+            }
+
             parts.join("\n\n")
         } else {
             "No structured summary available.".to_string()
@@ -252,7 +515,7 @@ fn compile_brief(
         "No structured summary available.".to_string()
     };
 
-    let brief = format!(
+    let mut brief = format!(
         r#"# Decision Brief
 
 ## About the Person
@@ -268,6 +531,15 @@ fn compile_brief(
         title = decision.title,
     );
 
+    let documents = decisions::list_context_files(app_data_dir, decision_id)?;
+    if !documents.is_empty() {
+        let sections: Vec<String> = documents
+            .iter()
+            .map(|doc| format!("### {}\n{}", doc.filename, doc.content.trim()))
+            .collect();
+        brief.push_str(&format!("\n\n## Supporting Documents\n{}", sections.join("\n\n")));
+    }
+
     Ok(brief)
 }
 
@@ -336,11 +608,19 @@ Keep the final verdict short and decisive:
 - At most 2 next steps"#
 }
 
-fn standalone_moderator_prompt(brief: &str, transcript: &str, participants: &str) -> String {
+fn standalone_moderator_prompt(brief: &str, transcript: &str, debaters: &[AgentInfo]) -> String {
+    let names = agents::format_participant_names(debaters);
+    let participants = if debaters.len() == 1 {
+        format!(
+            "Only {names} took part, so this was a single model's view rather than a debate. In the Verdict, name the strongest objection it left unanswered."
+        )
+    } else {
+        format!("The following models participated: {names}")
+    };
     format!(
         r#"{brief}
 
-The following models participated: {participants}
+{participants}
 
 Here is the full debate transcript:
 
@@ -403,7 +683,7 @@ fn parse_json_object(raw: &str) -> Option<Value> {
 }
 
 async fn request_moderator_direction(
-    api_key: &str,
+    endpoint: &llm::LlmEndpoint,
     moderator_model: &str,
     brief: &str,
     transcript: &str,
@@ -411,7 +691,7 @@ async fn request_moderator_direction(
     exchange_number: i32,
 ) -> Result<(String, bool), String> {
     let raw = llm::call_llm_simple(
-        api_key,
+        endpoint,
         moderator_model,
         standalone_moderator_steering_system_prompt(),
         &standalone_moderator_steering_prompt(brief, transcript, participants, exchange_number),
@@ -444,6 +724,67 @@ async fn request_moderator_direction(
     Ok((direction, conclude))
 }
 
+/// Transcript heading for one round/exchange.
+fn round_header(round_number: i32, exchange_number: i32) -> String {
+    match round_number {
+        1 => "Round 1 (opening)".to_string(),
+        2 => format!("Round 2 (exchange {})", exchange_number),
+        3 => "Round 3 (final statements)".to_string(),
+        99 => "Moderator synthesis".to_string(),
+        _ => format!("Round {}", round_number),
+    }
+}
+
+/// Settings for shrinking the moderator's transcript (see `compress_transcript`).
+#[derive(Debug, Clone)]
+struct TranscriptCompression {
+    enabled: bool,
+    token_budget: u32,
+    model: String,
+}
+
+/// Whether a transcript is too long to hand the moderator verbatim.
+fn transcript_exceeds_budget(transcript: &str, token_budget: u32) -> bool {
+    llm::estimate_tokens(transcript) > token_budget as usize
+}
+
+/// Summarize each round/exchange with a cheap model so the moderator prompt fits
+/// a smaller context window. Only the prompt is compressed; the saved rounds are
+/// untouched. Returns an error if any round can't be summarized.
+async fn compress_transcript(
+    endpoint: &llm::LlmEndpoint,
+    model: &str,
+    rounds: &[crate::db::DebateRound],
+    all_agents: &[AgentInfo],
+) -> Result<String, String> {
+    let mut groups: Vec<Vec<crate::db::DebateRound>> = Vec::new();
+    for r in rounds.iter().filter(|r| r.round_number != 99) {
+        match groups.last_mut() {
+            Some(group) if group[0].round_number == r.round_number && group[0].exchange_number == r.exchange_number => {
+                group.push(r.clone())
+            }
+            _ => groups.push(vec![r.clone()]),
+        }
+    }
+
+    let mut sections = Vec::with_capacity(groups.len());
+    for group in &groups {
+        let summary = llm::call_llm_simple(
+            endpoint,
+            model,
+            "You condense one round of a committee debate for the moderator who will synthesize it. For each speaker, keep their position, strongest argument, key evidence or numbers, and anyone they directly rebutted. Write one short paragraph per speaker, prefixed with the speaker's name and a colon. No commentary of your own.",
+            &format_transcript(group, all_agents),
+        )
+        .await?;
+        sections.push(format!(
+            "{} (summarized)\n\n{}",
+            round_header(group[0].round_number, group[0].exchange_number),
+            summary.trim()
+        ));
+    }
+    Ok(sections.join("\n\n"))
+}
+
 /// Format the debate transcript so far for injection into prompts.
 fn format_transcript(rounds: &[crate::db::DebateRound], all_agents: &[AgentInfo]) -> String {
     let mut sections: Vec<String> = Vec::new();
@@ -454,14 +795,7 @@ fn format_transcript(rounds: &[crate::db::DebateRound], all_agents: &[AgentInfo]
         if r.round_number != current_round || r.exchange_number != current_exchange {
             current_round = r.round_number;
             current_exchange = r.exchange_number;
-            let header = match current_round {
-                1 => "Round 1 (opening)".to_string(),
-                2 => format!("Round 2 (exchange {})", current_exchange),
-                3 => "Round 3 (final statements)".to_string(),
-                99 => "Moderator synthesis".to_string(),
-                _ => format!("Round {}", current_round),
-            };
-            sections.push(header);
+            sections.push(round_header(current_round, current_exchange));
         }
 
         let label = all_agents.iter()
@@ -474,33 +808,231 @@ fn format_transcript(rounds: &[crate::db::DebateRound], all_agents: &[AgentInfo]
     sections.join("\n\n")
 }
 
-/// Call a single agent with retry logic, streaming tokens to frontend.
-async fn call_agent_with_retry(
-    api_key: &str,
-    model: &str,
-    agent_key: &str,
-    agent_label: &str,
-    system_prompt: &str,
-    user_prompt: &str,
-    max_retries: u32,
-    app_handle: &tauri::AppHandle,
+/// Where a decision's debate stands, for a UI that reloads mid-debate.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DebateStatus {
+    pub running: bool,
+    /// While running, the latest phase reached: "starting", "opening", "exchange",
+    /// "final", or "moderator". Otherwise "not_started", "complete", or "interrupted"
+    /// (started but stopped before the moderator synthesis).
+    pub phase: String,
+    /// Highest debate round (1-3) with at least one saved turn. That round may
+    /// still be in progress while the debate is running.
+    pub latest_round: i32,
+    pub has_moderator: bool,
+    pub has_audio: bool,
+}
+
+/// Derive a `DebateStatus` from the decision row and its saved rounds. `running`
+/// comes from the caller, which knows whether a cancel flag is registered.
+pub fn debate_status(
+    decision: &Decision,
+    rounds: &[crate::db::DebateRound],
+    running: bool,
+    has_audio: bool,
+) -> DebateStatus {
+    let has_moderator = rounds.iter().any(|r| r.round_number == 99);
+    let latest_round = rounds
+        .iter()
+        .map(|r| r.round_number)
+        .filter(|n| (1..=3).contains(n))
+        .max()
+        .unwrap_or(0);
+    let phase = if running {
+        match latest_round {
+            _ if has_moderator => "moderator",
+            0 => "starting",
+            1 => "opening",
+            2 => "exchange",
+            _ => "final",
+        }
+    } else if has_moderator {
+        "complete"
+    } else if rounds.is_empty() && decision.debate_started_at.is_none() {
+        "not_started"
+    } else {
+        "interrupted"
+    };
+    DebateStatus {
+        running,
+        phase: phase.to_string(),
+        latest_round,
+        has_moderator,
+        has_audio,
+    }
+}
+
+/// Speaking pace assumed for replay segments that have no recorded audio.
+const REPLAY_WORDS_PER_SECOND: f64 = 2.5;
+
+/// One step of a debate replay: wait `delay_ms` after the previous step, then emit.
+#[derive(Debug, Clone)]
+struct ReplayEvent {
+    delay_ms: u64,
+    name: &'static str,
+    payload: Value,
+}
+
+/// Schedule the events that re-play a saved debate the way a live one unfolds:
+/// one `debate-agent-response` per round, each held for its audio duration
+/// (or an estimate from the word count) divided by `speed`, with
+/// `debate-round-complete` after the last speaker of each exchange.
+fn replay_events(
     decision_id: &str,
-    round_number: i32,
-    exchange_number: i32,
-) -> Result<String, String> {
+    rounds: &[crate::db::DebateRound],
+    manifest: Option<&tts::AudioManifest>,
+    speed: f32,
+) -> Vec<ReplayEvent> {
+    let mut used = vec![false; manifest.map_or(0, |m| m.segments.len())];
+    let mut duration_ms = |round: &crate::db::DebateRound| -> u64 {
+        let recorded = manifest.and_then(|m| {
+            let pos = m.segments.iter().enumerate().position(|(i, s)| {
+                !used[i] && s.agent == round.agent && s.round == round.round_number && s.exchange == round.exchange_number
+            })?;
+            used[pos] = true;
+            Some(m.segments[pos].duration_ms)
+        });
+        let ms = recorded.unwrap_or_else(|| {
+            (profile::word_count(&round.content) as f64 / REPLAY_WORDS_PER_SECOND * 1000.0) as u64
+        });
+        (ms as f64 / speed as f64) as u64
+    };
+    let round_complete = |round_number: i32, exchange_number: i32| {
+        json!({
+            "decision_id": decision_id,
+            "round_number": round_number,
+            "exchange_number": exchange_number,
+        })
+    };
+
+    let mut events = Vec::new();
+    let mut pending_ms = 0;
+    let mut current: Option<(i32, i32)> = None;
+    for round in rounds {
+        let step = (round.round_number, round.exchange_number);
+        if let Some((r, e)) = current.filter(|c| *c != step) {
+            events.push(ReplayEvent { delay_ms: pending_ms, name: "debate-round-complete", payload: round_complete(r, e) });
+            pending_ms = 0;
+        }
+        current = Some(step);
+        events.push(ReplayEvent {
+            delay_ms: pending_ms,
+            name: "debate-agent-response",
+            payload: json!({
+                "decision_id": decision_id,
+                "round_number": round.round_number,
+                "exchange_number": round.exchange_number,
+                "agent": round.agent,
+                "content": round.content,
+            }),
+        });
+        pending_ms = duration_ms(round);
+    }
+    if let Some((r, e)) = current {
+        events.push(ReplayEvent { delay_ms: pending_ms, name: "debate-round-complete", payload: round_complete(r, e) });
+    }
+    events
+}
+
+/// Re-emit a saved debate through `emit`, pacing events in real time.
+/// Returns the number of events emitted.
+pub async fn replay_debate<E: FnMut(&str, Value)>(
+    decision_id: &str,
+    rounds: &[crate::db::DebateRound],
+    manifest: Option<&tts::AudioManifest>,
+    speed: f32,
+    mut emit: E,
+) -> Result<usize, String> {
+    if !speed.is_finite() || speed <= 0.0 {
+        return Err("Replay speed must be greater than zero".to_string());
+    }
+    let events = replay_events(decision_id, rounds, manifest, speed);
+    for event in &events {
+        if event.delay_ms > 0 {
+            tokio::time::sleep(std::time::Duration::from_millis(event.delay_ms)).await;
+        }
+        emit(event.name, event.payload.clone());
+    }
+    Ok(events.len())
+}
+
+/// Structured transcript for integrations: decision metadata, brief, ordered rounds
+/// with resolved agent labels, the parsed debate summary, and the audio manifest.
+pub fn export_debate_json(db: &Database, all_agents: &[AgentInfo], decision_id: &str) -> Result<Value, String> {
+    let decision = db
+        .get_decision(decision_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Decision not found".to_string())?;
+    let rounds = db.get_debate_rounds(decision_id).map_err(|e| e.to_string())?;
+    let audio = db.get_debate_audio(decision_id).map_err(|e| e.to_string())?;
+
+    let rounds: Vec<Value> = rounds
+        .iter()
+        .map(|r| {
+            let label = all_agents
+                .iter()
+                .find(|a| a.key == r.agent)
+                .map(|a| a.label.clone())
+                .unwrap_or_else(|| r.agent.clone());
+            json!({
+                "round_number": r.round_number,
+                "exchange_number": r.exchange_number,
+                "agent": r.agent,
+                "agent_label": label,
+                "content": r.content,
+                "created_at": r.created_at,
+            })
+        })
+        .collect();
+    let debate_summary = decision
+        .summary_json
+        .as_deref()
+        .and_then(|s| serde_json::from_str::<Value>(s).ok())
+        .and_then(|v| v.get("debate_summary").cloned())
+        .unwrap_or(Value::Null);
+    let audio_manifest = audio
+        .and_then(|a| serde_json::from_str::<Value>(&a.manifest_json).ok())
+        .unwrap_or(Value::Null);
+
+    Ok(json!({
+        "decision": {
+            "id": decision.id,
+            "conversation_id": decision.conversation_id,
+            "title": decision.title,
+            "status": decision.status,
+            "user_choice": decision.user_choice,
+            "debate_started_at": decision.debate_started_at,
+            "debate_completed_at": decision.debate_completed_at,
+            "created_at": decision.created_at,
+            "updated_at": decision.updated_at,
+        },
+        "brief": decision.debate_brief,
+        "rounds": rounds,
+        "debate_summary": debate_summary,
+        "audio_manifest": audio_manifest,
+    }))
+}
+
+/// Error unless the reply still has at least `min_chars` characters once cleaned up
+/// for speech, so empty or one-word replies go down the retry path.
+fn ensure_substantive(text: &str, min_chars: usize) -> Result<(), String> {
+    let length = normalize_spoken_debate_output(text, None).trim().chars().count();
+    if length < min_chars {
+        return Err(format!("response too short ({} characters, need {})", length, min_chars));
+    }
+    Ok(())
+}
+
+/// Run `call` up to `max_retries + 1` times until it returns a substantive reply.
+/// Returns the last error (a call error or a too-short reply) if none does.
+async fn retry_until_substantive<F, Fut>(max_retries: u32, min_chars: usize, mut call: F) -> Result<String, String>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<String, String>>,
+{
     let mut last_err = String::new();
     for attempt in 0..=max_retries {
-        match llm::call_llm_streaming_debate(
-            api_key,
-            model,
-            system_prompt,
-            user_prompt,
-            app_handle,
-            decision_id,
-            round_number,
-            exchange_number,
-            agent_key,
-        ).await {
+        match call().await.and_then(|text| ensure_substantive(&text, min_chars).map(|_| text)) {
             Ok(text) => return Ok(text),
             Err(e) => {
                 last_err = e;
@@ -510,219 +1042,919 @@ async fn call_agent_with_retry(
             }
         }
     }
-    Err(format!("{} failed after {} retries: {}", agent_label, max_retries + 1, last_err))
+    Err(last_err)
 }
 
-/// Run a full debate round where debaters respond one at a time (sequential streaming).
-async fn run_sequential_round(
-    api_key: &str,
-    default_model: &str,
-    agent_models: &HashMap<String, String>,
-    brief: &str,
-    existing_rounds: &[crate::db::DebateRound],
-    round_number: i32,
-    exchange_number: i32,
-    app_handle: &tauri::AppHandle,
-    decision_id: &str,
-    cancel_flag: &Arc<AtomicBool>,
-    app_data_dir: &std::path::PathBuf,
-    debaters: &[AgentInfo],
-    all_agents: &[AgentInfo],
-    tts_state: &LiveTtsState,
-    standalone_sandbox: bool,
-    round_direction: Option<&str>,
-) -> Result<Vec<crate::db::DebateRound>, String> {
-    if cancel_flag.load(Ordering::Relaxed) {
-        return Err("Debate cancelled".to_string());
+/// Wall-clock time spent on one agent call, retries included.
+#[derive(Debug, Clone)]
+struct AgentTiming {
+    agent: String,
+    round: i32,
+    exchange: i32,
+    ms: u64,
+    attempts: u32,
+}
+
+/// Timings collected over one `run_debate` invocation. Cheap to clone so
+/// concurrently spawned agent calls can share it.
+#[derive(Debug, Clone, Default)]
+struct AgentTimings(Arc<Mutex<Vec<AgentTiming>>>);
+
+impl AgentTimings {
+    fn record(&self, timing: AgentTiming) {
+        if let Ok(mut timings) = self.0.lock() {
+            timings.push(timing);
+        }
     }
 
-    let mut new_rounds = Vec::new();
-    let mut speaker_order: Vec<&AgentInfo> = debaters.iter().collect();
-    if speaker_order.len() > 1 {
-        let rotation_seed = (round_number.max(1) - 1 + exchange_number.max(1) - 1) as usize;
-        let speaker_count = speaker_order.len();
-        speaker_order.rotate_left(rotation_seed % speaker_count);
+    fn snapshot(&self) -> Vec<AgentTiming> {
+        self.0.lock().map(|t| t.clone()).unwrap_or_default()
     }
+}
 
-    for agent in speaker_order {
-        if cancel_flag.load(Ordering::Relaxed) {
-            return Err("Debate cancelled".to_string());
-        }
+/// Await `fut`, returning its output along with the elapsed milliseconds.
+async fn timed<Fut: std::future::Future>(fut: Fut) -> (Fut::Output, u64) {
+    let started = std::time::Instant::now();
+    let output = fut.await;
+    (output, started.elapsed().as_millis() as u64)
+}
 
-        // Build transcript dynamically so later speakers can react to points
-        // made earlier in the same exchange.
-        let mut transcript_rounds = existing_rounds.to_vec();
-        transcript_rounds.extend(new_rounds.iter().cloned());
-        let transcript = format_transcript(&transcript_rounds, all_agents);
-        let mut user_prompt = match round_number {
-            1 => agents::round1_prompt(brief),
-            2 => agents::round2_prompt(brief, &transcript, exchange_number),
-            3 => agents::round3_prompt(brief, &transcript),
-            _ => return Err("Invalid round number".to_string()),
-        };
-        if round_number == 2 {
-            if let Some(last_round) = new_rounds.last() {
-                let prior_speaker = all_agents
-                    .iter()
-                    .find(|a| a.key == last_round.agent)
-                    .map(|a| a.label.as_str())
-                    .unwrap_or(last_round.agent.as_str());
-                user_prompt.push_str(&format!(
-                    "\n\nYou are speaking immediately after {}. In your first sentence, react directly to their main point.",
-                    prior_speaker
-                ));
-            }
-            if let Some(direction) = round_direction {
-                let direction = direction.trim();
-                if !direction.is_empty() {
-                    user_prompt.push_str(&format!(
-                        "\n\nModerator guidance for this exchange (follow naturally, do not quote it verbatim, and do not mention the moderator): {}",
-                        direction
-                    ));
-                }
-            }
-        }
-        if round_number == 1 {
-            user_prompt.push_str(&format!(
-                "\n\nRound 1 constraints:\n- You are speaking as \"{}\".\n- This is a blind opening; no other opening statements are available to you.\n- Do not reference, quote, or align with any other speaker yet.\n- State your independent initial position in first person.",
-                agent.label
-            ));
-        } else {
-            let other_speaker_labels = debaters
-                .iter()
-                .filter(|d| d.key != agent.key)
-                .map(|d| d.label.as_str())
-                .collect::<Vec<_>>()
-                .join(", ");
-            let other_speaker_labels = if other_speaker_labels.is_empty() {
-                "none".to_string()
-            } else {
-                other_speaker_labels
-            };
-            user_prompt.push_str(&format!(
-                "\n\nIdentity constraints:\n- You are speaking as \"{}\".\n- Never address yourself by name.\n- Never refer to yourself using your model id.\n- If referencing your own earlier point, use first person (\"I\", \"my view\") instead of your name.\n- Only mention speakers from this list: {}.\n- Do not invent or mention speakers not in that list.",
-                agent.label, other_speaker_labels
-            ));
+/// Per-agent latency aggregate for `debate_summary.agent_latency`.
+fn summarize_agent_latency(timings: &[AgentTiming]) -> Value {
+    let mut by_agent: Vec<(&str, Vec<&AgentTiming>)> = Vec::new();
+    for timing in timings {
+        match by_agent.iter_mut().find(|(agent, _)| *agent == timing.agent) {
+            Some((_, entries)) => entries.push(timing),
+            None => by_agent.push((&timing.agent, vec![timing])),
         }
+    }
+    let mut summary = serde_json::Map::new();
+    for (agent, entries) in by_agent {
+        let total_ms: u64 = entries.iter().map(|t| t.ms).sum();
+        summary.insert(agent.to_string(), json!({
+            "calls": entries.len(),
+            "attempts": entries.iter().map(|t| t.attempts).sum::<u32>(),
+            "total_ms": total_ms,
+            "avg_ms": total_ms / entries.len() as u64,
+            "max_ms": entries.iter().map(|t| t.ms).max().unwrap_or(0),
+        }));
+    }
+    Value::Object(summary)
+}
 
-        let base_system_prompt = if standalone_sandbox {
-            standalone_debater_system_prompt(&agent.label)
-        } else {
-            agents::read_agent_prompt(app_data_dir, &agent.key)
-        };
-        let system_prompt = format!(
-            "{}\n\n{}",
-            base_system_prompt,
-            agents::debate_spoken_style_overlay()
-        );
-        let agent_model = agent_models.get(&agent.key).filter(|m| !m.is_empty()).map(|m| m.as_str()).unwrap_or(default_model);
-        let result = call_agent_with_retry(
-            api_key, agent_model,
-            &agent.key, &agent.label, &system_prompt, &user_prompt, 2,
-            app_handle, decision_id, round_number, exchange_number,
-        ).await;
+/// One agent's turn: who speaks, where in the debate, and with which prompts.
+struct AgentTurn<'a> {
+    agent_key: &'a str,
+    agent_label: &'a str,
+    round_number: i32,
+    exchange_number: i32,
+    system_prompt: &'a str,
+    user_prompt: &'a str,
+}
 
-        match result {
-            Ok(text) => {
-                let normalized_text = normalize_spoken_debate_output(&text);
-                // Save to DB
-                let state: tauri::State<'_, Mutex<AppState>> = app_handle.state();
-                let round = {
-                    let state_guard = state.lock().map_err(|e| e.to_string())?;
-                    state_guard.db.save_debate_round(
-                        decision_id,
-                        round_number,
-                        exchange_number,
-                        &agent.key,
-                        &normalized_text,
-                    ).map_err(|e| e.to_string())?
-                };
-
-                // Emit per-agent complete event
-                let _ = app_handle.emit("debate-agent-response", json!({
-                    "decision_id": decision_id,
-                    "round_number": round_number,
-                    "exchange_number": exchange_number,
-                    "agent": agent.key,
-                    "content": normalized_text,
-                }));
+/// The parts of a running debate that every round needs. Fixed for the whole run.
+struct DebateRun<'a> {
+    app_handle: &'a tauri::AppHandle,
+    decision_id: &'a str,
+    cancel_flag: &'a Arc<AtomicBool>,
+    app_data_dir: &'a PathBuf,
+    settings: &'a DebateSettings,
+    brief: &'a str,
+    debaters: &'a [AgentInfo],
+    all_agents: &'a [AgentInfo],
+    tts_state: &'a LiveTtsState,
+    standalone_sandbox: bool,
+    timings: &'a AgentTimings,
+}
 
-                // Spawn live TTS for this segment
-                spawn_segment_tts(tts_state, app_handle, decision_id, &round);
+impl DebateRun<'_> {
+    fn cancelled(&self) -> bool {
+        self.cancel_flag.load(Ordering::Relaxed)
+    }
 
-                new_rounds.push(round);
-            }
-            Err(e) => {
-                eprintln!("Agent call failed: {}", e);
-                let _ = app_handle.emit("debate-agent-response", json!({
-                    "decision_id": decision_id,
-                    "round_number": round_number,
-                    "exchange_number": exchange_number,
-                    "agent": "error",
-                    "content": format!("An agent was unable to participate: {}", e),
-                }));
-            }
-        }
+    async fn call_agent(&self, turn: &AgentTurn<'_>, max_retries: u32) -> Result<String, String> {
+        call_agent_with_retry(self.settings, self.app_handle, self.decision_id, turn, max_retries, self.timings).await
     }
+}
 
-    // Emit round-complete
-    let _ = app_handle.emit("debate-round-complete", json!({
+/// Call a single agent with retry logic, streaming tokens to frontend.
+/// Emits `debate-agent-timing` once the call settles, success or not.
+async fn call_agent_with_retry(
+    settings: &DebateSettings,
+    app_handle: &tauri::AppHandle,
+    decision_id: &str,
+    turn: &AgentTurn<'_>,
+    max_retries: u32,
+    timings: &AgentTimings,
+) -> Result<String, String> {
+    let request_body = llm::debate_request_body(
+        settings.model_for(turn.agent_key),
+        turn.system_prompt,
+        turn.user_prompt,
+        settings.max_tokens,
+        settings.temperature,
+    );
+    let stream_turn = llm::DebateTurnRef {
+        decision_id,
+        agent_key: turn.agent_key,
+        round_number: turn.round_number,
+        exchange_number: turn.exchange_number,
+    };
+    let mut attempts = 0u32;
+    let (result, ms) = timed(retry_until_substantive(max_retries, settings.min_response_chars, || {
+        attempts += 1;
+        llm::call_llm_streaming_debate(&settings.endpoint, &request_body, app_handle, stream_turn, settings.token_flush_interval)
+    }))
+    .await;
+
+    let timing = AgentTiming {
+        agent: turn.agent_key.to_string(),
+        round: turn.round_number,
+        exchange: turn.exchange_number,
+        ms,
+        attempts,
+    };
+    let _ = app_handle.emit("debate-agent-timing", json!({
         "decision_id": decision_id,
-        "round_number": round_number,
-        "exchange_number": exchange_number,
+        "agent": timing.agent,
+        "round": timing.round,
+        "exchange": timing.exchange,
+        "ms": timing.ms,
+        "attempts": timing.attempts,
     }));
+    timings.record(timing);
 
-    Ok(new_rounds)
+    result.map_err(|e| format!("{} failed after {} retries: {}", turn.agent_label, max_retries + 1, e))
 }
 
-/// Main debate orchestrator. Runs the full debate asynchronously.
-pub async fn run_debate(
-    app_handle: tauri::AppHandle,
-    decision_id: String,
-    quick_mode: bool,
-    cancel_flag: Arc<AtomicBool>,
-    selected_agent_keys: Option<Vec<String>>,
-    brief_override: Option<String>,
-    standalone_participants: Option<Vec<AgentInfo>>,
-    standalone_model_map: Option<HashMap<String, String>>,
-    standalone_config: Option<StandaloneDebateConfig>,
-) -> Result<(), String> {
-    // 1. Compile brief (or use override for standalone debates)
-    let brief = match brief_override {
-        Some(b) => b,
-        None => compile_brief(&app_handle, &decision_id)?,
-    };
+/// Round 1 prompt for one debater: the brief plus the blind-opening constraints.
+fn opening_user_prompt(brief: &str, agent_label: &str) -> String {
+    let mut user_prompt = agents::round1_prompt(brief);
+    user_prompt.push_str(&format!(
+        "\n\nRound 1 constraints:\n- You are speaking as \"{}\".\n- This is a blind opening; no other opening statements are available to you.\n- Do not reference, quote, or align with any other speaker yet.\n- State your independent initial position in first person.",
+        agent_label
+    ));
+    user_prompt
+}
 
-    // 2. Save brief and update status
-    {
-        let state: tauri::State<'_, Mutex<AppState>> = app_handle.state();
+/// A debater's system prompt with the spoken-style overlay applied, unless
+/// `apply_spoken_overlay` (read once per debate into `DebateSettings`) is off.
+fn debater_system_prompt(
+    app_data_dir: &std::path::PathBuf,
+    agent: &AgentInfo,
+    standalone_sandbox: bool,
+    apply_spoken_overlay: bool,
+) -> String {
+    let base_system_prompt = if standalone_sandbox {
+        standalone_debater_system_prompt(&agent.label)
+    } else {
+        agents::read_agent_prompt(app_data_dir, &agent.key)
+    };
+    if !apply_spoken_overlay {
+        return base_system_prompt;
+    }
+    format!(
+        "{}\n\n{}",
+        base_system_prompt,
+        agents::debate_spoken_style_overlay()
+    )
+}
+
+/// Send a saved round to the UI and queue its live audio.
+fn announce_round(
+    app_handle: &tauri::AppHandle,
+    tts_state: &LiveTtsState,
+    decision_id: &str,
+    round: &crate::db::DebateRound,
+) {
+    let _ = app_handle.emit("debate-agent-response", json!({
+        "decision_id": decision_id,
+        "round_number": round.round_number,
+        "exchange_number": round.exchange_number,
+        "agent": round.agent,
+        "content": round.content,
+    }));
+    spawn_segment_tts(tts_state, app_handle, decision_id, round);
+}
+
+/// Tell the UI an agent couldn't take its turn; the round carries on without it.
+fn announce_agent_failure(
+    app_handle: &tauri::AppHandle,
+    decision_id: &str,
+    round_number: i32,
+    exchange_number: i32,
+    error: &str,
+) {
+    eprintln!("Agent call failed: {}", error);
+    let _ = app_handle.emit("debate-agent-response", json!({
+        "decision_id": decision_id,
+        "round_number": round_number,
+        "exchange_number": exchange_number,
+        "agent": "error",
+        "content": format!("An agent was unable to participate: {}", error),
+    }));
+}
+
+/// Extra attempts each failed agent gets in the end-of-round retry pass.
+const ROUND_RETRY_ATTEMPTS: u32 = 2;
+
+/// User prompt for one debater's turn in rounds 1-3. `new_rounds` are the turns
+/// already taken this exchange, so later speakers can react to earlier ones.
+fn round_user_prompt(
+    run: &DebateRun<'_>,
+    existing_rounds: &[crate::db::DebateRound],
+    new_rounds: &[crate::db::DebateRound],
+    round_number: i32,
+    exchange_number: i32,
+    agent: &AgentInfo,
+    round_direction: Option<&str>,
+) -> Result<String, String> {
+    let (brief, debaters, all_agents) = (run.brief, run.debaters, run.all_agents);
+    let mut transcript_rounds = existing_rounds.to_vec();
+    transcript_rounds.extend(new_rounds.iter().cloned());
+    let transcript = format_transcript(&transcript_rounds, all_agents);
+    let mut user_prompt = match round_number {
+        1 => opening_user_prompt(brief, &agent.label),
+        2 => agents::round2_prompt(brief, &transcript, exchange_number, debaters.len() == 1),
+        3 => agents::round3_prompt(brief, &transcript, debaters.len() == 1),
+        _ => return Err("Invalid round number".to_string()),
+    };
+    if round_number == 2 {
+        if let Some(last_round) = new_rounds.last() {
+            let prior_speaker = all_agents
+                .iter()
+                .find(|a| a.key == last_round.agent)
+                .map(|a| a.label.as_str())
+                .unwrap_or(last_round.agent.as_str());
+            user_prompt.push_str(&format!(
+                "\n\nYou are speaking immediately after {}. In your first sentence, react directly to their main point.",
+                prior_speaker
+            ));
+        }
+        if let Some(direction) = round_direction {
+            let direction = direction.trim();
+            if !direction.is_empty() {
+                user_prompt.push_str(&format!(
+                    "\n\nModerator guidance for this exchange (follow naturally, do not quote it verbatim, and do not mention the moderator): {}",
+                    direction
+                ));
+            }
+        }
+    }
+    if round_number != 1 {
+        let other_speaker_labels = debaters
+            .iter()
+            .filter(|d| d.key != agent.key)
+            .map(|d| d.label.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let speaker_rule = if other_speaker_labels.is_empty() {
+            "You are the only speaker. Do not address, invent, or mention any other speakers.".to_string()
+        } else {
+            format!(
+                "Only mention speakers from this list: {}.\n- Do not invent or mention speakers not in that list.",
+                other_speaker_labels
+            )
+        };
+        user_prompt.push_str(&format!(
+            "\n\nIdentity constraints:\n- You are speaking as \"{}\".\n- Never address yourself by name.\n- Never refer to yourself using your model id.\n- If referencing your own earlier point, use first person (\"I\", \"my view\") instead of your name.\n- {}",
+            agent.label, speaker_rule
+        ));
+    }
+    Ok(user_prompt)
+}
+
+/// Normalize and persist one agent's turn.
+fn save_agent_round(
+    app_handle: &tauri::AppHandle,
+    decision_id: &str,
+    round_number: i32,
+    exchange_number: i32,
+    agent: &AgentInfo,
+    text: &str,
+) -> Result<crate::db::DebateRound, String> {
+    let normalized_text = normalize_spoken_debate_output(text, Some(&agent.label));
+    let state: tauri::State<'_, Mutex<AppState>> = app_handle.state();
+    let state_guard = state.lock().map_err(|e| e.to_string())?;
+    state_guard.live_debate_buffers.clear(llm::DebateTurnRef {
+        decision_id,
+        agent_key: &agent.key,
+        round_number,
+        exchange_number,
+    });
+    state_guard.db.save_debate_round(
+        decision_id,
+        round_number,
+        exchange_number,
+        &agent.key,
+        &normalized_text,
+    ).map_err(|e| e.to_string())
+}
+
+/// Give each agent that failed during a round up to `attempts` more tries, in
+/// order. Returns (agent key, text) for those that recovered and (agent key,
+/// last error) for those that didn't.
+async fn retry_failed_agents<F, Fut>(
+    failed: Vec<(String, String)>,
+    attempts: u32,
+    mut call: F,
+) -> (Vec<(String, String)>, Vec<(String, String)>)
+where
+    F: FnMut(String) -> Fut,
+    Fut: std::future::Future<Output = Result<String, String>>,
+{
+    let mut recovered = Vec::new();
+    let mut still_failed = Vec::new();
+    for (key, first_error) in failed {
+        let mut last_error = first_error;
+        let mut text = None;
+        for _ in 0..attempts {
+            match call(key.clone()).await {
+                Ok(t) => {
+                    text = Some(t);
+                    break;
+                }
+                Err(e) => last_error = e,
+            }
+        }
+        match text {
+            Some(t) => recovered.push((key, t)),
+            None => still_failed.push((key, last_error)),
+        }
+    }
+    (recovered, still_failed)
+}
+
+/// End-of-round retry pass shared by every round runner: agents that failed get
+/// `ROUND_RETRY_ATTEMPTS` more tries (prompts from `user_prompt_for`, built once
+/// the rest of the round is in). Recovered turns are saved and announced; the
+/// rest get the placeholder failure message.
+async fn retry_round_failures<P>(
+    run: &DebateRun<'_>,
+    failed: Vec<(&AgentInfo, String)>,
+    round_number: i32,
+    exchange_number: i32,
+    user_prompt_for: P,
+) -> Result<Vec<crate::db::DebateRound>, String>
+where
+    P: Fn(&AgentInfo) -> Result<String, String>,
+{
+    if failed.is_empty() {
+        return Ok(Vec::new());
+    }
+    if run.cancelled() {
+        return Err("Debate cancelled".to_string());
+    }
+    tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+
+    let mut prompts = HashMap::new();
+    for (agent, _) in &failed {
+        prompts.insert(agent.key.clone(), (*agent, user_prompt_for(agent)?));
+    }
+    let failed_keys = failed.iter().map(|(a, e)| (a.key.clone(), e.clone())).collect();
+    let (recovered, still_failed) = retry_failed_agents(failed_keys, ROUND_RETRY_ATTEMPTS, |key| {
+        let (agent, user_prompt) = &prompts[&key];
+        let system_prompt = debater_system_prompt(run.app_data_dir, agent, run.standalone_sandbox, run.settings.apply_spoken_overlay);
+        async move {
+            let turn = AgentTurn {
+                agent_key: &agent.key,
+                agent_label: &agent.label,
+                round_number,
+                exchange_number,
+                system_prompt: &system_prompt,
+                user_prompt,
+            };
+            run.call_agent(&turn, 0).await
+        }
+    }).await;
+
+    let mut rounds = Vec::new();
+    for (key, text) in recovered {
+        let agent = prompts[&key].0;
+        let round = save_agent_round(run.app_handle, run.decision_id, round_number, exchange_number, agent, &text)?;
+        announce_round(run.app_handle, run.tts_state, run.decision_id, &round);
+        rounds.push(round);
+    }
+    for (_, e) in still_failed {
+        announce_agent_failure(run.app_handle, run.decision_id, round_number, exchange_number, &e);
+    }
+    Ok(rounds)
+}
+
+/// Run a full debate round where debaters respond one at a time (sequential streaming).
+async fn run_sequential_round(
+    run: &DebateRun<'_>,
+    existing_rounds: &[crate::db::DebateRound],
+    round_number: i32,
+    exchange_number: i32,
+    round_direction: Option<&str>,
+) -> Result<Vec<crate::db::DebateRound>, String> {
+    if run.cancelled() {
+        return Err("Debate cancelled".to_string());
+    }
+
+    let mut new_rounds = Vec::new();
+    // On resume, speakers who already have a turn saved for this step are skipped
+    let speaker_order: Vec<&AgentInfo> = speaking_order(run.debaters, round_number, exchange_number, run.settings.shuffle_seed)
+        .into_iter()
+        .filter(|agent| !has_spoken(existing_rounds, &agent.key, (round_number, exchange_number)))
+        .collect();
+
+    let mut failed: Vec<(&AgentInfo, String)> = Vec::new();
+    for agent in speaker_order {
+        if run.cancelled() {
+            return Err("Debate cancelled".to_string());
+        }
+
+        // Build transcript dynamically so later speakers can react to points
+        // made earlier in the same exchange.
+        let user_prompt = round_user_prompt(
+            run, existing_rounds, &new_rounds, round_number, exchange_number, agent, round_direction,
+        )?;
+        let system_prompt = debater_system_prompt(run.app_data_dir, agent, run.standalone_sandbox, run.settings.apply_spoken_overlay);
+        let turn = AgentTurn {
+            agent_key: &agent.key,
+            agent_label: &agent.label,
+            round_number,
+            exchange_number,
+            system_prompt: &system_prompt,
+            user_prompt: &user_prompt,
+        };
+        match run.call_agent(&turn, 2).await {
+            Ok(text) => {
+                let round = save_agent_round(run.app_handle, run.decision_id, round_number, exchange_number, agent, &text)?;
+                // Emit per-agent complete event and spawn live TTS for this segment
+                announce_round(run.app_handle, run.tts_state, run.decision_id, &round);
+                new_rounds.push(round);
+            }
+            Err(e) => failed.push((agent, e)),
+        }
+    }
+
+    // Second pass: give agents that failed another go now that the rest of the
+    // round is in, before settling for the placeholder message.
+    let recovered = retry_round_failures(run, failed, round_number, exchange_number, |agent| {
+        round_user_prompt(run, existing_rounds, &new_rounds, round_number, exchange_number, agent, round_direction)
+    }).await?;
+    new_rounds.extend(recovered);
+
+    // Emit round-complete
+    let _ = run.app_handle.emit("debate-round-complete", json!({
+        "decision_id": run.decision_id,
+        "round_number": round_number,
+        "exchange_number": exchange_number,
+    }));
+
+    Ok(new_rounds)
+}
+
+/// An agent that failed to deliver its opening: (agent key, error).
+type OpeningFailure = (String, String);
+
+/// Persist concurrently gathered opening statements in registry order, so the
+/// transcript reads the same as a sequential round regardless of which agent
+/// finished first. Failed agents are returned separately.
+fn save_opening_rounds(
+    db: &Database,
+    decision_id: &str,
+    debaters: &[AgentInfo],
+    mut outcomes: Vec<(String, Result<String, String>)>,
+) -> Result<(Vec<crate::db::DebateRound>, Vec<OpeningFailure>), String> {
+    let mut rounds = Vec::new();
+    let mut failures = Vec::new();
+    for agent in debaters {
+        let Some(pos) = outcomes.iter().position(|(key, _)| *key == agent.key) else {
+            continue;
+        };
+        match outcomes.swap_remove(pos).1 {
+            Ok(text) => {
+                let normalized_text = normalize_spoken_debate_output(&text, Some(&agent.label));
+                let round = db
+                    .save_debate_round(decision_id, 1, 1, &agent.key, &normalized_text)
+                    .map_err(|e| e.to_string())?;
+                rounds.push(round);
+            }
+            Err(e) => failures.push((agent.key.clone(), e)),
+        }
+    }
+    Ok((rounds, failures))
+}
+
+/// Run Round 1 with every debater speaking at once. Openings are blind, so no
+/// agent needs another's output; results are saved in registry order afterwards.
+async fn run_parallel_opening(
+    run: &DebateRun<'_>,
+    existing_rounds: &[crate::db::DebateRound],
+) -> Result<Vec<crate::db::DebateRound>, String> {
+    if run.cancelled() {
+        return Err("Debate cancelled".to_string());
+    }
+
+    let speakers: Vec<AgentInfo> = run.debaters
+        .iter()
+        .filter(|agent| !has_spoken(existing_rounds, &agent.key, (1, 1)))
+        .cloned()
+        .collect();
+    let mut tasks = tokio::task::JoinSet::new();
+    for agent in &speakers {
+        let settings = run.settings.clone();
+        let app_handle = run.app_handle.clone();
+        let decision_id = run.decision_id.to_string();
+        let system_prompt = debater_system_prompt(run.app_data_dir, agent, run.standalone_sandbox, run.settings.apply_spoken_overlay);
+        let user_prompt = opening_user_prompt(run.brief, &agent.label);
+        let agent = agent.clone();
+        let timings = run.timings.clone();
+        tasks.spawn(async move {
+            let turn = AgentTurn {
+                agent_key: &agent.key,
+                agent_label: &agent.label,
+                round_number: 1,
+                exchange_number: 1,
+                system_prompt: &system_prompt,
+                user_prompt: &user_prompt,
+            };
+            let result = call_agent_with_retry(&settings, &app_handle, &decision_id, &turn, 2, &timings).await;
+            (agent.key, result)
+        });
+    }
+
+    let mut outcomes = Vec::with_capacity(speakers.len());
+    while let Some(joined) = tasks.join_next().await {
+        outcomes.push(joined.map_err(|e| e.to_string())?);
+    }
+
+    if run.cancelled() {
+        return Err("Debate cancelled".to_string());
+    }
+
+    let (mut rounds, failures) = {
+        let state: tauri::State<'_, Mutex<AppState>> = run.app_handle.state();
         let state_guard = state.lock().map_err(|e| e.to_string())?;
-        state_guard.db.delete_debate_rounds(&decision_id).map_err(|e| e.to_string())?;
-        state_guard.db.update_debate_brief(&decision_id, &brief).map_err(|e| e.to_string())?;
-        state_guard.db.update_debate_started(&decision_id).map_err(|e| e.to_string())?;
+        let saved = save_opening_rounds(&state_guard.db, run.decision_id, &speakers, outcomes)?;
+        for round in &saved.0 {
+            state_guard.live_debate_buffers.clear(llm::DebateTurnRef {
+                decision_id: run.decision_id,
+                agent_key: &round.agent,
+                round_number: 1,
+                exchange_number: 1,
+            });
+        }
+        saved
+    };
+    for round in &rounds {
+        announce_round(run.app_handle, run.tts_state, run.decision_id, round);
     }
+    let failed = failures
+        .into_iter()
+        .filter_map(|(key, e)| speakers.iter().find(|a| a.key == key).map(|agent| (agent, e)))
+        .collect();
+    let recovered = retry_round_failures(run, failed, 1, 1, |agent| {
+        Ok(opening_user_prompt(run.brief, &agent.label))
+    }).await?;
+    rounds.extend(recovered);
+
+    let _ = run.app_handle.emit("debate-round-complete", json!({
+        "decision_id": run.decision_id,
+        "round_number": 1,
+        "exchange_number": 1,
+    }));
 
-    // 3. Emit debate-started
-    let _ = app_handle.emit("debate-started", json!({ "decision_id": decision_id }));
+    Ok(rounds)
+}
+
+/// Upper bound on Round 2 exchanges for committee debates.
+pub const MAX_ROUND2_EXCHANGES: u32 = 6;
 
-    let standalone_sandbox = standalone_participants.is_some();
+/// How many rounds a committee debate runs. The opening round and the
+/// moderator synthesis always happen; Round 2 exchanges and the final
+/// positions round are configurable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DebateShape {
+    pub round2_exchanges: u32,
+    pub include_round3: bool,
+}
+
+impl DebateShape {
+    /// Opening, two Round 2 exchanges, final positions.
+    pub const FULL: DebateShape = DebateShape { round2_exchanges: 2, include_round3: true };
+    /// Opening statements only.
+    pub const QUICK: DebateShape = DebateShape { round2_exchanges: 0, include_round3: false };
+
+    /// Preset used by callers that still pass the old `quick_mode` flag.
+    pub fn from_quick_mode(quick_mode: bool) -> Self {
+        if quick_mode { Self::QUICK } else { Self::FULL }
+    }
+
+    fn clamped(self) -> Self {
+        DebateShape {
+            round2_exchanges: self.round2_exchanges.min(MAX_ROUND2_EXCHANGES),
+            ..self
+        }
+    }
+}
+
+impl Default for DebateShape {
+    fn default() -> Self {
+        Self::FULL
+    }
+}
+
+/// The debaters taking part: the selected keys in registry order, or every
+/// debater when nothing was selected.
+pub fn select_debaters(debaters: Vec<AgentInfo>, selected_keys: Option<&[String]>) -> Vec<AgentInfo> {
+    match selected_keys {
+        Some(keys) if !keys.is_empty() => debaters
+            .into_iter()
+            .filter(|a| keys.contains(&a.key))
+            .collect(),
+        _ => debaters,
+    }
+}
+
+/// Refuse to start with fewer than `min_debaters` participants (never fewer than one).
+pub fn check_debater_count(count: usize, min_debaters: usize) -> Result<(), String> {
+    if count == 0 {
+        return Err("No debaters selected for the debate".to_string());
+    }
+    if count < min_debaters {
+        return Err(format!(
+            "A debate needs at least {} debaters, but only {} {} selected",
+            min_debaters,
+            count,
+            if count == 1 { "was" } else { "were" }
+        ));
+    }
+    Ok(())
+}
+
+/// The committee schedule as (round, exchange) steps, excluding the moderator synthesis.
+fn committee_steps(shape: DebateShape) -> Vec<(i32, i32)> {
+    let shape = shape.clamped();
+    let mut steps = vec![(1, 1)];
+    steps.extend((1..=shape.round2_exchanges as i32).map(|exchange| (2, exchange)));
+    if shape.include_round3 {
+        steps.push((3, 1));
+    }
+    steps
+}
+
+/// Whether `agent_key` already has a saved turn for the (round, exchange) `step`.
+fn has_spoken(rounds: &[crate::db::DebateRound], agent_key: &str, step: (i32, i32)) -> bool {
+    rounds
+        .iter()
+        .any(|r| r.agent == agent_key && (r.round_number, r.exchange_number) == step)
+}
+
+/// Committee steps that still need to run given the rounds already saved.
+/// Steps before the latest saved one are done. The latest step is run again
+/// when someone in `roster` has not spoken in it yet; the round runners then
+/// only call the missing speakers.
+/// Errors if the moderator synthesis exists, since the debate is then complete.
+pub fn remaining_committee_steps(
+    existing_rounds: &[crate::db::DebateRound],
+    shape: DebateShape,
+    roster: &[String],
+) -> Result<Vec<(i32, i32)>, String> {
+    if existing_rounds.iter().any(|r| r.round_number == 99) {
+        return Err("This debate is already complete; there is nothing to resume.".to_string());
+    }
+    let last_done = existing_rounds
+        .iter()
+        .map(|r| (r.round_number, r.exchange_number))
+        .max();
+    Ok(committee_steps(shape)
+        .into_iter()
+        .filter(|step| match last_done {
+            Some(done) if *step < done => false,
+            Some(done) if *step == done => roster.iter().any(|key| !has_spoken(existing_rounds, key, done)),
+            _ => true,
+        })
+        .collect())
+}
+
+/// The roster and shape a committee debate was started with. Saved on the
+/// decision so a resume runs the same debate instead of trusting the caller.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DebateSetup {
+    pub roster: Vec<String>,
+    pub shape: DebateShape,
+}
+
+pub fn save_debate_setup(db: &Database, decision_id: &str, setup: &DebateSetup) -> Result<(), String> {
+    let json = serde_json::to_string(setup).map_err(|e| e.to_string())?;
+    db.update_debate_setup(decision_id, &json).map_err(|e| e.to_string())
+}
+
+/// The stored setup, or `None` for debates started before it was recorded.
+pub fn load_debate_setup(db: &Database, decision_id: &str) -> Result<Option<DebateSetup>, String> {
+    match db.get_debate_setup(decision_id).map_err(|e| e.to_string())? {
+        Some(json) => serde_json::from_str(&json)
+            .map(Some)
+            .map_err(|e| format!("Saved debate setup is unreadable: {}", e)),
+        None => Ok(None),
+    }
+}
+
+/// Result of a manual transcript correction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoundEdit {
+    pub round: crate::db::DebateRound,
+    /// True when narrated audio already exists and no longer matches the transcript.
+    pub audio_stale: bool,
+}
+
+/// Apply a user's correction to one round, normalized the same way agent output is.
+pub fn edit_round_content(db: &Database, round_id: &str, content: &str) -> Result<RoundEdit, String> {
+    let normalized = normalize_spoken_debate_output(content, None);
+    if normalized.trim().is_empty() {
+        return Err("Round content cannot be empty".to_string());
+    }
+    let mut round = db
+        .get_debate_round(round_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Debate round not found".to_string())?;
+    db.update_debate_round_content(round_id, &normalized)
+        .map_err(|e| e.to_string())?;
+    let audio_stale = db
+        .get_debate_audio(&round.decision_id)
+        .map_err(|e| e.to_string())?
+        .is_some();
+    round.content = normalized;
+    Ok(RoundEdit { round, audio_stale })
+}
+
+/// What `reset_debate` removed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DebateReset {
+    pub rounds_deleted: usize,
+    pub audio_record_deleted: bool,
+    pub audio_dir_deleted: bool,
+}
+
+/// Wipe a decision's debate so it can be re-run from scratch: rounds, the audio
+/// record, the on-disk `debates/<id>` directory, and the status back to `analyzing`.
+pub fn reset_debate(db: &Database, app_data_dir: &Path, decision_id: &str) -> Result<DebateReset, String> {
+    db.get_decision(decision_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Decision not found".to_string())?;
+    let rounds_deleted = db.delete_debate_rounds(decision_id).map_err(|e| e.to_string())?;
+    let audio_record_deleted = db.delete_debate_audio(decision_id).map_err(|e| e.to_string())?;
+    let audio_dir_deleted = tts::remove_debate_audio_dir(app_data_dir, decision_id)?;
+    db.update_decision_status(decision_id, "analyzing").map_err(|e| e.to_string())?;
+    Ok(DebateReset { rounds_deleted, audio_record_deleted, audio_dir_deleted })
+}
+
+/// Refuse to delete the moderator synthesis once it has been folded into the
+/// decision summary, since the summary would then describe a round that no longer exists.
+pub fn ensure_round_deletable(
+    round: &crate::db::DebateRound,
+    summary_json: Option<&str>,
+) -> Result<(), String> {
+    if round.round_number != 99 {
+        return Ok(());
+    }
+    let has_debate_summary = summary_json
+        .and_then(|s| serde_json::from_str::<Value>(s).ok())
+        .is_some_and(|v| v.get("debate_summary").is_some_and(|d| !d.is_null()));
+    if has_debate_summary {
+        return Err(
+            "The moderator synthesis backs this decision's summary; re-run the debate instead of deleting it."
+                .to_string(),
+        );
+    }
+    Ok(())
+}
+
+/// Payload for `debate-brief-ready`, fired once the brief is compiled (or reused).
+fn brief_ready_payload(decision_id: &str, brief: &str) -> Value {
+    json!({
+        "decision_id": decision_id,
+        "brief_length": brief.chars().count(),
+    })
+}
+
+/// A standalone sandbox debate: a free-form topic and ad-hoc participants stand
+/// in for the decision brief and the agent registry.
+#[derive(Debug, Clone)]
+pub struct StandaloneDebate {
+    pub brief: String,
+    pub participants: Vec<AgentInfo>,
+    pub model_map: HashMap<String, String>,
+    pub config: StandaloneDebateConfig,
+}
+
+/// What a caller asks `run_debate` to do.
+#[derive(Debug, Clone, Default)]
+pub struct DebateRequest {
+    pub shape: DebateShape,
+    /// Debater keys taking part; `None` means every debater in the registry.
+    pub selected_agent_keys: Option<Vec<String>>,
+    pub standalone: Option<StandaloneDebate>,
+    pub resume: bool,
+    pub quick_poll: bool,
+}
+
+/// Main debate orchestrator. Runs the full debate asynchronously.
+/// With `resume`, previously saved rounds and the stored brief are kept and
+/// only the remaining committee steps are run.
+///
+/// Events fire in this order before any agent speaks: `debate-preparing`,
+/// `debate-brief-ready`, then `debate-started` once the brief has been saved.
+///
+/// `shape` picks how many Round 2 exchanges run and whether final positions
+/// are collected. With `quick_poll`, only the opening statements run (all
+/// debaters at once), followed directly by the moderator synthesis.
+pub async fn run_debate(
+    app_handle: tauri::AppHandle,
+    decision_id: String,
+    cancel_flag: Arc<AtomicBool>,
+    request: DebateRequest,
+) -> Result<(), String> {
+    let DebateRequest { shape, mut selected_agent_keys, standalone, resume, quick_poll } = request;
+    let mut shape = if quick_poll { DebateShape::QUICK } else { shape.clamped() };
+
+    // When resuming, pick up the saved rounds, the brief they were generated from,
+    // and the roster and shape the debate was started with
+    let (existing_rounds, stored_brief) = if resume {
+        let state: tauri::State<'_, Mutex<AppState>> = app_handle.state();
+        let state_guard = state.lock().map_err(|e| e.to_string())?;
+        let setup = load_debate_setup(&state_guard.db, &decision_id)?.ok_or_else(|| {
+            "This debate was started before resume support and can't be resumed. Start a new debate instead.".to_string()
+        })?;
+        shape = setup.shape.clamped();
+        selected_agent_keys = Some(setup.roster);
+        let rounds = state_guard.db.get_debate_rounds(&decision_id).map_err(|e| e.to_string())?;
+        let brief = state_guard.db.get_decision(&decision_id)
+            .map_err(|e| e.to_string())?
+            .and_then(|d| d.debate_brief);
+        (rounds, brief)
+    } else {
+        (Vec::new(), None)
+    };
 
     // Load LLM config and app_data_dir
-    let (api_key, model, mut agent_models, app_data_dir) = {
+    let (mut settings, app_data_dir) = {
         let state: tauri::State<'_, Mutex<AppState>> = app_handle.state();
         let state_guard = state.lock().map_err(|e| e.to_string())?;
         let config = config::load_config(&state_guard.app_data_dir);
-        (config.openrouter_api_key, config.model, config.agent_models, state_guard.app_data_dir.clone())
+        (
+            DebateSettings::from_config(&config, &decision_id, shape),
+            state_guard.app_data_dir.clone(),
+        )
     };
+    let quick_mode = settings.shape == DebateShape::QUICK;
 
-    if let Some(model_overrides) = standalone_model_map {
-        for (agent_key, model_id) in model_overrides {
-            if !model_id.trim().is_empty() {
-                agent_models.insert(agent_key, model_id);
-            }
+    // Give the UI something to show while the brief is assembled
+    let _ = app_handle.emit("debate-preparing", json!({ "decision_id": decision_id }));
+
+    let pending_steps = remaining_committee_steps(
+        &existing_rounds,
+        settings.shape,
+        selected_agent_keys.as_deref().unwrap_or_default(),
+    )?;
+
+    // 1. Compile brief (or use the topic for standalone debates)
+    let brief = match standalone.as_ref().map(|s| s.brief.clone()).or(stored_brief) {
+        Some(b) => b,
+        None => {
+            let state: tauri::State<'_, Mutex<AppState>> = app_handle.state();
+            let state_guard = state.lock().map_err(|e| e.to_string())?;
+            compile_brief(&state_guard.db, &state_guard.app_data_dir, &decision_id)?
+        }
+    };
+    let _ = app_handle.emit("debate-brief-ready", brief_ready_payload(&decision_id, &brief));
+
+    // 2. Save brief and update status
+    {
+        let state: tauri::State<'_, Mutex<AppState>> = app_handle.state();
+        let state_guard = state.lock().map_err(|e| e.to_string())?;
+        if resume {
+            state_guard.db.update_debate_brief(&decision_id, &brief).map_err(|e| e.to_string())?;
+            state_guard.db.update_decision_status(&decision_id, "debating").map_err(|e| e.to_string())?;
+        } else {
+            state_guard.db.delete_debate_rounds(&decision_id).map_err(|e| e.to_string())?;
+            state_guard.db.update_debate_brief(&decision_id, &brief).map_err(|e| e.to_string())?;
+            state_guard.db.update_debate_started(&decision_id).map_err(|e| e.to_string())?;
         }
     }
 
+    // 3. Emit debate-started
+    let _ = app_handle.emit("debate-started", json!({ "decision_id": decision_id }));
+
+    let standalone_sandbox = standalone.is_some();
+
+    let (standalone_participants, standalone_config) = match standalone {
+        Some(sandbox) => {
+            for (agent_key, model_id) in sandbox.model_map {
+                if !model_id.trim().is_empty() {
+                    settings.agent_models.insert(agent_key, model_id);
+                }
+            }
+            (Some(sandbox.participants), Some(sandbox.config))
+        }
+        None => (None, None),
+    };
+
     // Ensure agent prompt files exist (committee flow only)
     if !standalone_sandbox {
         agents::init_agent_files(&app_data_dir).ok();
@@ -735,54 +1967,64 @@ pub async fn run_debate(
         .cloned()
         .collect();
 
-    let debaters: Vec<AgentInfo> = match selected_agent_keys {
-        Some(ref keys) if !keys.is_empty() => {
-            // Use selected agents in the order they appear in the registry
-            all_debaters_in_registry.iter()
-                .filter(|a| keys.contains(&a.key))
-                .cloned()
-                .collect()
-        }
-        _ => all_debaters_in_registry,
-    };
+    let debaters = select_debaters(all_debaters_in_registry, selected_agent_keys.as_deref());
+    check_debater_count(debaters.len(), 1)?;
 
-    if debaters.is_empty() {
-        return Err("No debaters selected for the debate".to_string());
+    if !resume && !standalone_sandbox {
+        let setup = DebateSetup {
+            roster: debaters.iter().map(|a| a.key.clone()).collect(),
+            shape: settings.shape,
+        };
+        let state: tauri::State<'_, Mutex<AppState>> = app_handle.state();
+        let state_guard = state.lock().map_err(|e| e.to_string())?;
+        save_debate_setup(&state_guard.db, &decision_id, &setup)?;
     }
 
-    // Build participant names for moderator
-    let participant_names = agents::format_participant_names(&debaters);
-
     // All agents for transcript formatting (debaters + moderator)
     let all_agents: Vec<AgentInfo> = registry.clone();
 
     // Set up live TTS state
-    let tts_config = config::load_config(&app_data_dir);
-    let has_tts = match tts_config.tts_provider.as_str() {
-        "openai" => !tts_config.openrouter_api_key.is_empty(),
-        _ => !tts_config.elevenlabs_api_key.is_empty(),
-    };
-    let tts_state = LiveTtsState {
-        enabled: has_tts,
-        config: tts_config,
-        registry: registry.clone(),
-        app_data_dir: app_data_dir.clone(),
-        segment_counter: Arc::new(AtomicUsize::new(0)),
-        handles: Arc::new(Mutex::new(Vec::new())),
+    let tts_state = LiveTtsState::new(
+        config::load_config(&app_data_dir),
+        registry.clone(),
+        app_data_dir.clone(),
+        existing_rounds.len(),
+    );
+    let recovered_segments = if tts_state.enabled && resume {
+        tts::recover_live_segments(&app_data_dir, &decision_id, &existing_rounds)
+    } else {
+        Vec::new()
     };
 
-    let mut all_rounds: Vec<crate::db::DebateRound> = Vec::new();
+    let mut all_rounds: Vec<crate::db::DebateRound> = existing_rounds;
+    let timings = AgentTimings::default();
+    let run = DebateRun {
+        app_handle: &app_handle,
+        decision_id: &decision_id,
+        cancel_flag: &cancel_flag,
+        app_data_dir: &app_data_dir,
+        settings: &settings,
+        brief: &brief,
+        debaters: &debaters,
+        all_agents: &all_agents,
+        tts_state: &tts_state,
+        standalone_sandbox,
+        timings: &timings,
+    };
 
-    // 4. Round 1: Opening Positions
-    let round1 = run_sequential_round(
-        &api_key, &model, &agent_models,
-        &brief, &all_rounds, 1, 1,
-        &app_handle, &decision_id, &cancel_flag, &app_data_dir,
-        &debaters, &all_agents, &tts_state, standalone_sandbox, None,
-    ).await?;
-    all_rounds.extend(round1);
+    // 4. Round 1: Opening Positions. Openings are blind, so they can run concurrently;
+    // later rounds react to earlier turns and stay sequential.
+    if pending_steps.contains(&(1, 1)) {
+        let round1 = if quick_poll || settings.parallel_opening {
+            run_parallel_opening(&run, &all_rounds).await?
+        } else {
+            run_sequential_round(&run, &all_rounds, 1, 1, None).await?
+        };
+        all_rounds.extend(round1);
+    }
 
-    let mut include_final_positions = !quick_mode;
+    let include_final_positions;
+    let moderator_model = settings.model_for("moderator");
 
     if standalone_sandbox {
         let cfg = normalize_standalone_config(standalone_config, quick_mode);
@@ -790,30 +2032,21 @@ pub async fn run_debate(
             include_final_positions = false;
             let max_exchanges = cfg.max_exchanges.unwrap_or(12) as i32;
             let participant_names_for_steering = agents::format_participant_names(&debaters);
-            let moderator_model = agent_models
-                .get("moderator")
-                .filter(|m| !m.is_empty())
-                .map(|m| m.as_str())
-                .unwrap_or(&model);
             let mut direction_for_next_exchange: Option<String> = None;
 
             for exchange in 1..=max_exchanges {
-                if cancel_flag.load(Ordering::Relaxed) {
+                if run.cancelled() {
                     return handle_cancellation(&app_handle, &decision_id);
                 }
 
                 let exchange_rounds = run_sequential_round(
-                    &api_key, &model, &agent_models,
-                    &brief, &all_rounds, 2, exchange,
-                    &app_handle, &decision_id, &cancel_flag, &app_data_dir,
-                    &debaters, &all_agents, &tts_state, standalone_sandbox,
-                    direction_for_next_exchange.as_deref(),
+                    &run, &all_rounds, 2, exchange, direction_for_next_exchange.as_deref(),
                 ).await?;
                 all_rounds.extend(exchange_rounds);
 
                 let transcript = format_transcript(&all_rounds, &all_agents);
                 let (direction, conclude) = request_moderator_direction(
-                    &api_key,
+                    &settings.endpoint,
                     moderator_model,
                     &brief,
                     &transcript,
@@ -837,30 +2070,21 @@ pub async fn run_debate(
             let exchanges = cfg.exchange_count.unwrap_or(2) as i32;
             include_final_positions = exchanges > 0;
             let participant_names_for_steering = agents::format_participant_names(&debaters);
-            let moderator_model = agent_models
-                .get("moderator")
-                .filter(|m| !m.is_empty())
-                .map(|m| m.as_str())
-                .unwrap_or(&model);
             let mut direction_for_next_exchange: Option<String> = None;
 
             for exchange in 1..=exchanges {
-                if cancel_flag.load(Ordering::Relaxed) {
+                if run.cancelled() {
                     return handle_cancellation(&app_handle, &decision_id);
                 }
                 let exchange_rounds = run_sequential_round(
-                    &api_key, &model, &agent_models,
-                    &brief, &all_rounds, 2, exchange,
-                    &app_handle, &decision_id, &cancel_flag, &app_data_dir,
-                    &debaters, &all_agents, &tts_state, standalone_sandbox,
-                    direction_for_next_exchange.as_deref(),
+                    &run, &all_rounds, 2, exchange, direction_for_next_exchange.as_deref(),
                 ).await?;
                 all_rounds.extend(exchange_rounds);
 
                 if exchange < exchanges {
                     let transcript = format_transcript(&all_rounds, &all_agents);
                     let (direction, _) = request_moderator_direction(
-                        &api_key,
+                        &settings.endpoint,
                         moderator_model,
                         &brief,
                         &transcript,
@@ -879,56 +2103,48 @@ pub async fn run_debate(
             }
         }
     } else {
-        if !quick_mode {
-            // 5. Round 2 Exchange 1
-            if cancel_flag.load(Ordering::Relaxed) {
-                return handle_cancellation(&app_handle, &decision_id);
-            }
-            let r2e1 = run_sequential_round(
-                &api_key, &model, &agent_models,
-                &brief, &all_rounds, 2, 1,
-                &app_handle, &decision_id, &cancel_flag, &app_data_dir,
-                &debaters, &all_agents, &tts_state, standalone_sandbox, None,
-            ).await?;
-            all_rounds.extend(r2e1);
-
-            // 6. Round 2 Exchange 2
-            if cancel_flag.load(Ordering::Relaxed) {
+        // 5. Round 2 exchanges
+        let round2_steps: Vec<i32> = pending_steps
+            .iter()
+            .filter(|(round, _)| *round == 2)
+            .map(|(_, exchange)| *exchange)
+            .collect();
+        for exchange in round2_steps {
+            if run.cancelled() {
                 return handle_cancellation(&app_handle, &decision_id);
             }
-            let r2e2 = run_sequential_round(
-                &api_key, &model, &agent_models,
-                &brief, &all_rounds, 2, 2,
-                &app_handle, &decision_id, &cancel_flag, &app_data_dir,
-                &debaters, &all_agents, &tts_state, standalone_sandbox, None,
-            ).await?;
-            all_rounds.extend(r2e2);
+            let exchange_rounds = run_sequential_round(&run, &all_rounds, 2, exchange, None).await?;
+            all_rounds.extend(exchange_rounds);
         }
+        include_final_positions = pending_steps.contains(&(3, 1));
     }
 
     if include_final_positions {
-        if cancel_flag.load(Ordering::Relaxed) {
+        if run.cancelled() {
             return handle_cancellation(&app_handle, &decision_id);
         }
-        let round3 = run_sequential_round(
-            &api_key, &model, &agent_models,
-            &brief, &all_rounds, 3, 1,
-            &app_handle, &decision_id, &cancel_flag, &app_data_dir,
-            &debaters, &all_agents, &tts_state, standalone_sandbox, None,
-        ).await?;
+        let round3 = run_sequential_round(&run, &all_rounds, 3, 1, None).await?;
         all_rounds.extend(round3);
     }
 
     // 8. Moderator Synthesis
-    if cancel_flag.load(Ordering::Relaxed) {
+    if run.cancelled() {
         return handle_cancellation(&app_handle, &decision_id);
     }
 
-    let transcript = format_transcript(&all_rounds, &all_agents);
+    let mut transcript = format_transcript(&all_rounds, &all_agents);
+    let compression = &settings.compression;
+    if compression.enabled && transcript_exceeds_budget(&transcript, compression.token_budget) {
+        let summary_model = if compression.model.is_empty() { settings.model.as_str() } else { compression.model.as_str() };
+        match compress_transcript(&settings.endpoint, summary_model, &all_rounds, &all_agents).await {
+            Ok(compressed) => transcript = compressed,
+            Err(e) => eprintln!("Transcript compression failed, sending it in full: {}", e),
+        }
+    }
     let moderator_user_prompt = if standalone_sandbox {
-        standalone_moderator_prompt(&brief, &transcript, &participant_names)
+        standalone_moderator_prompt(&brief, &transcript, &debaters)
     } else {
-        agents::moderator_prompt(&brief, &transcript, &participant_names)
+        agents::moderator_prompt(&brief, &transcript, &debaters)
     };
     let moderator_system_prompt = if standalone_sandbox {
         standalone_moderator_system_prompt().to_string()
@@ -936,17 +2152,34 @@ pub async fn run_debate(
         agents::read_agent_prompt(&app_data_dir, "moderator")
     };
 
-    let moderator_model = agent_models.get("moderator").filter(|m| !m.is_empty()).map(|m| m.as_str()).unwrap_or(&model);
-    let moderator_response = call_agent_with_retry(
-        &api_key, moderator_model,
-        "moderator", "Moderator", &moderator_system_prompt, &moderator_user_prompt, 2,
-        &app_handle, &decision_id, 99, 1,
-    ).await?;
+    let moderator_turn = json!({
+        "decision_id": decision_id,
+        "round_number": 99,
+        "exchange_number": 1,
+        "agent": "moderator",
+    });
+    let _ = app_handle.emit("debate-agent-token-start", moderator_turn.clone());
+    let moderator_result = run.call_agent(&AgentTurn {
+        agent_key: "moderator",
+        agent_label: "Moderator",
+        round_number: 99,
+        exchange_number: 1,
+        system_prompt: &moderator_system_prompt,
+        user_prompt: &moderator_user_prompt,
+    }, 2).await;
+    let _ = app_handle.emit("debate-agent-token-end", moderator_turn);
+    let moderator_response = moderator_result?;
 
     // Save moderator round
     {
         let state: tauri::State<'_, Mutex<AppState>> = app_handle.state();
         let state_guard = state.lock().map_err(|e| e.to_string())?;
+        state_guard.live_debate_buffers.clear(llm::DebateTurnRef {
+            decision_id: &decision_id,
+            agent_key: "moderator",
+            round_number: 99,
+            exchange_number: 1,
+        });
         state_guard.db.save_debate_round(
             &decision_id, 99, 1, "moderator", &moderator_response,
         ).map_err(|e| e.to_string())?;
@@ -989,7 +2222,9 @@ pub async fn run_debate(
 
     // 9. Parse moderator output and update decision summary (skip for standalone debates)
     if !is_standalone {
-        update_summary_from_debate(&app_handle, &decision_id, &all_rounds, &moderator_response, &debaters)?;
+        update_summary_from_debate(
+            &app_handle, &decision_id, &all_rounds, &moderator_response, &debaters, &timings.snapshot(),
+        )?;
     }
 
     // 10. Mark debate complete
@@ -1004,13 +2239,13 @@ pub async fn run_debate(
     let _ = app_handle.emit("debate-complete", json!({ "decision_id": decision_id }));
 
     // Await all live TTS tasks and build the manifest
-    if has_tts {
+    if tts_state.enabled {
         let handles_to_await = {
             let mut h = tts_state.handles.lock().map_err(|e| e.to_string())?;
             std::mem::take(&mut *h)
         };
 
-        let mut completed_segments: Vec<tts::AudioSegment> = Vec::new();
+        let mut completed_segments: Vec<tts::AudioSegment> = recovered_segments;
         for handle in handles_to_await {
             if let Ok(Some(segment)) = handle.await {
                 completed_segments.push(segment);
@@ -1018,34 +2253,85 @@ pub async fn run_debate(
         }
 
         if !completed_segments.is_empty() {
-            let manifest = tts::build_manifest_from_segments(&decision_id, completed_segments);
-            let manifest_json = serde_json::to_string_pretty(&manifest).unwrap_or_default();
-            let audio_dir_path = app_data_dir.join("debates").join(&decision_id);
-            let audio_dir_str = audio_dir_path.to_string_lossy().to_string();
+            let manifest = tts::build_manifest_from_segments(
+                &decision_id,
+                completed_segments,
+                tts_state.config.segment_gap_ms,
+            );
+            if let Err(e) = finish_debate_audio(&app_handle, &manifest) {
+                eprintln!("[TTS] Failed to save live audio manifest: {}", e);
+            }
+        }
+    } else if tts_state.post_generate {
+        generate_post_debate_audio(&app_handle, &decision_id, &tts_state).await;
+    }
 
-            // Save manifest.json to disk
-            let _ = std::fs::write(audio_dir_path.join("manifest.json"), &manifest_json);
+    Ok(())
+}
 
-            // Save to DB
-            let state: tauri::State<'_, Mutex<AppState>> = app_handle.state();
-            if let Ok(sg) = state.lock() {
-                let _ = sg.db.save_debate_audio(
-                    &decision_id,
-                    &manifest_json,
-                    manifest.total_duration_ms as i64,
-                    &audio_dir_str,
-                );
-            }
+/// Persist a finished debate's audio manifest and announce it to the AudioPlayer.
+pub fn finish_debate_audio(app_handle: &tauri::AppHandle, manifest: &tts::AudioManifest) -> Result<(), String> {
+    {
+        let state: tauri::State<'_, Mutex<AppState>> = app_handle.state();
+        let sg = state.lock().map_err(|e| e.to_string())?;
+        tts::persist_debate_audio(&sg.db, &sg.app_data_dir, manifest)?;
+    }
+    let _ = app_handle.emit("audio-generation-complete", json!({
+        "decision_id": manifest.decision_id,
+        "manifest": manifest,
+    }));
+    Ok(())
+}
 
-            // Emit final manifest for AudioPlayer replay
-            let _ = app_handle.emit("audio-generation-complete", json!({
+/// Rounds as they should be spoken: same order, TTS-normalized content.
+fn spoken_rounds(rounds: Vec<crate::db::DebateRound>) -> Vec<crate::db::DebateRound> {
+    rounds
+        .into_iter()
+        .map(|mut round| {
+            round.content = normalize_spoken_debate_output(&round.content, None);
+            round
+        })
+        .collect()
+}
+
+/// Generate the whole debate's audio in one pass ("post" TTS mode).
+/// Failures are reported as events; the debate itself already succeeded.
+async fn generate_post_debate_audio(
+    app_handle: &tauri::AppHandle,
+    decision_id: &str,
+    tts_state: &LiveTtsState,
+) {
+    let rounds = {
+        let state: tauri::State<'_, Mutex<AppState>> = app_handle.state();
+        let Ok(sg) = state.lock() else { return };
+        sg.db.get_debate_rounds(decision_id).unwrap_or_default()
+    };
+    if rounds.is_empty() {
+        return;
+    }
+
+    let rounds = spoken_rounds(rounds);
+    match tts::generate_debate_audio(
+        app_handle,
+        decision_id,
+        &rounds,
+        &tts_state.config,
+        &tts_state.registry,
+        &tts_state.app_data_dir,
+    ).await {
+        Ok(manifest) => {
+            if let Err(e) = finish_debate_audio(app_handle, &manifest) {
+                eprintln!("[TTS] Failed to save post-debate audio: {}", e);
+            }
+        }
+        Err(e) => {
+            eprintln!("[TTS] Post-debate audio generation failed: {}", e);
+            let _ = app_handle.emit("audio-generation-error", json!({
                 "decision_id": decision_id,
-                "manifest": manifest,
+                "error": e,
             }));
         }
     }
-
-    Ok(())
 }
 
 fn handle_cancellation(app_handle: &tauri::AppHandle, decision_id: &str) -> Result<(), String> {
@@ -1073,23 +2359,27 @@ fn handle_cancellation(app_handle: &tauri::AppHandle, decision_id: &str) -> Resu
     Ok(())
 }
 
-/// Extract final votes from the last round and build debate_summary for the decision.
-fn update_summary_from_debate(
-    app_handle: &tauri::AppHandle,
-    decision_id: &str,
+/// The summary fields a finished debate produces: `debate_summary` (votes from each
+/// debater's last turn plus the moderator's sections) and, when the moderator gave
+/// one, `recommendation`.
+fn debate_summary_update(
     all_rounds: &[crate::db::DebateRound],
-    moderator_response: &str,
-    debaters: &[AgentInfo],
-) -> Result<(), String> {
+    moderator_response: &str,
+    debater_keys: &[&str],
+    existing_summary: Option<&str>,
+    agent_latency: Value,
+) -> Value {
     let mut final_votes = serde_json::Map::new();
+    let mut closing_statements: Vec<&str> = Vec::new();
 
-    for agent in debaters {
+    for key in debater_keys {
         let last_entry = all_rounds.iter()
-            .filter(|r| r.agent == agent.key)
+            .filter(|r| r.agent == *key)
             .last();
         if let Some(entry) = last_entry {
             let vote = entry.content.chars().take(200).collect::<String>();
-            final_votes.insert(agent.key.clone(), Value::String(vote));
+            final_votes.insert(key.to_string(), Value::String(vote));
+            closing_statements.push(&entry.content);
         }
     }
 
@@ -1097,16 +2387,41 @@ fn update_summary_from_debate(
     let disagreements = extract_section(moderator_response, "Key Disagreements");
     let biases = extract_section(moderator_response, "Biases & Blind Spots Identified");
 
+    let rec_section = extract_section(moderator_response, "Recommendation");
+
+    let option_labels = summary_option_labels(existing_summary);
+    let vote_tally = tally_votes(&closing_statements, &option_labels);
+    let recommendation = parse_moderator_recommendation(&rec_section, moderator_response, &vote_tally);
     let debate_summary = json!({
-        "consensus_points": split_to_points(&consensus),
-        "key_disagreements": split_to_points(&disagreements),
-        "biases_identified": split_to_points(&biases),
+        "consensus_points": dedup_points(split_to_points(&consensus)),
+        "key_disagreements": dedup_points(split_to_points(&disagreements)),
+        "biases_identified": dedup_points(split_to_points(&biases)),
         "final_votes": final_votes,
+        "vote_tally": vote_tally,
+        "agent_latency": agent_latency,
     });
 
-    let rec_section = extract_section(moderator_response, "Recommendation");
-    let recommendation = parse_moderator_recommendation(&rec_section, moderator_response);
+    if let Some(rec) = recommendation {
+        json!({
+            "debate_summary": debate_summary,
+            "recommendation": rec,
+        })
+    } else {
+        json!({
+            "debate_summary": debate_summary,
+        })
+    }
+}
 
+/// Extract final votes from the last round and build debate_summary for the decision.
+fn update_summary_from_debate(
+    app_handle: &tauri::AppHandle,
+    decision_id: &str,
+    all_rounds: &[crate::db::DebateRound],
+    moderator_response: &str,
+    debaters: &[AgentInfo],
+    timings: &[AgentTiming],
+) -> Result<(), String> {
     let state: tauri::State<'_, Mutex<AppState>> = app_handle.state();
     let state_guard = state.lock().map_err(|e| e.to_string())?;
 
@@ -1116,114 +2431,1040 @@ fn update_summary_from_debate(
         .flatten()
         .and_then(|d| d.summary_json);
 
-    let update = if let Some(rec) = recommendation {
-        json!({
-            "debate_summary": debate_summary,
-            "recommendation": rec,
-        })
-    } else {
-        json!({
-            "debate_summary": debate_summary,
-        })
-    };
+    let debater_keys: Vec<&str> = debaters.iter().map(|a| a.key.as_str()).collect();
+    let update = debate_summary_update(
+        all_rounds,
+        moderator_response,
+        &debater_keys,
+        existing_summary.as_deref(),
+        summarize_agent_latency(timings),
+    );
 
-    let merged = decisions::merge_summary(existing_summary.as_deref(), &update);
+    let (merged, changes) = decisions::merge_summary_with_diff(existing_summary.as_deref(), &update);
     state_guard.db.update_decision_summary(decision_id, &merged).map_err(|e| e.to_string())?;
 
     let _ = app_handle.emit("decision-summary-updated", json!({
         "decision_id": decision_id,
         "summary": merged,
         "status": "recommended",
+        "changes": changes,
     }));
 
     Ok(())
 }
 
-/// Extract a markdown section by heading.
+fn latest_moderator_round(rounds: &[crate::db::DebateRound]) -> Option<&crate::db::DebateRound> {
+    rounds.iter().rev().find(|r| r.round_number == 99)
+}
+
+/// The moderator's full markdown synthesis (the round-99 row), if the debate got that far.
+pub fn moderator_synthesis(db: &Database, decision_id: &str) -> Result<Option<String>, String> {
+    let rounds = db.get_debate_rounds(decision_id).map_err(|e| e.to_string())?;
+    Ok(latest_moderator_round(&rounds).map(|r| r.content.clone()))
+}
+
+/// Recompute the debate-derived summary fields from the saved transcript alone.
+/// `debate_summary` and `recommendation` are replaced outright rather than merged,
+/// so anything a stale synthesis or the earlier chat put there is dropped. Agent
+/// latency isn't in the transcript and is carried over. Returns the new summary
+/// JSON and what changed.
+pub fn rebuild_summary_from_debate(
+    db: &Database,
+    decision_id: &str,
+) -> Result<(String, decisions::SummaryDiff), String> {
+    let decision = db
+        .get_decision(decision_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Decision not found".to_string())?;
+    let is_standalone = db
+        .get_conversation(&decision.conversation_id)
+        .map_err(|e| e.to_string())?
+        .is_some_and(|c| c.conv_type == "debate");
+    if is_standalone {
+        return Err("Standalone debates have no decision summary to rebuild".to_string());
+    }
+
+    let rounds = db.get_debate_rounds(decision_id).map_err(|e| e.to_string())?;
+    let moderator_response = latest_moderator_round(&rounds)
+        .map(|r| r.content.clone())
+        .ok_or_else(|| "Debate has no moderator synthesis to rebuild from".to_string())?;
+    let mut debater_keys: Vec<&str> = Vec::new();
+    for round in rounds.iter().filter(|r| r.round_number != 99) {
+        if !debater_keys.contains(&round.agent.as_str()) {
+            debater_keys.push(&round.agent);
+        }
+    }
+
+    let existing = decision.summary_json.as_deref();
+    let mut base: Value = existing
+        .and_then(|s| serde_json::from_str(s).ok())
+        .unwrap_or_else(|| json!({}));
+    let agent_latency = base
+        .pointer("/debate_summary/agent_latency")
+        .cloned()
+        .unwrap_or_else(|| json!({}));
+    if let Some(obj) = base.as_object_mut() {
+        obj.remove("debate_summary");
+        obj.remove("recommendation");
+    }
+    let base = base.to_string();
+
+    let update = debate_summary_update(&rounds, &moderator_response, &debater_keys, Some(&base), agent_latency);
+    let rebuilt = decisions::merge_summary(Some(&base), &update);
+    db.update_decision_summary(decision_id, &rebuilt).map_err(|e| e.to_string())?;
+    let changes = decisions::diff_summaries(existing, &rebuilt);
+    Ok((rebuilt, changes))
+}
+
+/// Seed for shuffled speaking order, derived from the decision id (FNV-1a) so a
+/// resumed or re-rendered debate reproduces the same order without extra state.
+pub fn speaking_order_seed(decision_id: &str) -> u64 {
+    decision_id.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, b| {
+        (hash ^ b as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Order debaters for one exchange. Without a seed (or in round 1, so the opening
+/// stays in registry order) the order rotates by exchange; with a seed it is a
+/// Fisher-Yates shuffle driven by splitmix64 over (seed, round, exchange).
+fn speaking_order(
+    debaters: &[AgentInfo],
+    round_number: i32,
+    exchange_number: i32,
+    shuffle_seed: Option<u64>,
+) -> Vec<&AgentInfo> {
+    let mut order: Vec<&AgentInfo> = debaters.iter().collect();
+    if order.len() < 2 {
+        return order;
+    }
+    match shuffle_seed {
+        Some(seed) if round_number > 1 => {
+            let mut state = seed ^ ((round_number as u64) << 32 | exchange_number as u32 as u64);
+            let mut next = || {
+                state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+                let mut z = state;
+                z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+                z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+                z ^ (z >> 31)
+            };
+            for i in (1..order.len()).rev() {
+                let j = (next() % (i as u64 + 1)) as usize;
+                order.swap(i, j);
+            }
+        }
+        _ => {
+            let rotation = (round_number.max(1) - 1 + exchange_number.max(1) - 1) as usize;
+            let count = order.len();
+            order.rotate_left(rotation % count);
+        }
+    }
+    order
+}
+
+/// Model, sampling and length settings for one debate, read from the config once
+/// when it starts so a settings change mid-debate doesn't mix models.
+#[derive(Debug, Clone)]
+pub struct DebateSettings {
+    pub endpoint: llm::LlmEndpoint,
+    pub model: String,
+    pub debate_default_model: String,
+    pub agent_models: HashMap<String, String>,
+    pub default_moderator_model: String,
+    pub max_tokens: u32,
+    pub temperature: f32,
+    pub min_response_chars: usize,
+    pub shape: DebateShape,
+    pub parallel_opening: bool,
+    pub shuffle_seed: Option<u64>,
+    /// How often streamed text is written to the live buffer.
+    pub token_flush_interval: std::time::Duration,
+    /// Append the spoken-style overlay to debater prompts.
+    pub apply_spoken_overlay: bool,
+    compression: TranscriptCompression,
+}
+
+impl DebateSettings {
+    pub fn from_config(config: &config::AppConfig, decision_id: &str, shape: DebateShape) -> Self {
+        Self {
+            endpoint: llm::LlmEndpoint::from_config(config),
+            model: config.model.clone(),
+            debate_default_model: config.debate_default_model.clone(),
+            agent_models: config.agent_models.clone(),
+            default_moderator_model: config.default_moderator_model.clone(),
+            max_tokens: config.debate_max_tokens,
+            temperature: debate_temperature(config.debate_temperature),
+            min_response_chars: config.min_response_chars,
+            shape,
+            parallel_opening: config.parallel_opening,
+            shuffle_seed: config.shuffle_speaking_order.then(|| speaking_order_seed(decision_id)),
+            token_flush_interval: std::time::Duration::from_millis(config.token_flush_interval_ms),
+            apply_spoken_overlay: config.apply_spoken_overlay,
+            compression: TranscriptCompression {
+                enabled: config.compress_long_transcripts,
+                token_budget: config.moderator_transcript_budget,
+                model: config.transcript_summary_model.clone(),
+            },
+        }
+    }
+
+    /// The model an agent speaks with: the moderator's resolution for "moderator",
+    /// the debater resolution for everyone else.
+    pub fn model_for(&self, agent_key: &str) -> &str {
+        if agent_key == "moderator" {
+            resolve_moderator_model(&self.agent_models, &self.default_moderator_model, &self.model)
+        } else {
+            resolve_debater_model(&self.agent_models, agent_key, &self.debate_default_model, &self.model)
+        }
+    }
+}
+
+/// The configured debate temperature clamped to the accepted range, or
+/// `DEFAULT_DEBATE_TEMPERATURE` if it isn't a usable number.
+pub fn debate_temperature(configured: f32) -> f32 {
+    config::clamp_temperature(configured).unwrap_or(config::DEFAULT_DEBATE_TEMPERATURE)
+}
+
+/// Pick the moderator's model: an explicit `agent_models["moderator"]` wins, then the
+/// configured default moderator model, then the general chat model.
+pub fn resolve_moderator_model<'a>(
+    agent_models: &'a HashMap<String, String>,
+    default_moderator_model: &'a str,
+    model: &'a str,
+) -> &'a str {
+    agent_models
+        .get("moderator")
+        .map(|m| m.trim())
+        .filter(|m| !m.is_empty())
+        .or_else(|| Some(default_moderator_model.trim()).filter(|m| !m.is_empty()))
+        .unwrap_or(model)
+}
+
+/// The model debaters use when they have no per-agent override: the configured
+/// debate default, or the general chat model when that is unset.
+pub fn debater_fallback_model<'a>(debate_default_model: &'a str, model: &'a str) -> &'a str {
+    Some(debate_default_model.trim()).filter(|m| !m.is_empty()).unwrap_or(model)
+}
+
+/// Pick a debater's model: an explicit `agent_models[agent_key]` wins, then the
+/// debate default model, then the general chat model.
+pub fn resolve_debater_model<'a>(
+    agent_models: &'a HashMap<String, String>,
+    agent_key: &str,
+    debate_default_model: &'a str,
+    model: &'a str,
+) -> &'a str {
+    agent_models
+        .get(agent_key)
+        .map(|m| m.trim())
+        .filter(|m| !m.is_empty())
+        .unwrap_or_else(|| debater_fallback_model(debate_default_model, model))
+}
+
+/// Option labels from a decision's summary JSON, in the order they were listed.
+fn summary_option_labels(summary_json: Option<&str>) -> Vec<String> {
+    summary_json
+        .and_then(|s| serde_json::from_str::<Value>(s).ok())
+        .and_then(|v| v.get("options").and_then(|o| o.as_array()).cloned())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|o| o.get("label").and_then(|l| l.as_str()).map(|l| l.to_string()))
+        .collect()
+}
+
+/// Words that, shortly before an option, mean the speaker is rejecting it.
+const VOTE_NEGATIONS: &[&str] = &[
+    "not", "no", "never", "against", "don't", "dont", "won't", "wouldn't", "shouldn't", "can't", "cannot",
+];
+
+/// Phrases marking the sentence that carries a speaker's explicit vote.
+const VOTE_MARKERS: &[&str] = &["vote", "position", "final call", "my call", "i recommend", "go with"];
+
+/// Whether the mention at `pos` is negated ("I would not accept…", "rather than
+/// accept…"), looking back a few words within the same clause.
+fn mention_is_negated(statement: &str, pos: usize) -> bool {
+    let before = &statement[..pos];
+    let clause_start = before
+        .rfind([',', ';', ':', '.', '!', '?', '\n'])
+        .map(|i| i + 1)
+        .unwrap_or(0);
+    let clause = before[clause_start..].replace('\u{2019}', "'");
+    if clause.trim_end().ends_with("rather than") || clause.trim_end().ends_with("instead of") {
+        return true;
+    }
+    clause
+        .split_whitespace()
+        .rev()
+        .take(4)
+        .any(|w| VOTE_NEGATIONS.contains(&w.trim_matches(|c: char| !c.is_alphanumeric() && c != '\'')))
+}
+
+/// Where a statement first backs an option: the full label if present, otherwise
+/// the first of its significant words, provided every significant word appears.
+/// Negated mentions don't count.
+fn option_mention(statement: &str, label: &str) -> Option<usize> {
+    let label = label.trim().to_lowercase();
+    if label.is_empty() {
+        return None;
+    }
+    let backed = |needle: &str| {
+        statement
+            .match_indices(needle)
+            .map(|(pos, _)| pos)
+            .find(|&pos| !mention_is_negated(statement, pos))
+    };
+    if let Some(pos) = backed(&label) {
+        return Some(pos);
+    }
+    let words: Vec<&str> = label
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.len() >= 4)
+        .collect();
+    if words.is_empty() {
+        return None;
+    }
+    let positions: Option<Vec<usize>> = words.iter().map(|w| backed(w)).collect();
+    positions.and_then(|p| p.into_iter().min())
+}
+
+/// The option a text backs: the one it mentions first, ignoring negated mentions.
+fn backed_option(text: &str, option_labels: &[String]) -> Option<usize> {
+    option_labels
+        .iter()
+        .enumerate()
+        .filter_map(|(i, label)| option_mention(text, label).map(|pos| (pos, i)))
+        .min()
+        .map(|(_, i)| i)
+}
+
+/// The option a closing statement votes for. An explicit vote sentence ("My vote:
+/// …", "My final position is …") wins over options merely discussed elsewhere.
+fn statement_vote(statement: &str, option_labels: &[String]) -> Option<usize> {
+    let lowered = statement.to_lowercase();
+    lowered
+        .split(['.', '!', '?', '\n'])
+        .filter(|sentence| VOTE_MARKERS.iter().any(|m| sentence.contains(m)))
+        .find_map(|sentence| backed_option(sentence, option_labels))
+        .or_else(|| backed_option(&lowered, option_labels))
+}
+
+/// Count closing statements per option using `statement_vote`; statements that
+/// back none go to `undecided`.
+fn tally_votes(statements: &[&str], option_labels: &[String]) -> serde_json::Map<String, Value> {
+    let mut counts: Vec<u64> = vec![0; option_labels.len()];
+    let mut undecided = 0u64;
+
+    for statement in statements {
+        match statement_vote(statement, option_labels) {
+            Some(i) => counts[i] += 1,
+            None => undecided += 1,
+        }
+    }
+
+    let mut tally = serde_json::Map::new();
+    for (label, count) in option_labels.iter().zip(counts) {
+        tally.insert(label.clone(), json!(count));
+    }
+    tally.insert("undecided".to_string(), json!(undecided));
+    tally
+}
+
+/// Extract a `## Heading` section up to the next `##` heading. Deeper
+/// `###` sub-headings are treated as part of the section.
 fn extract_section(text: &str, heading: &str) -> String {
-    let marker = format!("## {}", heading);
-    if let Some(start) = text.find(&marker) {
-        let after = &text[start + marker.len()..];
-        let end = after.find("\n## ").unwrap_or(after.len());
-        after[..end].trim().to_string()
+    let mut section: Option<Vec<&str>> = None;
+    for line in text.lines() {
+        let h2_title = line.trim_start().strip_prefix("## ");
+        match (&mut section, h2_title) {
+            (None, Some(title)) => {
+                if let Some(rest) = title.trim_start().strip_prefix(heading) {
+                    section = Some(vec![rest]);
+                }
+            }
+            (Some(_), Some(_)) => break,
+            (Some(lines), None) => lines.push(line),
+            (None, None) => {}
+        }
+    }
+    section.map(|lines| lines.join("\n").trim().to_string()).unwrap_or_default()
+}
+
+/// Split text into points, dropping bullet and numbered-list markers
+/// (`- `, `* `, `1. `, `2) `) and any sub-heading lines.
+fn split_to_points(text: &str) -> Vec<String> {
+    if text.is_empty() {
+        return Vec::new();
+    }
+    text.lines()
+        .map(|l| l.trim())
+        .filter(|l| !l.starts_with('#'))
+        .map(|l| strip_list_number(l.trim_start_matches('-').trim_start_matches('*').trim()))
+        .filter(|l| !l.is_empty())
+        .map(|l| l.to_string())
+        .collect()
+}
+
+/// Drop points that repeat an earlier one (ignoring case and surrounding
+/// whitespace), keeping the first occurrence in its original position.
+fn dedup_points(points: Vec<String>) -> Vec<String> {
+    let mut seen = std::collections::HashSet::new();
+    points
+        .into_iter()
+        .filter(|p| seen.insert(p.trim().to_lowercase()))
+        .collect()
+}
+
+/// Strip a leading `1. ` or `2) ` list number.
+fn strip_list_number(line: &str) -> &str {
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits == 0 {
+        return line;
+    }
+    let rest = &line[digits..];
+    rest.strip_prefix(". ")
+        .or_else(|| rest.strip_prefix(") "))
+        .map(str::trim_start)
+        .unwrap_or(line)
+}
+
+/// Parse the moderator's recommendation section into a structured Recommendation object.
+/// A close committee vote (see `close_vote_split`) lowers the stated confidence one level.
+fn parse_moderator_recommendation(
+    rec_section: &str,
+    full_text: &str,
+    vote_tally: &serde_json::Map<String, Value>,
+) -> Option<Value> {
+    if rec_section.is_empty() && !full_text.contains("**Choice**") {
+        return None;
+    }
+
+    let text = if rec_section.is_empty() { full_text } else { rec_section };
+
+    let choice = extract_bold_value(text, "Choice")
+        .unwrap_or_else(|| "See moderator's synthesis".to_string());
+    let confidence = extract_bold_value(text, "Confidence")
+        .unwrap_or_else(|| "medium".to_string())
+        .to_lowercase();
+    let reasoning = extract_bold_value(text, "Reasoning")
+        .unwrap_or_else(|| {
+            rec_section.lines()
+                .filter(|l| !l.starts_with("**"))
+                .map(|l| l.trim())
+                .filter(|l| !l.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ")
+        });
+
+    let tradeoffs = extract_section(full_text, "What You're Giving Up");
+    let dissent = extract_section(full_text, "Minority View");
+    let unanimous = dissent
+        .trim_end_matches('.')
+        .eq_ignore_ascii_case("none");
+
+    let action_plan = extract_section(full_text, "Action Plan");
+    let next_steps: Vec<String> = split_to_points(&action_plan);
+
+    let mut conf = if confidence.contains("high") {
+        "high"
+    } else if confidence.contains("low") {
+        "low"
+    } else {
+        "medium"
+    };
+    let mut reasoning = reasoning;
+    if let Some(split) = close_vote_split(vote_tally) {
+        let lowered = if conf == "high" { "medium" } else { "low" };
+        if lowered != conf {
+            reasoning = format!(
+                "{} (Confidence lowered from {} to {}: the committee split {}.)",
+                reasoning, conf, lowered, split
+            )
+            .trim_start()
+            .to_string();
+            conf = lowered;
+        }
+    }
+
+    Some(json!({
+        "choice": choice,
+        "confidence": conf,
+        "reasoning": reasoning,
+        "tradeoffs": if tradeoffs.is_empty() { None } else { Some(tradeoffs) },
+        "dissent": if dissent.is_empty() || unanimous { None } else { Some(dissent) },
+        "next_steps": if next_steps.is_empty() { None } else { Some(next_steps) },
+    }))
+}
+
+/// Below this share of the decided votes, the winning option hasn't carried the
+/// committee convincingly (a 3-2 split is 60%).
+const DECISIVE_VOTE_SHARE: f64 = 2.0 / 3.0;
+
+/// The split (e.g. "3-2") when the leading option won less than a decisive share
+/// of the votes that backed an option. `None` for decisive votes or no votes.
+fn close_vote_split(vote_tally: &serde_json::Map<String, Value>) -> Option<String> {
+    let mut counts: Vec<u64> = vote_tally
+        .iter()
+        .filter(|(label, _)| label.as_str() != "undecided")
+        .filter_map(|(_, count)| count.as_u64())
+        .filter(|count| *count > 0)
+        .collect();
+    let decided: u64 = counts.iter().sum();
+    counts.sort_unstable_by(|a, b| b.cmp(a));
+    let leading = *counts.first()?;
+    if (leading as f64) / (decided as f64) >= DECISIVE_VOTE_SHARE {
+        return None;
+    }
+    Some(counts.iter().map(|c| c.to_string()).collect::<Vec<_>>().join("-"))
+}
+
+/// Extract a value after a bold label like **Choice**: value
+fn extract_bold_value(text: &str, label: &str) -> Option<String> {
+    let pattern = format!("**{}**:", label);
+    if let Some(pos) = text.find(&pattern) {
+        let after = &text[pos + pattern.len()..];
+        let end = after.find('\n').unwrap_or(after.len());
+        let value = after[..end].trim().to_string();
+        if value.is_empty() { None } else { Some(value) }
     } else {
-        String::new()
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn integration_resume_plan_keeps_saved_rounds_and_skips_completed_steps() {
+        let db = Database::new(":memory:").expect("in-memory database should initialize");
+        let conv = db
+            .create_conversation_with_type("Switch teams?", "decision")
+            .expect("conversation should be created");
+        let decision = db
+            .create_decision(&conv.id, "Switch teams?")
+            .expect("decision should be created");
+        db.save_debate_round(&decision.id, 1, 1, "rationalist", "Opening A").unwrap();
+        db.save_debate_round(&decision.id, 1, 1, "advocate", "Opening B").unwrap();
+        db.save_debate_round(&decision.id, 2, 1, "rationalist", "Rebuttal A").unwrap();
+
+        let setup = DebateSetup {
+            roster: vec!["rationalist".to_string(), "advocate".to_string()],
+            shape: DebateShape::FULL,
+        };
+        save_debate_setup(&db, &decision.id, &setup).expect("setup should save");
+        let setup = load_debate_setup(&db, &decision.id)
+            .expect("setup should load")
+            .expect("setup should exist");
+        assert_eq!(setup.shape, DebateShape::FULL);
+
+        let rounds = db.get_debate_rounds(&decision.id).expect("rounds should load");
+        let pending = remaining_committee_steps(&rounds, setup.shape, &setup.roster)
+            .expect("resume should be allowed");
+        // The advocate still owes a Round 2 rebuttal, so that exchange runs again first
+        assert_eq!(pending, vec![(2, 1), (2, 2), (3, 1)]);
+        assert!(has_spoken(&rounds, "rationalist", (2, 1)));
+        assert!(!has_spoken(&rounds, "advocate", (2, 1)));
+        assert_eq!(db.get_debate_rounds(&decision.id).unwrap().len(), 3);
+
+        db.save_debate_round(&decision.id, 2, 1, "advocate", "Rebuttal B").unwrap();
+        let rounds = db.get_debate_rounds(&decision.id).unwrap();
+        assert_eq!(
+            remaining_committee_steps(&rounds, setup.shape, &setup.roster).unwrap(),
+            vec![(2, 2), (3, 1)]
+        );
+
+        assert_eq!(remaining_committee_steps(&[], DebateShape::FULL, &setup.roster).unwrap().len(), 4);
+        assert!(remaining_committee_steps(&rounds[..2], DebateShape::QUICK, &setup.roster).unwrap().is_empty());
+
+        db.save_debate_round(&decision.id, 99, 1, "moderator", "Synthesis").unwrap();
+        let rounds = db.get_debate_rounds(&decision.id).unwrap();
+        assert!(remaining_committee_steps(&rounds, DebateShape::FULL, &setup.roster).is_err());
+
+        let other = db.create_decision(&conv.id, "Legacy").unwrap();
+        assert_eq!(load_debate_setup(&db, &other.id).unwrap(), None);
+    }
+
+    #[test]
+    fn unit_debate_shape_schedules_expected_steps() {
+        assert_eq!(
+            committee_steps(DebateShape { round2_exchanges: 2, include_round3: true }),
+            vec![(1, 1), (2, 1), (2, 2), (3, 1)]
+        );
+        assert_eq!(
+            committee_steps(DebateShape { round2_exchanges: 0, include_round3: false }),
+            vec![(1, 1)]
+        );
+        assert_eq!(
+            committee_steps(DebateShape { round2_exchanges: 1, include_round3: false }),
+            vec![(1, 1), (2, 1)]
+        );
+        assert_eq!(DebateShape::from_quick_mode(true), DebateShape::QUICK);
+        assert_eq!(DebateShape::from_quick_mode(false), DebateShape::FULL);
+
+        let oversized = DebateShape { round2_exchanges: 50, include_round3: false };
+        assert_eq!(committee_steps(oversized).len(), 1 + MAX_ROUND2_EXCHANGES as usize);
+
+        let rounds = vec![crate::db::DebateRound {
+            id: "r1".to_string(),
+            decision_id: "d".to_string(),
+            round_number: 2,
+            exchange_number: 1,
+            agent: "rationalist".to_string(),
+            content: "Rebuttal".to_string(),
+            created_at: String::new(),
+        }];
+        let custom = DebateShape { round2_exchanges: 1, include_round3: false };
+        assert!(remaining_committee_steps(&rounds, custom, &["rationalist".to_string()]).unwrap().is_empty());
+    }
+
+    fn debater(key: &str, sort_order: u32) -> AgentInfo {
+        AgentInfo {
+            key: key.to_string(),
+            label: key.to_string(),
+            emoji: String::new(),
+            color: String::new(),
+            role: "debater".to_string(),
+            builtin: true,
+            sort_order,
+            voice_gender: "male".to_string(),
+        }
+    }
+
+    #[test]
+    fn integration_quick_poll_saves_one_opening_per_debater_then_moderator() {
+        let db = Database::new(":memory:").expect("in-memory database should initialize");
+        let conv = db.create_conversation_with_type("Buy a house?", "decision").unwrap();
+        let decision = db.create_decision(&conv.id, "Buy a house?").unwrap();
+        let debaters = vec![debater("rationalist", 0), debater("advocate", 1), debater("contrarian", 2)];
+
+        assert_eq!(committee_steps(DebateShape::QUICK), vec![(1, 1)]);
+        let outcomes = debaters
+            .iter()
+            .map(|d| (d.key.clone(), Ok(format!("{} says buy.", d.key))))
+            .collect();
+        let (rounds, failures) = save_opening_rounds(&db, &decision.id, &debaters, outcomes)
+            .expect("openings should save");
+        assert_eq!(rounds.len(), debaters.len());
+        assert!(failures.is_empty());
+        db.save_debate_round(&decision.id, 99, 1, "moderator", "Synthesis").unwrap();
+
+        let saved = db.get_debate_rounds(&decision.id).unwrap();
+        assert_eq!(saved.len(), debaters.len() + 1);
+        for d in &debaters {
+            assert_eq!(saved.iter().filter(|r| r.agent == d.key && r.round_number == 1).count(), 1);
+        }
+        assert_eq!(saved.iter().filter(|r| r.round_number == 99).count(), 1);
+        assert!(saved.iter().all(|r| r.round_number == 1 || r.round_number == 99));
+    }
+
+    #[test]
+    fn integration_parallel_openings_save_in_registry_order() {
+        let db = Database::new(":memory:").expect("in-memory database should initialize");
+        let conv = db.create_conversation_with_type("Go back to school?", "decision").unwrap();
+        let decision = db.create_decision(&conv.id, "Go back to school?").unwrap();
+        let debaters = vec![
+            debater("rationalist", 0),
+            debater("advocate", 1),
+            debater("contrarian", 2),
+            debater("visionary", 3),
+        ];
+
+        // Completion order as a concurrent join might report it
+        let outcomes = vec![
+            ("visionary".to_string(), Ok("Think ten years out.".to_string())),
+            ("advocate".to_string(), Ok("You light up talking about it.".to_string())),
+            ("contrarian".to_string(), Err("timeout".to_string())),
+            ("rationalist".to_string(), Ok("Run the numbers first.".to_string())),
+        ];
+        let (rounds, failures) = save_opening_rounds(&db, &decision.id, &debaters, outcomes)
+            .expect("openings should save");
+        assert_eq!(failures, vec![("contrarian".to_string(), "timeout".to_string())]);
+
+        let expected = vec!["rationalist", "advocate", "visionary"];
+        let returned: Vec<&str> = rounds.iter().map(|r| r.agent.as_str()).collect();
+        assert_eq!(returned, expected);
+        let saved = db.get_debate_rounds(&decision.id).unwrap();
+        let stored: Vec<&str> = saved.iter().map(|r| r.agent.as_str()).collect();
+        assert_eq!(stored, expected);
+        assert!(saved.iter().all(|r| r.round_number == 1 && r.exchange_number == 1));
+    }
+
+    #[test]
+    fn unit_ensure_round_deletable_guards_summarized_moderator_round() {
+        let round = |round_number: i32, agent: &str| crate::db::DebateRound {
+            id: "r".to_string(),
+            decision_id: "d".to_string(),
+            round_number,
+            exchange_number: 1,
+            agent: agent.to_string(),
+            content: String::new(),
+            created_at: String::new(),
+        };
+        let summarized = r#"{"debate_summary":{"consensus":"Stay"}}"#;
+
+        assert!(ensure_round_deletable(&round(1, "advocate"), Some(summarized)).is_ok());
+        assert!(ensure_round_deletable(&round(99, "moderator"), None).is_ok());
+        assert!(ensure_round_deletable(&round(99, "moderator"), Some(r#"{"options":[]}"#)).is_ok());
+        assert!(ensure_round_deletable(&round(99, "moderator"), Some(summarized)).is_err());
+    }
+
+    #[test]
+    fn integration_compile_brief_is_stable_and_respects_preconditions() {
+        let dir = tempdir().expect("temp directory should exist");
+        let app_data_dir = dir.path().to_path_buf();
+        profile::write_profile_file(&app_data_dir, "values.md", "# Values\n- Family")
+            .expect("profile should write");
+        profile::write_profile_file(&app_data_dir, "career.md", "# Career\n- Engineer")
+            .expect("profile should write");
+
+        let db = Database::new(":memory:").expect("in-memory database should initialize");
+        let conv = db
+            .create_conversation_with_type("Relocate?", "decision")
+            .expect("conversation should be created");
+        let decision = db
+            .create_decision(&conv.id, "Relocate?")
+            .expect("decision should be created");
+        assert!(check_debate_preconditions(&decision).is_err());
+
+        let summary = decisions::merge_summary(None, &json!({
+            "options": [{"label": "Stay", "description": "Keep current city"}]
+        }));
+        db.update_decision_summary(&decision.id, &summary).expect("summary should update");
+        let decision = db.get_decision(&decision.id).unwrap().unwrap();
+        assert_eq!(
+            check_debate_preconditions(&decision).unwrap_err(),
+            "Decision isn't ready for a debate yet: add at least one key variable."
+        );
+
+        let summary = decisions::merge_summary(Some(&summary), &json!({
+            "variables": [{"label": "Rent", "value": "30% lower", "impact": "high"}]
+        }));
+        db.update_decision_summary(&decision.id, &summary).expect("summary should update");
+        let decision = db.get_decision(&decision.id).unwrap().unwrap();
+        assert!(check_debate_preconditions(&decision).is_ok());
+
+        let brief = compile_brief(&db, &app_data_dir, &decision.id).expect("brief should compile");
+        assert_eq!(brief, compile_brief(&db, &app_data_dir, &decision.id).unwrap());
+        assert!(brief.contains("**Relocate?**"));
+        assert!(brief.contains("**Rent**: 30% lower (impact: high)"));
+        let career_pos = brief.find("### career.md").expect("career profile should be included");
+        let values_pos = brief.find("### values.md").expect("values profile should be included");
+        assert!(career_pos < values_pos);
+        assert!(!brief.contains("## Supporting Documents"));
+    }
+
+    #[test]
+    fn integration_compile_brief_includes_decision_context_files() {
+        let dir = tempdir().expect("temp directory should exist");
+        let app_data_dir = dir.path().to_path_buf();
+        let db = Database::new(":memory:").expect("in-memory database should initialize");
+        let conv = db.create_conversation_with_type("Take the offer?", "decision").unwrap();
+        let decision = db.create_decision(&conv.id, "Take the offer?").unwrap();
+
+        decisions::add_context_file(&app_data_dir, &decision.id, "offer.txt", "Base salary: 140k\n")
+            .expect("context should be stored");
+        decisions::add_context_file(&app_data_dir, &decision.id, "budget.md", "Rent is 2.1k/month")
+            .expect("context should be stored");
+        assert!(decisions::add_context_file(&app_data_dir, &decision.id, "../escape.md", "x").is_err());
+        assert!(decisions::add_context_file(&app_data_dir, &decision.id, "offer.pdf", "x").is_err());
+
+        let files = decisions::list_context_files(&app_data_dir, &decision.id).unwrap();
+        assert_eq!(files.iter().map(|f| f.filename.as_str()).collect::<Vec<_>>(), vec!["budget.md", "offer.txt"]);
+
+        let brief = compile_brief(&db, &app_data_dir, &decision.id).expect("brief should compile");
+        assert!(brief.contains("## Supporting Documents\n### budget.md\nRent is 2.1k/month\n\n### offer.txt\nBase salary: 140k"));
+
+        decisions::remove_context_file(&app_data_dir, &decision.id, "budget.md").unwrap();
+        assert!(decisions::remove_context_file(&app_data_dir, &decision.id, "budget.md").is_err());
+        let brief = compile_brief(&db, &app_data_dir, &decision.id).unwrap();
+        assert!(!brief.contains("budget.md"));
+        assert!(brief.contains("### offer.txt"));
+    }
+
+    #[test]
+    fn integration_debater_system_prompt_omits_overlay_when_disabled() {
+        let dir = tempdir().expect("temp directory should exist");
+        let app_data_dir = dir.path().to_path_buf();
+        let agent = agents::builtin_agents().into_iter().find(|a| a.key == "pragmatist").unwrap();
+        let overlay = agents::debate_spoken_style_overlay();
+
+        let mut config = config::load_config(&app_data_dir);
+        let settings = DebateSettings::from_config(&config, "d1", DebateShape::FULL);
+        let prompt = debater_system_prompt(&app_data_dir, &agent, false, settings.apply_spoken_overlay);
+        assert!(prompt.ends_with(overlay));
+
+        config.apply_spoken_overlay = false;
+        let settings = DebateSettings::from_config(&config, "d1", DebateShape::FULL);
+        assert!(!settings.apply_spoken_overlay);
+        let prompt = debater_system_prompt(&app_data_dir, &agent, false, settings.apply_spoken_overlay);
+        assert!(!prompt.contains(overlay));
+        assert_eq!(prompt, agents::read_agent_prompt(&app_data_dir, "pragmatist"));
+    }
+
+    #[test]
+    fn integration_compile_brief_truncates_long_conversations() {
+        let dir = tempdir().expect("temp directory should exist");
+        let app_data_dir = dir.path().to_path_buf();
+        profile::write_profile_file(&app_data_dir, "values.md", "# Values\n- Stability")
+            .expect("profile should write");
+        let db = Database::new(":memory:").expect("in-memory database should initialize");
+        let conv = db.create_conversation_with_type("Buy a boat?", "decision").unwrap();
+        let decision = db.create_decision(&conv.id, "Buy a boat?").unwrap();
+        db.add_message(&conv.id, "user", &"I grew up sailing. ".repeat(20)).unwrap();
+        db.add_message(&conv.id, "assistant", "How often would you use it?").unwrap();
+        db.add_message(&conv.id, "user", "Most summer weekends.").unwrap();
+
+        let brief = compile_brief(&db, &app_data_dir, &decision.id).unwrap();
+        assert!(!brief.contains(CONVERSATION_OMITTED_MARKER), "short chats pass through unchanged");
+        assert!(brief.contains("I grew up sailing."));
+
+        let mut config = config::load_config(&app_data_dir);
+        config.brief_conversation_char_limit = 100;
+        config::save_config(&app_data_dir, &config).expect("config should save");
+        let brief = compile_brief(&db, &app_data_dir, &decision.id).unwrap();
+        assert!(brief.contains(
+            "### Conversation Context\n[earlier conversation omitted]\n\nAI: How often would you use it?\n\nUser: Most summer weekends."
+        ));
+        assert!(!brief.contains("I grew up sailing."));
+        assert!(brief.contains("### values.md\n# Values\n- Stability"));
+        assert!(brief.contains("**Buy a boat?**"));
     }
-}
 
-/// Split text into bullet points.
-fn split_to_points(text: &str) -> Vec<String> {
-    if text.is_empty() {
-        return Vec::new();
+    #[test]
+    fn unit_transcript_compression_triggers_only_over_budget() {
+        let agents = agents::builtin_agents();
+        let rounds: Vec<crate::db::DebateRound> = (0..15)
+            .map(|i| crate::db::DebateRound {
+                id: format!("r{}", i),
+                decision_id: "d".to_string(),
+                round_number: 1 + i / 5,
+                exchange_number: 1,
+                agent: agents[(i % 5) as usize].key.clone(),
+                content: "The rent in Lisbon is lower but the salary drops by a third. ".repeat(60),
+                created_at: String::new(),
+            })
+            .collect();
+        let transcript = format_transcript(&rounds, &agents);
+
+        assert!(llm::estimate_tokens(&transcript) > 10_000);
+        assert!(transcript_exceeds_budget(&transcript, 8_000));
+        assert!(!transcript_exceeds_budget(&transcript, 24_000));
+        assert!(!transcript_exceeds_budget(&format_transcript(&rounds[..2], &agents), 8_000));
+        assert_eq!(round_header(2, 3), "Round 2 (exchange 3)");
     }
-    text.lines()
-        .map(|l| l.trim().trim_start_matches('-').trim_start_matches('*').trim())
-        .filter(|l| !l.is_empty())
-        .map(|l| l.to_string())
-        .collect()
-}
 
-/// Parse the moderator's recommendation section into a structured Recommendation object.
-fn parse_moderator_recommendation(rec_section: &str, full_text: &str) -> Option<Value> {
-    if rec_section.is_empty() && !full_text.contains("**Choice**") {
-        return None;
+    #[tokio::test]
+    async fn unit_one_word_reply_triggers_retry() {
+        let replies = std::cell::RefCell::new(vec![
+            "Moving to Lisbon keeps your savings intact for two more years.".to_string(),
+            "**Agreed.**".to_string(),
+        ]);
+        let calls = std::cell::Cell::new(0);
+        let text = retry_until_substantive(2, 20, || {
+            calls.set(calls.get() + 1);
+            let reply = replies.borrow_mut().pop().unwrap();
+            async move { Ok(reply) }
+        })
+        .await
+        .expect("second reply is long enough");
+        assert_eq!(calls.get(), 2, "the one-word reply was retried");
+        assert!(text.starts_with("Moving to Lisbon"));
+
+        let err = retry_until_substantive(1, 20, || async { Ok("Yes.".to_string()) })
+            .await
+            .unwrap_err();
+        assert!(err.contains("too short"), "{}", err);
+        assert!(ensure_substantive("   ", 1).is_err());
+        assert!(ensure_substantive("Stay.", 0).is_ok());
     }
 
-    let text = if rec_section.is_empty() { full_text } else { rec_section };
+    #[tokio::test]
+    async fn unit_replay_emits_one_response_per_round_and_round_boundaries() {
+        let round = |round_number: i32, exchange_number: i32, agent: &str, content: &str| crate::db::DebateRound {
+            id: String::new(),
+            decision_id: "d1".to_string(),
+            round_number,
+            exchange_number,
+            agent: agent.to_string(),
+            content: content.to_string(),
+            created_at: String::new(),
+        };
+        let rounds = vec![
+            round(1, 1, "rationalist", "Stay where you are."),
+            round(1, 1, "advocate", "Move for the growth."),
+            round(2, 1, "rationalist", "Growth is speculative."),
+            round(99, 1, "moderator", "Lean toward moving."),
+        ];
+        let segment = |index: usize, agent: &str, round: i32, duration_ms: u64| tts::AudioSegment {
+            index,
+            agent: agent.to_string(),
+            round,
+            exchange: 1,
+            text: String::new(),
+            audio_file: String::new(),
+            duration_ms,
+            start_ms: 0,
+        };
+        let manifest = tts::AudioManifest {
+            decision_id: "d1".to_string(),
+            segments: vec![
+                segment(0, "rationalist", 1, 4000),
+                segment(1, "advocate", 1, 6000),
+                segment(2, "rationalist", 2, 2000),
+            ],
+            total_duration_ms: 12_000,
+        };
 
-    let choice = extract_bold_value(text, "Choice")
-        .unwrap_or_else(|| "See moderator's synthesis".to_string());
-    let confidence = extract_bold_value(text, "Confidence")
-        .unwrap_or_else(|| "medium".to_string())
-        .to_lowercase();
-    let reasoning = extract_bold_value(text, "Reasoning")
-        .unwrap_or_else(|| {
-            rec_section.lines()
-                .filter(|l| !l.starts_with("**"))
-                .map(|l| l.trim())
-                .filter(|l| !l.is_empty())
-                .collect::<Vec<_>>()
-                .join(" ")
-        });
+        let plan = replay_events("d1", &rounds, Some(&manifest), 2.0);
+        let names: Vec<&str> = plan.iter().map(|e| e.name).collect();
+        assert_eq!(names, vec![
+            "debate-agent-response", "debate-agent-response", "debate-round-complete",
+            "debate-agent-response", "debate-round-complete",
+            "debate-agent-response", "debate-round-complete",
+        ]);
+        let delays: Vec<u64> = plan.iter().map(|e| e.delay_ms).collect();
+        // Moderator has no audio: 3 words at 2.5 words/s = 1200ms, halved by speed 2
+        assert_eq!(delays, vec![0, 2000, 3000, 0, 1000, 0, 600]);
+        assert_eq!(plan[5].payload["agent"], "moderator");
+
+        let mut emitted: Vec<(String, Value)> = Vec::new();
+        let count = replay_debate("d1", &rounds, Some(&manifest), 1000.0, |name, payload| {
+            emitted.push((name.to_string(), payload));
+        })
+        .await
+        .unwrap();
+        assert_eq!(count, 7);
+        assert_eq!(emitted.len(), 7);
+        assert_eq!(emitted.iter().filter(|(n, _)| n == "debate-agent-response").count(), 4);
+        assert_eq!(emitted[2].1["round_number"], 1);
+
+        assert!(replay_debate("d1", &rounds, None, 0.0, |_, _| {}).await.is_err());
+        assert_eq!(replay_debate("d1", &[], None, 1.0, |_, _| {}).await.unwrap(), 0);
+    }
 
-    let tradeoffs = extract_section(full_text, "What You're Giving Up");
+    #[tokio::test]
+    async fn unit_live_tts_semaphore_bounds_in_flight_requests() {
+        let cfg = config::AppConfig { tts_concurrency: 2, ..Default::default() };
+        let tts_state = LiveTtsState::new(cfg, Vec::new(), PathBuf::from("/tmp"), 0);
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let mut tasks = tokio::task::JoinSet::new();
+        for _ in 0..6 {
+            let permits = Arc::clone(&tts_state.permits);
+            let in_flight = Arc::clone(&in_flight);
+            let peak = Arc::clone(&peak);
+            tasks.spawn(async move {
+                throttled_tts(permits, async move {
+                    let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+                    in_flight.fetch_sub(1, Ordering::SeqCst);
+                    Ok::<_, String>(())
+                })
+                .await
+            });
+        }
+        while let Some(joined) = tasks.join_next().await {
+            joined.unwrap().unwrap();
+        }
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+        assert_eq!(tts_state.permits.available_permits(), 2);
 
-    let action_plan = extract_section(full_text, "Action Plan");
-    let next_steps: Vec<String> = split_to_points(&action_plan);
+        let result = throttled_tts(Arc::clone(&tts_state.permits), async {
+            Err::<(), _>("ElevenLabs API error (401 Unauthorized): bad key".to_string())
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(tts_state.permits.available_permits(), 2, "a failed request releases its permit");
+    }
 
-    let conf = if confidence.contains("high") {
-        "high"
-    } else if confidence.contains("low") {
-        "low"
-    } else {
-        "medium"
-    };
+    #[tokio::test]
+    async fn unit_timed_records_elapsed_time_for_stubbed_call() {
+        let (result, ms) = timed(async {
+            tokio::time::sleep(std::time::Duration::from_millis(25)).await;
+            Ok::<_, String>("Stay put.".to_string())
+        })
+        .await;
+        assert_eq!(result.unwrap(), "Stay put.");
+        assert!(ms >= 25, "elapsed {}ms", ms);
+
+        let timings = AgentTimings::default();
+        let shared = timings.clone();
+        for (agent, ms, attempts) in [("rationalist", 1200, 1), ("advocate", 300, 1), ("rationalist", 2800, 3)] {
+            shared.record(AgentTiming { agent: agent.to_string(), round: 1, exchange: 1, ms, attempts });
+        }
+        let latency = summarize_agent_latency(&timings.snapshot());
+        assert_eq!(latency["rationalist"]["calls"], 2);
+        assert_eq!(latency["rationalist"]["attempts"], 4);
+        assert_eq!(latency["rationalist"]["avg_ms"], 2000);
+        assert_eq!(latency["rationalist"]["max_ms"], 2800);
+        assert_eq!(latency["advocate"]["total_ms"], 300);
+        assert_eq!(summarize_agent_latency(&[]), json!({}));
+    }
 
-    Some(json!({
-        "choice": choice,
-        "confidence": conf,
-        "reasoning": reasoning,
-        "tradeoffs": if tradeoffs.is_empty() { None } else { Some(tradeoffs) },
-        "next_steps": if next_steps.is_empty() { None } else { Some(next_steps) },
-    }))
-}
+    #[tokio::test]
+    async fn unit_retry_failed_agents_recovers_agent_that_failed_once() {
+        // The contrarian failed in the main pass; the retry pass succeeds on its
+        // first attempt. The visionary keeps failing and falls through.
+        let calls = std::cell::RefCell::new(Vec::new());
+        let failed = vec![
+            ("contrarian".to_string(), "first pass: 503".to_string()),
+            ("visionary".to_string(), "first pass: timeout".to_string()),
+        ];
+        let (recovered, still_failed) = retry_failed_agents(failed, 2, |key| {
+            calls.borrow_mut().push(key.clone());
+            async move {
+                if key == "contrarian" {
+                    Ok("Let me push back on that.".to_string())
+                } else {
+                    Err(format!("{}: still timing out", key))
+                }
+            }
+        })
+        .await;
 
-/// Extract a value after a bold label like **Choice**: value
-fn extract_bold_value(text: &str, label: &str) -> Option<String> {
-    let pattern = format!("**{}**:", label);
-    if let Some(pos) = text.find(&pattern) {
-        let after = &text[pos + pattern.len()..];
-        let end = after.find('\n').unwrap_or(after.len());
-        let value = after[..end].trim().to_string();
-        if value.is_empty() { None } else { Some(value) }
-    } else {
-        None
+        assert_eq!(recovered, vec![("contrarian".to_string(), "Let me push back on that.".to_string())]);
+        assert_eq!(still_failed, vec![("visionary".to_string(), "visionary: still timing out".to_string())]);
+        assert_eq!(*calls.borrow(), vec!["contrarian", "visionary", "visionary"]);
+
+        let (recovered, still_failed) =
+            retry_failed_agents(Vec::new(), 2, |_| async { Ok(String::new()) }).await;
+        assert!(recovered.is_empty() && still_failed.is_empty());
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn unit_pre_read_ranking_averages_scores_and_lists_unscored_last() {
+        let summary = json!({
+            "options": [{"label": "Stay"}, {"label": "Hybrid"}, {"label": "Leave"}],
+            "pros_cons": [
+                {"option": "Stay", "alignment_score": 6},
+                {"option": "Leave", "alignment_score": 8},
+                {"option": "Stay", "alignment_score": 7},
+                {"option": "Freelance", "alignment_score": 9},
+                {"option": "Contract", "alignment_score": 8.5}
+            ]
+        });
+
+        let ranking = pre_read_ranking(&summary);
+        assert_eq!(
+            ranking,
+            vec![
+                ("Freelance".to_string(), Some(9.0)),
+                ("Contract".to_string(), Some(8.5)),
+                ("Leave".to_string(), Some(8.0)),
+                ("Stay".to_string(), Some(6.5)),
+                ("Hybrid".to_string(), None),
+            ]
+        );
+
+        let section = format_pre_read_ranking(&summary).unwrap();
+        assert!(section.starts_with("## Committee Pre-Read Ranking\n1. **Freelance** (alignment: 9.0/10)"));
+        assert!(section.contains("2. **Contract** (alignment: 8.5/10)"));
+        assert!(section.contains("4. **Stay** (alignment: 6.5/10)"));
+        assert!(section.ends_with("5. **Hybrid** (not yet scored)"));
+        assert!(format_pre_read_ranking(&json!({})).is_none());
+    }
 
     #[test]
     fn unit_extract_section_reads_content_until_next_heading() {
@@ -1241,6 +3482,74 @@ mod tests {
         assert!(!section.contains("Key Disagreements"));
     }
 
+    #[test]
+    fn unit_extract_section_keeps_subheadings_and_ignores_deeper_heading_matches() {
+        let content = r#"
+### Recommendation notes
+Not this one.
+
+## Action Plan
+### This week
+1. Call the recruiter
+2) Ask for the offer in writing
+### This month
+3. Give notice
+
+## Risks
+- Burnout
+"#;
+
+        let section = extract_section(content, "Action Plan");
+        assert!(section.contains("Call the recruiter"));
+        assert!(section.contains("Give notice"));
+        assert!(!section.contains("Burnout"));
+        assert_eq!(
+            split_to_points(&section),
+            vec!["Call the recruiter", "Ask for the offer in writing", "Give notice"]
+        );
+        assert!(extract_section(content, "Recommendation").is_empty());
+    }
+
+    #[test]
+    fn unit_debate_summary_update_dedups_repeated_points() {
+        let moderator = "## Where the Committee Agreed\n- Rent is too high\n- Commute matters\n- rent is too high \n\n\
+                         ## Key Disagreements\n1. Timing\n2. TIMING\n3. Cost of schools\n\n\
+                         ## Biases & Blind Spots Identified\n- Status quo bias\n* Status quo bias\n";
+        let update = debate_summary_update(&[], moderator, &[], None, json!({}));
+        let summary = &update["debate_summary"];
+        assert_eq!(summary["consensus_points"], json!(["Rent is too high", "Commute matters"]));
+        assert_eq!(summary["key_disagreements"], json!(["Timing", "Cost of schools"]));
+        assert_eq!(summary["biases_identified"], json!(["Status quo bias"]));
+    }
+
+    #[test]
+    fn unit_truncate_conversation_keeps_most_recent_messages_within_limit() {
+        let lines: Vec<String> = vec![
+            "User: Should I take the job in Denver?".to_string(),
+            "AI: What matters most to you about the move?".to_string(),
+            "User: Being near my sister.".to_string(),
+        ];
+        let full = lines.join("\n\n");
+        assert_eq!(truncate_conversation(&lines, 0), full);
+        assert_eq!(truncate_conversation(&lines, full.len()), full);
+
+        let limit = lines[1].len() + 2 + lines[2].len();
+        assert_eq!(
+            truncate_conversation(&lines, limit),
+            format!("[earlier conversation omitted]\n\n{}\n\n{}", lines[1], lines[2])
+        );
+        assert_eq!(
+            truncate_conversation(&lines, 7),
+            "[earlier conversation omitted]\n\nsister."
+        );
+    }
+
+    #[test]
+    fn unit_split_to_points_strips_numbered_list_prefixes() {
+        let points = split_to_points("1. First step\n2) Second step\n10. Tenth step\n2024 was a good year");
+        assert_eq!(points, vec!["First step", "Second step", "Tenth step", "2024 was a good year"]);
+    }
+
     #[test]
     fn unit_split_to_points_strips_bullets_and_empty_lines() {
         let points = split_to_points(
@@ -1273,7 +3582,7 @@ Third
 
         let rec_section = extract_section(full_text, "Recommendation");
         let recommendation =
-            parse_moderator_recommendation(&rec_section, full_text).expect("recommendation should parse");
+            parse_moderator_recommendation(&rec_section, full_text, &serde_json::Map::new()).expect("recommendation should parse");
 
         assert_eq!(recommendation["choice"], "Option B");
         assert_eq!(recommendation["confidence"], "high");
@@ -1286,12 +3595,317 @@ Third
             recommendation["tradeoffs"],
             "- Predictability\n- Familiar team"
         );
+        assert!(recommendation["dissent"].is_null());
+    }
+
+    #[test]
+    fn unit_parse_moderator_recommendation_captures_minority_view() {
+        let full_text = r#"
+## Key Disagreements
+The Contrarian and Pragmatist resisted the move.
+
+## Recommendation
+**Choice**: Move to Lisbon
+**Confidence**: Medium
+**Reasoning**: Three of five members found the quality-of-life case decisive.
+
+## Minority View
+The Contrarian and Pragmatist argued the visa timeline makes a move this year premature.
+
+## What You're Giving Up
+- Proximity to family
+"#;
+        let rec_section = extract_section(full_text, "Recommendation");
+        let recommendation = parse_moderator_recommendation(&rec_section, full_text, &serde_json::Map::new()).unwrap();
+        assert_eq!(
+            recommendation["dissent"],
+            "The Contrarian and Pragmatist argued the visa timeline makes a move this year premature."
+        );
+        assert_eq!(recommendation["tradeoffs"], "- Proximity to family");
+
+        let unanimous = full_text.replace(
+            "The Contrarian and Pragmatist argued the visa timeline makes a move this year premature.",
+            "None.",
+        );
+        let rec_section = extract_section(&unanimous, "Recommendation");
+        let recommendation = parse_moderator_recommendation(&rec_section, &unanimous, &serde_json::Map::new()).unwrap();
+        assert!(recommendation["dissent"].is_null());
+    }
+
+    #[test]
+    fn integration_moderator_synthesis_returns_saved_text() {
+        let db = Database::new(":memory:").expect("in-memory database should initialize");
+        let conv = db.create_conversation_with_type("Sell the house?", "decision").unwrap();
+        let decision = db.create_decision(&conv.id, "Sell the house?").unwrap();
+        db.save_debate_round(&decision.id, 1, 1, "rationalist", "Sell while prices are high.").unwrap();
+        assert_eq!(moderator_synthesis(&db, &decision.id).unwrap(), None);
+
+        let synthesis = "## Where the Committee Agreed\n- Prices are near a peak\n\n## Recommendation\n**Choice**: Sell\n";
+        db.save_debate_round(&decision.id, 99, 1, "moderator", synthesis).unwrap();
+        assert_eq!(moderator_synthesis(&db, &decision.id).unwrap().as_deref(), Some(synthesis));
+    }
+
+    #[test]
+    fn integration_rebuild_summary_replaces_stale_debate_fields() {
+        let db = Database::new(":memory:").expect("in-memory database should initialize");
+        let conv = db.create_conversation_with_type("Leave the startup?", "decision").unwrap();
+        let decision = db.create_decision(&conv.id, "Leave the startup?").unwrap();
+        let stale = json!({
+            "options": [{"label": "Stay"}, {"label": "Leave"}],
+            "recommendation": {"choice": "Stay", "confidence": "high", "reasoning": "Old take"},
+            "debate_summary": {
+                "consensus_points": ["Stale point"],
+                "agent_latency": {"rationalist": {"calls": 2}},
+            },
+        });
+        db.update_decision_summary(&decision.id, &stale.to_string()).unwrap();
+        assert!(rebuild_summary_from_debate(&db, &decision.id).is_err(), "no moderator round yet");
+
+        db.save_debate_round(&decision.id, 1, 1, "rationalist", "Leave, the equity is worthless.").unwrap();
+        db.save_debate_round(&decision.id, 1, 1, "advocate", "Leave while you still care.").unwrap();
+        db.save_debate_round(
+            &decision.id, 99, 1, "moderator",
+            "## Where the Committee Agreed\n- The equity is unlikely to pay out\n\n## Recommendation\n**Choice**: Leave\n**Confidence**: High\n**Reasoning**: Everyone backed leaving.\n",
+        ).unwrap();
+
+        let (rebuilt, changes) = rebuild_summary_from_debate(&db, &decision.id).expect("summary should rebuild");
+        let summary: Value = serde_json::from_str(&rebuilt).unwrap();
+        assert_eq!(summary["debate_summary"]["consensus_points"], json!(["The equity is unlikely to pay out"]));
+        assert_eq!(summary["debate_summary"]["vote_tally"]["Leave"], 2);
+        assert_eq!(summary["debate_summary"]["agent_latency"]["rationalist"]["calls"], 2);
+        assert_eq!(summary["recommendation"]["choice"], "Leave");
+        assert_eq!(summary["options"].as_array().unwrap().len(), 2);
+        assert!(changes.recommendation_changed && changes.debate_summary_changed);
+
+        let stored = db.get_decision(&decision.id).unwrap().unwrap().summary_json.unwrap();
+        assert_eq!(stored, rebuilt);
+    }
+
+    #[test]
+    fn unit_standalone_moderator_prompt_special_cases_a_single_debater() {
+        let debaters: Vec<AgentInfo> = agents::builtin_agents().into_iter().filter(|a| a.role == "debater").collect();
+
+        let committee = standalone_moderator_prompt("brief", "transcript", &debaters[..2]);
+        assert!(committee.contains("The following models participated: The Rationalist and The Advocate"));
+
+        let solo = standalone_moderator_prompt("brief", "transcript", &debaters[..1]);
+        assert!(solo.contains("Only The Rationalist took part"));
+        assert!(!solo.contains("The following models participated"));
+    }
+
+    #[test]
+    fn unit_single_debater_is_rejected_below_the_minimum() {
+        let registry: Vec<AgentInfo> = agents::builtin_agents().into_iter().filter(|a| a.role == "debater").collect();
+        let one = select_debaters(registry.clone(), Some(&["advocate".to_string()]));
+        assert_eq!(one.len(), 1);
+        assert_eq!(
+            check_debater_count(one.len(), 2).unwrap_err(),
+            "A debate needs at least 2 debaters, but only 1 was selected"
+        );
+        assert!(check_debater_count(one.len(), 1).is_ok());
+        assert!(check_debater_count(0, 0).is_err());
+
+        let all = select_debaters(registry.clone(), None);
+        assert_eq!(all.len(), registry.len());
+        assert!(check_debater_count(all.len(), 2).is_ok());
+    }
+
+    #[test]
+    fn unit_close_vote_split_downgrades_moderator_confidence() {
+        let full_text = "## Recommendation\n**Choice**: Leave\n**Confidence**: High\n**Reasoning**: Growth wins.\n";
+        let rec_section = extract_section(full_text, "Recommendation");
+        let tally = |leave: u64, stay: u64| {
+            let mut tally = serde_json::Map::new();
+            tally.insert("Leave".to_string(), json!(leave));
+            tally.insert("Stay".to_string(), json!(stay));
+            tally.insert("undecided".to_string(), json!(0));
+            tally
+        };
+
+        let split = parse_moderator_recommendation(&rec_section, full_text, &tally(3, 2)).unwrap();
+        assert_eq!(split["confidence"], "medium");
+        assert_eq!(
+            split["reasoning"],
+            "Growth wins. (Confidence lowered from high to medium: the committee split 3-2.)"
+        );
+
+        let decisive = parse_moderator_recommendation(&rec_section, full_text, &tally(4, 1)).unwrap();
+        assert_eq!(decisive["confidence"], "high");
+        assert_eq!(decisive["reasoning"], "Growth wins.");
+
+        let low = full_text.replace("High", "Low");
+        let rec_section = extract_section(&low, "Recommendation");
+        let still_low = parse_moderator_recommendation(&rec_section, &low, &tally(3, 2)).unwrap();
+        assert_eq!(still_low["confidence"], "low");
+        assert_eq!(still_low["reasoning"], "Growth wins.");
+
+        assert_eq!(close_vote_split(&tally(0, 0)), None);
     }
 
     #[test]
     fn unit_parse_moderator_recommendation_returns_none_without_recommendation_fields() {
         let no_recommendation = "## Where the Committee Agreed\n- Point A";
-        assert!(parse_moderator_recommendation("", no_recommendation).is_none());
+        assert!(parse_moderator_recommendation("", no_recommendation, &serde_json::Map::new()).is_none());
+    }
+
+    #[test]
+    fn unit_brief_ready_payload_reports_character_length() {
+        let payload = brief_ready_payload("dec-1", "## Décision\nStay?");
+        assert_eq!(payload["decision_id"], "dec-1");
+        assert_eq!(payload["brief_length"], 17);
+    }
+
+    #[test]
+    fn unit_resolve_moderator_model_prefers_override_then_default_then_model() {
+        let mut agent_models = HashMap::new();
+        assert_eq!(resolve_moderator_model(&agent_models, "", "cheap/model"), "cheap/model");
+        assert_eq!(
+            resolve_moderator_model(&agent_models, "strong/model", "cheap/model"),
+            "strong/model"
+        );
+
+        agent_models.insert("moderator".to_string(), "pinned/model".to_string());
+        assert_eq!(
+            resolve_moderator_model(&agent_models, "strong/model", "cheap/model"),
+            "pinned/model"
+        );
+
+        agent_models.insert("moderator".to_string(), "  ".to_string());
+        assert_eq!(
+            resolve_moderator_model(&agent_models, "strong/model", "cheap/model"),
+            "strong/model"
+        );
+    }
+
+    #[test]
+    fn integration_debate_readiness_names_what_is_missing() {
+        let db = Database::new(":memory:").expect("in-memory database should initialize");
+        let conv = db
+            .create_conversation_with_type("New car?", "decision")
+            .expect("conversation should be created");
+        let decision = db
+            .create_decision(&conv.id, "New car?")
+            .expect("decision should be created");
+        assert_eq!(
+            debate_readiness(&decision).missing,
+            vec!["add at least one option", "add at least one key variable"]
+        );
+
+        let options_only = decisions::merge_summary(None, &json!({
+            "options": [{"label": "Buy", "description": "Get the new car"}]
+        }));
+        db.update_decision_summary(&decision.id, &options_only).expect("summary should update");
+        let decision = db.get_decision(&decision.id).unwrap().unwrap();
+        let readiness = debate_readiness(&decision);
+        assert!(!readiness.ready);
+        assert_eq!(readiness.missing, vec!["add at least one key variable"]);
+
+        let variables_only = decisions::merge_summary(None, &json!({
+            "variables": [{"label": "Budget", "value": "$20k", "impact": "high"}]
+        }));
+        db.update_decision_summary(&decision.id, &variables_only).expect("summary should update");
+        let decision = db.get_decision(&decision.id).unwrap().unwrap();
+        assert_eq!(debate_readiness(&decision).missing, vec!["add at least one option"]);
+        assert_eq!(
+            check_debate_preconditions(&decision).unwrap_err(),
+            "Decision isn't ready for a debate yet: add at least one option."
+        );
+    }
+
+    #[test]
+    fn unit_resolve_debater_model_prefers_override_then_debate_default_then_model() {
+        let mut agent_models = HashMap::new();
+        assert_eq!(resolve_debater_model(&agent_models, "skeptic", "", "chat/model"), "chat/model");
+        assert_eq!(
+            resolve_debater_model(&agent_models, "skeptic", "debate/model", "chat/model"),
+            "debate/model"
+        );
+
+        agent_models.insert("skeptic".to_string(), "pinned/model".to_string());
+        assert_eq!(
+            resolve_debater_model(&agent_models, "skeptic", "debate/model", "chat/model"),
+            "pinned/model"
+        );
+        assert_eq!(
+            resolve_debater_model(&agent_models, "advocate", "debate/model", "chat/model"),
+            "debate/model"
+        );
+
+        agent_models.insert("skeptic".to_string(), "  ".to_string());
+        assert_eq!(
+            resolve_debater_model(&agent_models, "skeptic", " ", "chat/model"),
+            "chat/model"
+        );
+    }
+
+    #[test]
+    fn unit_debate_settings_clamp_temperature_and_resolve_models() {
+        let mut config = config::AppConfig {
+            debate_temperature: 1.2,
+            debate_default_model: "debate/model".to_string(),
+            default_moderator_model: "moderator/model".to_string(),
+            ..Default::default()
+        };
+        config.agent_models.insert("contrarian".to_string(), "pinned/model".to_string());
+        let settings = DebateSettings::from_config(&config, "d1", DebateShape::FULL);
+        assert_eq!(settings.temperature, 1.2);
+        assert_eq!(settings.model_for("contrarian"), "pinned/model");
+        assert_eq!(settings.model_for("rationalist"), "debate/model");
+        assert_eq!(settings.model_for("moderator"), "moderator/model");
+
+        assert_eq!(debate_temperature(5.0), config::MAX_TEMPERATURE);
+        assert_eq!(debate_temperature(-1.0), 0.0);
+        assert_eq!(debate_temperature(f32::NAN), config::DEFAULT_DEBATE_TEMPERATURE);
+    }
+
+    #[test]
+    fn unit_tally_votes_maps_closing_statements_to_options() {
+        let options = vec![
+            "Accept the Berlin offer".to_string(),
+            "Stay at current job".to_string(),
+        ];
+        let statements = [
+            "My final position: accept the Berlin offer. The growth outweighs the move.",
+            "I still say stay at current job over accept the berlin offer.",
+            "Berlin wins for me, and the offer is strong enough to accept.",
+            "Honestly I can't call this one without more data.",
+        ];
+        let tally = tally_votes(&statements, &options);
+
+        assert_eq!(tally["Accept the Berlin offer"], 2);
+        assert_eq!(tally["Stay at current job"], 1);
+        assert_eq!(tally["undecided"], 1);
+    }
+
+    #[test]
+    fn unit_tally_votes_ignores_negated_mentions_and_prefers_the_vote_line() {
+        let options = vec![
+            "Accept the Berlin offer".to_string(),
+            "Stay at current job".to_string(),
+        ];
+        let statements = [
+            "I would not accept the Berlin offer; stay at current job is the safer path.",
+            "Everyone keeps saying stay at current job. My vote: accept the Berlin offer.",
+            "Rather than accept the Berlin offer, I'd stay at current job for another year.",
+            "I won\u{2019}t stay at current job, so accept the Berlin offer.",
+            "I can't back either path without more data.",
+        ];
+        let tally = tally_votes(&statements, &options);
+
+        assert_eq!(tally["Accept the Berlin offer"], 2);
+        assert_eq!(tally["Stay at current job"], 2);
+        assert_eq!(tally["undecided"], 1);
+    }
+
+    #[test]
+    fn unit_tally_votes_without_options_counts_everyone_undecided() {
+        let tally = tally_votes(&["Go with A", "Go with B"], &[]);
+        assert_eq!(tally.len(), 1);
+        assert_eq!(tally["undecided"], 2);
+
+        let labels = summary_option_labels(Some(r#"{"options":[{"label":"A"},{"label":"B"}]}"#));
+        assert_eq!(labels, vec!["A", "B"]);
+        assert!(summary_option_labels(None).is_empty());
     }
 
     #[test]
@@ -1302,7 +3916,7 @@ Third
 - **Key argument**: Better upside over 5 years.
 1. **Concern**: Burnout risk is still real.
 "#;
-        let cleaned = normalize_spoken_debate_output(raw);
+        let cleaned = normalize_spoken_debate_output(raw, None);
         assert!(!cleaned.contains("##"));
         assert!(!cleaned.contains("**"));
         assert!(!cleaned.contains("- "));
@@ -1310,4 +3924,251 @@ Third
         assert!(cleaned.contains("Better upside over 5 years."));
         assert!(cleaned.contains("Burnout risk is still real."));
     }
+
+    #[test]
+    fn integration_debate_status_tracks_phase_across_decision_states() {
+        let db = Database::new(":memory:").expect("in-memory database should initialize");
+        let conv = db.create_conversation_with_type("Move?", "decision").unwrap();
+        let decision = db.create_decision(&conv.id, "Move?").unwrap();
+        let status_of = |running: bool| {
+            let decision = db.get_decision(&decision.id).unwrap().unwrap();
+            let rounds = db.get_debate_rounds(&decision.id).unwrap();
+            debate_status(&decision, &rounds, running, false)
+        };
+
+        let status = status_of(false);
+        assert_eq!(status.phase, "not_started");
+        assert_eq!(status.latest_round, 0);
+        assert!(!status.running && !status.has_moderator);
+
+        db.update_debate_started(&decision.id).unwrap();
+        assert_eq!(status_of(true).phase, "starting");
+        db.save_debate_round(&decision.id, 1, 1, "rationalist", "Go.").unwrap();
+        db.save_debate_round(&decision.id, 2, 1, "contrarian", "Stay.").unwrap();
+        let status = status_of(true);
+        assert_eq!(status.phase, "exchange");
+        assert_eq!(status.latest_round, 2);
+
+        let status = status_of(false);
+        assert_eq!(status.phase, "interrupted", "started but no flag and no synthesis");
+        assert!(!status.running);
+
+        db.save_debate_round(&decision.id, 3, 1, "rationalist", "Final: go.").unwrap();
+        db.save_debate_round(&decision.id, 99, 1, "moderator", "Synthesis").unwrap();
+        assert_eq!(status_of(true).phase, "moderator");
+        db.update_debate_completed(&decision.id).unwrap();
+        let status = status_of(false);
+        assert_eq!(status.phase, "complete");
+        assert_eq!(status.latest_round, 3);
+        assert!(status.has_moderator);
+    }
+
+    #[test]
+    fn unit_shuffled_speaking_order_is_deterministic_and_complete() {
+        let debaters: Vec<AgentInfo> = agents::builtin_agents()
+            .into_iter()
+            .filter(|a| a.role == "debater")
+            .collect();
+        let keys = |order: Vec<&AgentInfo>| order.iter().map(|a| a.key.clone()).collect::<Vec<_>>();
+        let seed = speaking_order_seed("decision-123");
+        assert_eq!(seed, speaking_order_seed("decision-123"));
+
+        let first = keys(speaking_order(&debaters, 2, 1, Some(seed)));
+        assert_eq!(first, keys(speaking_order(&debaters, 2, 1, Some(seed))));
+        let mut sorted = first.clone();
+        sorted.sort();
+        let mut expected: Vec<String> = debaters.iter().map(|a| a.key.clone()).collect();
+        expected.sort();
+        assert_eq!(sorted, expected, "every debater speaks exactly once");
+
+        let rounds: Vec<Vec<String>> = (1..=4)
+            .map(|exchange| keys(speaking_order(&debaters, 2, exchange, Some(seed))))
+            .collect();
+        assert!(rounds.windows(2).any(|w| w[0] != w[1]), "order varies between exchanges");
+
+        let registry_order: Vec<String> = debaters.iter().map(|a| a.key.clone()).collect();
+        assert_eq!(keys(speaking_order(&debaters, 1, 1, Some(seed))), registry_order);
+        assert_eq!(keys(speaking_order(&debaters, 1, 1, None)), registry_order);
+        let rotated = keys(speaking_order(&debaters, 2, 1, None));
+        assert_eq!(rotated[0], registry_order[1]);
+    }
+
+    #[test]
+    fn integration_export_debate_json_lists_rounds_with_labels() {
+        let db = Database::new(":memory:").expect("in-memory database should initialize");
+        let conv = db.create_conversation_with_type("Move?", "decision").unwrap();
+        let decision = db.create_decision(&conv.id, "Move?").unwrap();
+        db.update_debate_brief(&decision.id, "Should I move to Lisbon?").unwrap();
+        db.save_debate_round(&decision.id, 1, 1, "rationalist", "Run the numbers.").unwrap();
+        db.save_debate_round(&decision.id, 1, 1, "contrarian", "Stay put.").unwrap();
+        db.save_debate_round(&decision.id, 99, 1, "moderator", "Synthesis").unwrap();
+        db.update_decision_summary(&decision.id, r#"{"debate_summary":{"recommendation":"Move"}}"#).unwrap();
+
+        let agents = agents::builtin_agents();
+        let exported = export_debate_json(&db, &agents, &decision.id).expect("export should succeed");
+
+        assert_eq!(exported["decision"]["title"], "Move?");
+        assert_eq!(exported["brief"], "Should I move to Lisbon?");
+        let rounds = exported["rounds"].as_array().unwrap();
+        assert_eq!(rounds.len(), 3);
+        let moderator = &rounds[2];
+        assert_eq!(moderator["round_number"], 99);
+        assert_eq!(moderator["agent"], "moderator");
+        assert_eq!(moderator["agent_label"], "Moderator");
+        assert_eq!(rounds[0]["agent_label"], "Rationalist");
+        assert_eq!(exported["debate_summary"]["recommendation"], "Move");
+        assert!(exported["audio_manifest"].is_null());
+
+        db.save_debate_audio(&decision.id, r#"{"segments":[]}"#, 1000, "/tmp/debates/move").unwrap();
+        let exported = export_debate_json(&db, &agents, &decision.id).unwrap();
+        assert!(exported["audio_manifest"]["segments"].is_array());
+        assert!(export_debate_json(&db, &agents, "missing").is_err());
+    }
+
+    #[test]
+    fn unit_normalize_spoken_debate_output_strips_filler_openers() {
+        assert_eq!(
+            normalize_spoken_debate_output("Look, the rent math doesn't work.", None),
+            "The rent math doesn't work."
+        );
+        assert_eq!(
+            normalize_spoken_debate_output("Honestly, I'd stay another year.", None),
+            "I'd stay another year."
+        );
+        assert_eq!(
+            normalize_spoken_debate_output("**Look,** honestly, you should move.", None),
+            "You should move."
+        );
+    }
+
+    #[test]
+    fn unit_normalize_spoken_debate_output_strips_speaker_label_echo() {
+        assert_eq!(
+            normalize_spoken_debate_output("The Rationalist: Go with B.", Some("Rationalist")),
+            "Go with B."
+        );
+        assert_eq!(
+            normalize_spoken_debate_output("**Rationalist:** Go with B.", Some("Rationalist")),
+            "Go with B."
+        );
+        // Another agent's label is quoted content, not an echo of the speaker.
+        assert_eq!(
+            normalize_spoken_debate_output("Contrarian: is wrong here.", Some("Rationalist")),
+            "Contrarian: is wrong here."
+        );
+        // Only the leading label goes.
+        assert_eq!(
+            normalize_spoken_debate_output("Stay put.\nRationalist: noted.", Some("Rationalist")),
+            "Stay put. Rationalist: noted."
+        );
+        assert_eq!(
+            normalize_spoken_debate_output("The Rationalist: Go with B.", None),
+            "The Rationalist: Go with B."
+        );
+    }
+
+    #[test]
+    fn unit_normalize_spoken_debate_output_keeps_mid_sentence_fillers() {
+        assert_eq!(
+            normalize_spoken_debate_output("If you look, honestly, the numbers favor Lisbon.", None),
+            "If you look, honestly, the numbers favor Lisbon."
+        );
+        assert_eq!(
+            normalize_spoken_debate_output("The offer is fine. Look, it's not great either.", None),
+            "The offer is fine. Look, it's not great either."
+        );
+        assert_eq!(strip_filler_openers("Look, "), "Look, ");
+        assert_eq!(strip_filler_openers("Lookout points matter."), "Lookout points matter.");
+    }
+
+    #[test]
+    fn integration_edit_round_content_normalizes_and_flags_stale_audio() {
+        let db = Database::new(":memory:").expect("in-memory database should initialize");
+        let conv = db.create_conversation_with_type("Move?", "decision").unwrap();
+        let decision = db.create_decision(&conv.id, "Move?").unwrap();
+        let round = db.save_debate_round(&decision.id, 1, 1, "advocate", "Original").unwrap();
+
+        let edit = edit_round_content(&db, &round.id, "## Opening\n- **Position**: Move to Lisbon.")
+            .expect("edit should apply");
+        assert!(!edit.audio_stale);
+        assert!(!edit.round.content.contains("**"));
+        assert!(!edit.round.content.contains("##"));
+        assert!(edit.round.content.contains("Move to Lisbon."));
+        let stored = db.get_debate_round(&round.id).unwrap().unwrap();
+        assert_eq!(stored.content, edit.round.content);
+
+        db.save_debate_audio(&decision.id, "{}", 1000, "/tmp/debates/move").unwrap();
+        let edit = edit_round_content(&db, &round.id, "Stay put.").unwrap();
+        assert!(edit.audio_stale);
+        assert_eq!(edit.round.content, "Stay put.");
+
+        assert!(edit_round_content(&db, &round.id, "   ").is_err());
+        assert!(edit_round_content(&db, "missing", "Text").is_err());
+    }
+
+    #[test]
+    fn integration_reset_debate_clears_rounds_audio_and_status() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::new(":memory:").expect("in-memory database should initialize");
+        let conv = db.create_conversation_with_type("Move?", "decision").unwrap();
+        let decision = db.create_decision(&conv.id, "Move?").unwrap();
+        db.save_debate_round(&decision.id, 1, 1, "advocate", "Go.").unwrap();
+        db.save_debate_round(&decision.id, 1, 2, "skeptic", "Stay.").unwrap();
+        let audio_dir = dir.path().join("debates").join(&decision.id);
+        std::fs::create_dir_all(&audio_dir).unwrap();
+        std::fs::write(audio_dir.join("000_advocate_r1.mp3"), b"mp3").unwrap();
+        db.save_debate_audio(&decision.id, "{}", 1000, &audio_dir.to_string_lossy()).unwrap();
+        db.update_decision_status(&decision.id, "decided").unwrap();
+
+        let report = reset_debate(&db, dir.path(), &decision.id).expect("reset should succeed");
+        assert_eq!(report.rounds_deleted, 2);
+        assert!(report.audio_record_deleted);
+        assert!(report.audio_dir_deleted);
+        assert!(db.get_debate_rounds(&decision.id).unwrap().is_empty());
+        assert!(db.get_debate_audio(&decision.id).unwrap().is_none());
+        assert!(!audio_dir.exists());
+        assert_eq!(db.get_decision(&decision.id).unwrap().unwrap().status, "analyzing");
+
+        let again = reset_debate(&db, dir.path(), &decision.id).unwrap();
+        assert_eq!(again.rounds_deleted, 0);
+        assert!(!again.audio_record_deleted && !again.audio_dir_deleted);
+        assert!(reset_debate(&db, dir.path(), "missing").is_err());
+    }
+
+    #[test]
+    fn integration_post_tts_mode_skips_live_segments_and_speaks_all_rounds() {
+        let mut cfg = config::AppConfig {
+            elevenlabs_api_key: "el-test".to_string(),
+            tts_mode: "post".to_string(),
+            ..Default::default()
+        };
+
+        let post = LiveTtsState::new(cfg.clone(), Vec::new(), PathBuf::from("/tmp"), 2);
+        assert!(!post.enabled, "post mode must not spawn per-segment TTS");
+        assert!(post.post_generate);
+        assert_eq!(post.segment_counter.load(Ordering::Relaxed), 2);
+        assert!(post.handles.lock().unwrap().is_empty());
+
+        cfg.tts_mode = "live".to_string();
+        let live = LiveTtsState::new(cfg.clone(), Vec::new(), PathBuf::from("/tmp"), 0);
+        assert!(live.enabled);
+        assert!(!live.post_generate);
+
+        cfg.tts_mode = "off".to_string();
+        let off = LiveTtsState::new(cfg, Vec::new(), PathBuf::from("/tmp"), 0);
+        assert!(!off.enabled && !off.post_generate);
+        assert_eq!(off.permits.available_permits(), 2);
+
+        let db = Database::new(":memory:").expect("in-memory database should initialize");
+        let conv = db.create_conversation_with_type("Move?", "decision").unwrap();
+        let decision = db.create_decision(&conv.id, "Move?").unwrap();
+        db.save_debate_round(&decision.id, 1, 1, "rationalist", "## Opening\n- **Position**: Stay.").unwrap();
+        db.save_debate_round(&decision.id, 99, 1, "moderator", "Synthesis").unwrap();
+
+        let rounds = spoken_rounds(db.get_debate_rounds(&decision.id).unwrap());
+        assert_eq!(rounds.len(), 2);
+        assert_eq!(rounds[0].agent, "rationalist");
+        assert!(!rounds[0].content.contains("**"));
+        assert_eq!(rounds[1].round_number, 99);
+    }
 }
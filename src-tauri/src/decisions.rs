@@ -1,8 +1,338 @@
+use crate::db::{Database, Decision, DECISION_STATUSES};
+use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Largest supporting document accepted for a decision, to keep briefs manageable.
+const MAX_CONTEXT_FILE_BYTES: usize = 100 * 1024;
+
+/// One side of a decision comparison: what was considered, recommended, chosen, and what happened.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecisionSnapshot {
+    pub decision_id: String,
+    pub title: String,
+    pub status: String,
+    pub options: Vec<String>,
+    pub recommended_choice: Option<String>,
+    pub confidence: Option<String>,
+    pub user_choice: Option<String>,
+    pub outcome: Option<String>,
+}
+
+/// A variable both decisions tracked, matched by label.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SharedVariable {
+    pub label: String,
+    pub value_a: String,
+    pub value_b: String,
+    pub differs: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComparisonResult {
+    pub a: DecisionSnapshot,
+    pub b: DecisionSnapshot,
+    pub shared_variables: Vec<SharedVariable>,
+    pub only_in_a: Vec<String>,
+    pub only_in_b: Vec<String>,
+}
+
+/// A variable whose value moved between two versions of a summary.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VariableChange {
+    pub label: String,
+    pub before: String,
+    pub after: String,
+}
+
+/// Pros and cons that appeared for one option since the previous summary.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProsConsAdded {
+    pub option: String,
+    pub pros: Vec<String>,
+    pub cons: Vec<String>,
+}
+
+/// What a summary update changed, so the UI can highlight it instead of
+/// re-rendering the whole panel.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SummaryDiff {
+    pub options_added: Vec<String>,
+    pub variables_added: Vec<String>,
+    pub variables_changed: Vec<VariableChange>,
+    pub pros_cons_added: Vec<ProsConsAdded>,
+    pub recommendation_changed: bool,
+    pub debate_summary_changed: bool,
+}
+
+/// Compare two summaries (as stored JSON). A missing or unparseable `before`
+/// counts as empty, so everything in `after` shows up as added.
+pub fn diff_summaries(before_json: Option<&str>, after_json: &str) -> SummaryDiff {
+    let parse = |s: &str| serde_json::from_str::<Value>(s).unwrap_or_else(|_| json!({}));
+    let before = before_json.map(parse).unwrap_or_else(|| json!({}));
+    let after = parse(after_json);
+
+    let items = |summary: &Value, field: &str| summary.get(field).and_then(|v| v.as_array()).cloned().unwrap_or_default();
+    let find = |list: &[Value], key: &str, name: &str| list.iter().find(|v| v[key].as_str() == Some(name)).cloned();
+    let strings = |v: &Value| -> Vec<String> {
+        v.as_array()
+            .map(|arr| arr.iter().filter_map(|s| s.as_str()).map(String::from).collect())
+            .unwrap_or_default()
+    };
+
+    let mut diff = SummaryDiff::default();
+
+    let before_options = items(&before, "options");
+    for option in items(&after, "options") {
+        if let Some(label) = option["label"].as_str() {
+            if find(&before_options, "label", label).is_none() {
+                diff.options_added.push(label.to_string());
+            }
+        }
+    }
+
+    let before_vars = items(&before, "variables");
+    for var in items(&after, "variables") {
+        let Some(label) = var["label"].as_str() else { continue };
+        let after_value = var["value"].as_str().unwrap_or("").to_string();
+        match find(&before_vars, "label", label) {
+            None => diff.variables_added.push(label.to_string()),
+            Some(old) => {
+                let before_value = old["value"].as_str().unwrap_or("").to_string();
+                if before_value != after_value {
+                    diff.variables_changed.push(VariableChange {
+                        label: label.to_string(),
+                        before: before_value,
+                        after: after_value,
+                    });
+                }
+            }
+        }
+    }
+
+    let before_pc = items(&before, "pros_cons");
+    for pc in items(&after, "pros_cons") {
+        let Some(option) = pc["option"].as_str() else { continue };
+        let old = find(&before_pc, "option", option).unwrap_or_else(|| json!({}));
+        let (old_pros, old_cons) = (strings(&old["pros"]), strings(&old["cons"]));
+        let pros: Vec<String> = strings(&pc["pros"]).into_iter().filter(|p| !old_pros.contains(p)).collect();
+        let cons: Vec<String> = strings(&pc["cons"]).into_iter().filter(|c| !old_cons.contains(c)).collect();
+        if !pros.is_empty() || !cons.is_empty() {
+            diff.pros_cons_added.push(ProsConsAdded { option: option.to_string(), pros, cons });
+        }
+    }
+
+    diff.recommendation_changed = before.get("recommendation") != after.get("recommendation");
+    diff.debate_summary_changed = before.get("debate_summary") != after.get("debate_summary");
+    diff
+}
+
+/// `merge_summary` plus the diff between the stored summary and the merged result.
+pub fn merge_summary_with_diff(existing_json: Option<&str>, update: &Value) -> (String, SummaryDiff) {
+    let merged = merge_summary(existing_json, update);
+    let diff = diff_summaries(existing_json, &merged);
+    (merged, diff)
+}
+
+/// A scalar as a string; arrays of scalars are joined with ", ".
+fn coerce_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        Value::Array(items) => {
+            let parts: Option<Vec<String>> = items
+                .iter()
+                .map(|item| match item {
+                    Value::Array(_) | Value::Object(_) => None,
+                    other => coerce_string(other),
+                })
+                .collect();
+            parts.map(|p| p.join(", "))
+        }
+        _ => None,
+    }
+}
+
+/// An integer from a number or a numeric string like "8", "8.0", or "8/10".
+fn coerce_integer(value: &Value) -> Option<i64> {
+    match value {
+        Value::Number(n) => n.as_i64().or_else(|| n.as_f64().map(|f| f.round() as i64)),
+        Value::String(s) => {
+            let s = s.trim();
+            let head = s.split('/').next().unwrap_or(s).trim();
+            head.parse::<i64>()
+                .ok()
+                .or_else(|| head.parse::<f64>().ok().filter(|f| f.is_finite()).map(|f| f.round() as i64))
+        }
+        _ => None,
+    }
+}
+
+/// Coerce `obj[field]` to a string in place, dropping it (with a note) if impossible.
+fn coerce_string_field(obj: &mut serde_json::Map<String, Value>, field: &str, path: &str, problems: &mut Vec<String>) {
+    let Some(value) = obj.get(field) else { return };
+    if value.is_string() {
+        return;
+    }
+    match coerce_string(value) {
+        Some(s) => {
+            obj.insert(field.to_string(), Value::String(s));
+        }
+        None => {
+            problems.push(format!("{}.{}: expected a string, got {}", path, field, value));
+            obj.remove(field);
+        }
+    }
+}
+
+/// Coerce `obj[field]` to a list of strings; a lone string becomes a one-item list.
+fn coerce_string_list_field(obj: &mut serde_json::Map<String, Value>, field: &str, path: &str, problems: &mut Vec<String>) {
+    let Some(value) = obj.get(field) else { return };
+    let list: Vec<Value> = match value {
+        Value::Array(items) => items
+            .iter()
+            .filter_map(|item| {
+                let coerced = coerce_string(item);
+                if coerced.is_none() {
+                    problems.push(format!("{}.{}: dropped non-text item {}", path, field, item));
+                }
+                coerced.map(Value::String)
+            })
+            .collect(),
+        other => match coerce_string(other) {
+            Some(s) => vec![Value::String(s)],
+            None => {
+                problems.push(format!("{}.{}: expected a list of strings, got {}", path, field, other));
+                obj.remove(field);
+                return;
+            }
+        },
+    };
+    obj.insert(field.to_string(), Value::Array(list));
+}
+
+/// Lower-case `obj[field]` and drop it if it isn't one of `allowed`.
+fn coerce_enum_field(obj: &mut serde_json::Map<String, Value>, field: &str, allowed: &[&str], path: &str, problems: &mut Vec<String>) {
+    let Some(value) = obj.get(field) else { return };
+    match value.as_str().map(|s| s.trim().to_lowercase()) {
+        Some(s) if allowed.contains(&s.as_str()) => {
+            obj.insert(field.to_string(), Value::String(s));
+        }
+        _ => {
+            problems.push(format!("{}.{}: expected one of {}, got {}", path, field, allowed.join("/"), value));
+            obj.remove(field);
+        }
+    }
+}
+
+/// Coerce an array of objects in place. Bare strings become `{key: string}`, a
+/// single object becomes a one-item array, and items without `key` are dropped.
+fn coerce_object_list(
+    input: &mut serde_json::Map<String, Value>,
+    field: &str,
+    key: &str,
+    problems: &mut Vec<String>,
+    mut fix_item: impl FnMut(&mut serde_json::Map<String, Value>, &str, &mut Vec<String>),
+) {
+    let Some(value) = input.remove(field) else { return };
+    let items = match value {
+        Value::Array(items) => items,
+        Value::Object(_) | Value::String(_) => vec![value],
+        other => {
+            problems.push(format!("{}: expected a list, got {}", field, other));
+            return;
+        }
+    };
+    let mut fixed = Vec::with_capacity(items.len());
+    for (i, item) in items.into_iter().enumerate() {
+        let path = format!("{}[{}]", field, i);
+        let mut obj = match item {
+            Value::Object(obj) => obj,
+            Value::String(s) => {
+                let mut obj = serde_json::Map::new();
+                obj.insert(key.to_string(), Value::String(s));
+                obj
+            }
+            other => {
+                problems.push(format!("{}: expected an object, got {}", path, other));
+                continue;
+            }
+        };
+        coerce_string_field(&mut obj, key, &path, problems);
+        let label = obj.get(key).and_then(|k| k.as_str()).map(str::trim).unwrap_or("");
+        if label.is_empty() {
+            problems.push(format!("{}: missing {}", path, key));
+            continue;
+        }
+        fix_item(&mut obj, &path, problems);
+        fixed.push(Value::Object(obj));
+    }
+    input.insert(field.to_string(), Value::Array(fixed));
+}
+
+/// Clean up `update_decision_summary` input before merging: coerce the type
+/// mismatches models commonly make (stringified integers, lists where text was
+/// expected, single items where a list was expected) and drop what can't be
+/// fixed. Returns the cleaned input and a note for everything changed or dropped.
+pub fn coerce_summary_input(input: &Value) -> (Value, Vec<String>) {
+    let mut problems = Vec::new();
+    let Some(obj) = input.as_object() else {
+        problems.push(format!("summary input must be an object, got {}", input));
+        return (json!({}), problems);
+    };
+    let mut obj = obj.clone();
+
+    coerce_string_field(&mut obj, "title", "summary", &mut problems);
+    coerce_enum_field(&mut obj, "status", &["exploring", "analyzing", "recommended"], "summary", &mut problems);
+
+    coerce_object_list(&mut obj, "options", "label", &mut problems, |item, path, problems| {
+        coerce_string_field(item, "description", path, problems);
+    });
+    coerce_object_list(&mut obj, "variables", "label", &mut problems, |item, path, problems| {
+        coerce_string_field(item, "value", path, problems);
+        coerce_enum_field(item, "impact", &["high", "medium", "low"], path, problems);
+    });
+    coerce_object_list(&mut obj, "pros_cons", "option", &mut problems, |item, path, problems| {
+        coerce_string_list_field(item, "pros", path, problems);
+        coerce_string_list_field(item, "cons", path, problems);
+        coerce_string_field(item, "alignment_reasoning", path, problems);
+        if let Some(score) = item.get("alignment_score") {
+            match coerce_integer(score) {
+                Some(n) => {
+                    item.insert("alignment_score".to_string(), json!(n));
+                }
+                None => {
+                    problems.push(format!("{}.alignment_score: expected an integer, got {}", path, score));
+                    item.remove("alignment_score");
+                }
+            }
+        }
+    });
+
+    if let Some(rec) = obj.get_mut("recommendation") {
+        match rec.as_object_mut() {
+            Some(rec) => {
+                for field in ["choice", "reasoning", "tradeoffs", "dissent"] {
+                    coerce_string_field(rec, field, "recommendation", &mut problems);
+                }
+                coerce_enum_field(rec, "confidence", &["high", "medium", "low"], "recommendation", &mut problems);
+                coerce_string_list_field(rec, "next_steps", "recommendation", &mut problems);
+            }
+            None => {
+                problems.push(format!("recommendation: expected an object, got {}", rec));
+                obj.remove("recommendation");
+            }
+        }
+    }
+
+    (Value::Object(obj), problems)
+}
 
 /// Merge new summary fields into existing summary JSON.
 /// Arrays (options, variables, pros_cons) are merged by label/option.
-/// Recommendation is replaced entirely if provided.
+/// Recommendation and debate summary are replaced entirely if provided.
 pub fn merge_summary(existing_json: Option<&str>, update: &Value) -> String {
     let mut existing: Value = existing_json
         .and_then(|s| serde_json::from_str(s).ok())
@@ -46,6 +376,11 @@ pub fn merge_summary(existing_json: Option<&str>, update: &Value) -> String {
         existing["recommendation"] = rec.clone();
     }
 
+    // Debate summary: replace entirely (each debate run supersedes the last)
+    if let Some(debate_summary) = update.get("debate_summary") {
+        existing["debate_summary"] = debate_summary.clone();
+    }
+
     serde_json::to_string(&existing).unwrap_or_else(|_| "{}".to_string())
 }
 
@@ -71,10 +406,514 @@ fn merge_array_by_key(existing: Vec<Value>, new_items: &[Value], key: &str) -> V
     result
 }
 
+/// Just the committee's parsed debate summary and the recommendation from a
+/// decision's summary JSON, or `None` if no debate summary has been stored.
+pub fn debate_summary_view(summary_json: Option<&str>) -> Option<Value> {
+    let summary: Value = serde_json::from_str(summary_json?).ok()?;
+    let debate_summary = summary.get("debate_summary").filter(|v| !v.is_null())?;
+    Some(json!({
+        "debate_summary": debate_summary,
+        "recommendation": summary.get("recommendation").cloned().unwrap_or(Value::Null),
+    }))
+}
+
+/// Record what happened after a decision, which moves it to `reviewed`. Goes
+/// through the same transition check as a manual status change, so a decision
+/// that was never decided can't jump straight to reviewed. Returns the updated row.
+pub fn log_outcome(db: &Database, decision_id: &str, outcome: &str, outcome_score: Option<i64>) -> Result<Decision, String> {
+    let current = db
+        .get_decision(decision_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Decision not found".to_string())?;
+    check_status_transition(&current.status, "reviewed")?;
+    db.update_decision_outcome(decision_id, outcome, outcome_score)
+        .map_err(|e| e.to_string())?;
+    db.get_decision(decision_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Decision not found".to_string())
+}
+
+/// Build the synthetic user message that kicks off outcome reflection.
+/// Mirrors the format the assistant's system prompt watches for.
+pub fn outcome_reflection_message(
+    title: &str,
+    summary_json: Option<&str>,
+    user_choice: Option<&str>,
+    user_choice_reasoning: Option<&str>,
+    outcome: &str,
+) -> String {
+    let summary: Value = summary_json
+        .and_then(|s| serde_json::from_str(s).ok())
+        .unwrap_or_else(|| json!({}));
+    let rec_field = |key: &str| {
+        summary
+            .get("recommendation")
+            .and_then(|r| r.get(key))
+            .and_then(|v| v.as_str())
+            .unwrap_or("N/A")
+            .to_string()
+    };
+
+    format!(
+        "[DECISION OUTCOME LOGGED]\n\nDecision: {}\n\nYour recommendation: {} ({} confidence)\nYour reasoning: {}\n\nWhat I chose: {}\nWhy I chose it: {}\n\nWhat actually happened:\n{}\n\nPlease reflect on this outcome and update my profile with any new insights.",
+        title,
+        rec_field("choice"),
+        rec_field("confidence"),
+        rec_field("reasoning"),
+        user_choice.unwrap_or("N/A"),
+        user_choice_reasoning.unwrap_or("N/A"),
+        outcome,
+    )
+}
+
+/// Statuses a decision may move to next from `from`. Besides the forward path
+/// (exploring → analyzing → debating → recommended → decided → reviewed), a
+/// decision can step back to refine its framing or re-run the committee.
+pub fn valid_transitions(from: &str) -> &'static [&'static str] {
+    match from {
+        "exploring" => &["analyzing"],
+        "analyzing" => &["exploring", "debating"],
+        "debating" => &["analyzing", "recommended"],
+        "recommended" => &["analyzing", "debating", "decided"],
+        "decided" => &["recommended", "reviewed"],
+        _ => &[],
+    }
+}
+
+/// Reject status changes that skip or reverse the decision lifecycle.
+/// Standalone debates use their own statuses and aren't checked.
+pub fn check_status_transition(from: &str, to: &str) -> Result<(), String> {
+    if !DECISION_STATUSES.contains(&from) || from == to {
+        return Ok(());
+    }
+    if !DECISION_STATUSES.contains(&to) {
+        return Err(format!("Unknown decision status: {}", to));
+    }
+    let allowed = valid_transitions(from);
+    if allowed.contains(&to) {
+        return Ok(());
+    }
+    let next = if allowed.is_empty() { "none".to_string() } else { allowed.join(", ") };
+    Err(format!(
+        "Cannot move a decision from '{}' to '{}'. Allowed next statuses: {}.",
+        from, to, next
+    ))
+}
+
+/// Validate a decision deadline and normalize it to RFC3339 UTC (second precision),
+/// so stored values sort correctly as text. Blank input clears the deadline.
+pub fn normalize_due_date(due_date: Option<&str>) -> Result<Option<String>, String> {
+    let Some(raw) = due_date.map(str::trim).filter(|s| !s.is_empty()) else {
+        return Ok(None);
+    };
+    let parsed = chrono::DateTime::parse_from_rfc3339(raw)
+        .map_err(|_| format!("Invalid due date '{}': expected an RFC3339 timestamp", raw))?;
+    Ok(Some(
+        parsed
+            .with_timezone(&chrono::Utc)
+            .to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+    ))
+}
+
+// ── Supporting documents ──
+
+/// A text document the user attached to a decision (offer letter, budget notes).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContextFile {
+    pub filename: String,
+    pub content: String,
+    pub size_bytes: u64,
+}
+
+fn context_dir(app_data_dir: &Path, decision_id: &str) -> PathBuf {
+    app_data_dir.join("decision_context_files").join(decision_id)
+}
+
+/// Only bare `.md` / `.txt` names, so context stays text and inside the decision's folder.
+fn validate_context_filename(filename: &str) -> Result<(), String> {
+    let stem = filename
+        .strip_suffix(".md")
+        .or_else(|| filename.strip_suffix(".txt"))
+        .unwrap_or("");
+    if stem.trim().is_empty()
+        || filename.contains('/')
+        || filename.contains('\\')
+        || filename.contains("..")
+    {
+        return Err("Context files must be plain .md or .txt filenames".to_string());
+    }
+    Ok(())
+}
+
+/// Store (or replace) a supporting document for a decision.
+pub fn add_context_file(
+    app_data_dir: &Path,
+    decision_id: &str,
+    filename: &str,
+    content: &str,
+) -> Result<ContextFile, String> {
+    validate_context_filename(filename)?;
+    if content.len() > MAX_CONTEXT_FILE_BYTES {
+        return Err(format!(
+            "Context file is too large ({} KB, limit {} KB)",
+            content.len() / 1024,
+            MAX_CONTEXT_FILE_BYTES / 1024
+        ));
+    }
+    let dir = context_dir(app_data_dir, decision_id);
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    crate::config::write_atomic(&dir.join(filename), content.as_bytes())?;
+    Ok(ContextFile {
+        filename: filename.to_string(),
+        content: content.to_string(),
+        size_bytes: content.len() as u64,
+    })
+}
+
+/// A decision's supporting documents, sorted by filename. None yet is an empty list.
+pub fn list_context_files(app_data_dir: &Path, decision_id: &str) -> Result<Vec<ContextFile>, String> {
+    let Ok(entries) = fs::read_dir(context_dir(app_data_dir, decision_id)) else {
+        return Ok(Vec::new());
+    };
+    let mut files = Vec::new();
+    for entry in entries {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let filename = entry.file_name().to_string_lossy().to_string();
+        if validate_context_filename(&filename).is_err() {
+            continue;
+        }
+        let content = fs::read_to_string(entry.path()).map_err(|e| e.to_string())?;
+        files.push(ContextFile { filename, size_bytes: content.len() as u64, content });
+    }
+    files.sort_by(|a, b| a.filename.cmp(&b.filename));
+    Ok(files)
+}
+
+pub fn remove_context_file(app_data_dir: &Path, decision_id: &str, filename: &str) -> Result<(), String> {
+    validate_context_filename(filename)?;
+    let path = context_dir(app_data_dir, decision_id).join(filename);
+    if !path.is_file() {
+        return Err(format!("Context file '{}' not found", filename));
+    }
+    fs::remove_file(&path).map_err(|e| e.to_string())
+}
+
+/// How reviewed decisions turned out for one recommendation confidence level.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalibrationBucket {
+    pub confidence: String,
+    pub count: usize,
+    pub scored_count: usize,
+    pub average_score: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalibrationReport {
+    pub buckets: Vec<CalibrationBucket>,
+    /// Reviewed decisions whose summary has no recommendation confidence.
+    pub unrated_count: usize,
+}
+
+/// Bucket reviewed decisions by the recommendation's confidence and average
+/// their outcome scores, to see whether "high confidence" calls pan out.
+pub fn calibration_report(decisions: &[Decision]) -> CalibrationReport {
+    let mut buckets: Vec<CalibrationBucket> = ["high", "medium", "low"]
+        .iter()
+        .map(|c| CalibrationBucket {
+            confidence: c.to_string(),
+            count: 0,
+            scored_count: 0,
+            average_score: None,
+        })
+        .collect();
+    let mut totals = [0i64; 3];
+    let mut unrated_count = 0;
+
+    for decision in decisions.iter().filter(|d| d.status == "reviewed") {
+        let summary = parse_summary(decision.summary_json.as_deref());
+        let confidence = summary
+            .get("recommendation")
+            .and_then(|r| r.get("confidence"))
+            .and_then(|v| v.as_str())
+            .map(|c| c.trim().to_lowercase());
+        let Some(idx) = confidence.and_then(|c| buckets.iter().position(|b| b.confidence == c)) else {
+            unrated_count += 1;
+            continue;
+        };
+        buckets[idx].count += 1;
+        if let Some(score) = decision.outcome_score {
+            buckets[idx].scored_count += 1;
+            totals[idx] += score;
+        }
+    }
+
+    for (bucket, total) in buckets.iter_mut().zip(totals) {
+        if bucket.scored_count > 0 {
+            bucket.average_score = Some(total as f64 / bucket.scored_count as f64);
+        }
+    }
+
+    CalibrationReport { buckets, unrated_count }
+}
+
+/// Side-by-side view of two decisions built from their stored summaries.
+/// Variables are matched by label, ignoring case and surrounding whitespace.
+pub fn compare_decisions(a: &Decision, b: &Decision) -> ComparisonResult {
+    let summary_a = parse_summary(a.summary_json.as_deref());
+    let summary_b = parse_summary(b.summary_json.as_deref());
+    let vars_a = summary_variables(&summary_a);
+    let vars_b = summary_variables(&summary_b);
+
+    let mut shared_variables = Vec::new();
+    let mut only_in_a = Vec::new();
+    for (label, value_a) in &vars_a {
+        let key = label.trim().to_lowercase();
+        match vars_b.iter().find(|(l, _)| l.trim().to_lowercase() == key) {
+            Some((_, value_b)) => shared_variables.push(SharedVariable {
+                label: label.clone(),
+                value_a: value_a.clone(),
+                value_b: value_b.clone(),
+                differs: value_a.trim() != value_b.trim(),
+            }),
+            None => only_in_a.push(label.clone()),
+        }
+    }
+    let only_in_b = vars_b
+        .iter()
+        .filter(|(l, _)| {
+            let key = l.trim().to_lowercase();
+            !vars_a.iter().any(|(la, _)| la.trim().to_lowercase() == key)
+        })
+        .map(|(l, _)| l.clone())
+        .collect();
+
+    ComparisonResult {
+        a: snapshot(a, &summary_a),
+        b: snapshot(b, &summary_b),
+        shared_variables,
+        only_in_a,
+        only_in_b,
+    }
+}
+
+fn parse_summary(summary_json: Option<&str>) -> Value {
+    summary_json
+        .and_then(|s| serde_json::from_str(s).ok())
+        .unwrap_or_else(|| json!({}))
+}
+
+fn summary_variables(summary: &Value) -> Vec<(String, String)> {
+    summary
+        .get("variables")
+        .and_then(|v| v.as_array())
+        .map(|vars| {
+            vars.iter()
+                .filter_map(|v| {
+                    let label = v.get("label")?.as_str()?.to_string();
+                    let value = v.get("value").and_then(|x| x.as_str()).unwrap_or("").to_string();
+                    Some((label, value))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn snapshot(decision: &Decision, summary: &Value) -> DecisionSnapshot {
+    let rec_field = |key: &str| {
+        summary
+            .get("recommendation")
+            .and_then(|r| r.get(key))
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+    };
+    let options = summary
+        .get("options")
+        .and_then(|v| v.as_array())
+        .map(|opts| {
+            opts.iter()
+                .filter_map(|o| o.get("label").and_then(|l| l.as_str()).map(|l| l.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    DecisionSnapshot {
+        decision_id: decision.id.clone(),
+        title: decision.title.clone(),
+        status: decision.status.clone(),
+        options,
+        recommended_choice: rec_field("choice"),
+        confidence: rec_field("confidence"),
+        user_choice: decision.user_choice.clone(),
+        outcome: decision.outcome.clone(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn unit_debate_summary_view_extracts_debate_summary_and_recommendation() {
+        let stored = json!({
+            "options": [{"label": "Stay"}, {"label": "Leave"}],
+            "recommendation": {"choice": "Leave", "confidence": "medium"},
+            "debate_summary": {"consensus": "Pay matters", "disagreements": ["Timing"]},
+        })
+        .to_string();
+        let view = debate_summary_view(Some(&stored)).expect("debate summary should be present");
+        assert_eq!(view["debate_summary"]["consensus"], "Pay matters");
+        assert_eq!(view["recommendation"]["choice"], "Leave");
+        assert!(view.get("options").is_none());
+
+        let no_debate = json!({"recommendation": {"choice": "Stay"}}).to_string();
+        assert_eq!(debate_summary_view(Some(&no_debate)), None);
+        assert_eq!(debate_summary_view(None), None);
+        assert_eq!(debate_summary_view(Some("not json")), None);
+    }
+
+    #[test]
+    fn unit_normalize_due_date_converts_to_utc_and_rejects_garbage() {
+        assert_eq!(
+            normalize_due_date(Some("2026-10-23T17:00:00-04:00")).unwrap().as_deref(),
+            Some("2026-10-23T21:00:00Z")
+        );
+        assert_eq!(normalize_due_date(Some("  ")).unwrap(), None);
+        assert_eq!(normalize_due_date(None).unwrap(), None);
+        assert!(normalize_due_date(Some("Friday")).unwrap_err().contains("RFC3339"));
+    }
+
+    #[test]
+    fn unit_coerce_summary_input_fixes_common_type_mismatches() {
+        let input = json!({
+            "options": ["Stay", {"label": "Leave", "description": ["Higher pay", "New city"]}],
+            "variables": [{"label": "Savings", "value": 12000, "impact": "HIGH"}],
+            "pros_cons": [{"option": "Leave", "pros": "Higher pay", "cons": ["Far from family"], "alignment_score": "8"}],
+            "recommendation": {"choice": "Leave", "confidence": "Medium", "reasoning": "Pay", "next_steps": "Call the recruiter"},
+            "status": "analyzing"
+        });
+        let (coerced, problems) = coerce_summary_input(&input);
+
+        assert!(problems.is_empty(), "{:?}", problems);
+        assert_eq!(coerced["options"][0], json!({"label": "Stay"}));
+        assert_eq!(coerced["options"][1]["description"], "Higher pay, New city");
+        assert_eq!(coerced["variables"][0]["value"], "12000");
+        assert_eq!(coerced["variables"][0]["impact"], "high");
+        assert_eq!(coerced["pros_cons"][0]["alignment_score"], json!(8));
+        assert_eq!(coerced["pros_cons"][0]["pros"], json!(["Higher pay"]));
+        assert_eq!(coerced["recommendation"]["confidence"], "medium");
+        assert_eq!(coerced["recommendation"]["next_steps"], json!(["Call the recruiter"]));
+        assert_eq!(coerced["status"], "analyzing");
+
+        assert_eq!(coerce_integer(&json!("7/10")), Some(7));
+        assert_eq!(coerce_integer(&json!(6.6)), Some(7));
+        assert_eq!(coerce_integer(&json!("high")), None);
+    }
+
+    #[test]
+    fn unit_coerce_summary_input_drops_malformed_parts_gracefully() {
+        let input = json!({
+            "options": 42,
+            "variables": [{"value": "no label"}, {"label": "Risk", "impact": 3}],
+            "pros_cons": [{"option": "Stay", "alignment_score": "very aligned"}],
+            "recommendation": "Leave",
+            "status": "done"
+        });
+        let (coerced, problems) = coerce_summary_input(&input);
+
+        assert!(coerced.get("options").is_none());
+        assert_eq!(coerced["variables"], json!([{"label": "Risk"}]));
+        assert!(coerced["pros_cons"][0].get("alignment_score").is_none());
+        assert!(coerced.get("recommendation").is_none());
+        assert!(coerced.get("status").is_none());
+        assert_eq!(problems.len(), 6, "{:?}", problems);
+
+        let merged: Value = serde_json::from_str(&merge_summary(None, &coerced)).unwrap();
+        assert_eq!(merged["variables"][0]["label"], "Risk");
+
+        let (coerced, problems) = coerce_summary_input(&json!("not an object"));
+        assert_eq!(coerced, json!({}));
+        assert_eq!(problems.len(), 1);
+    }
+
+    #[test]
+    fn unit_status_transition_allows_lifecycle_steps() {
+        assert!(check_status_transition("exploring", "analyzing").is_ok());
+        assert!(check_status_transition("recommended", "decided").is_ok());
+        assert!(check_status_transition("debating", "debating").is_ok());
+        assert!(check_status_transition("completed", "cancelled").is_ok());
+    }
+
+    #[test]
+    fn unit_status_transition_rejects_reopening_reviewed_decision() {
+        let err = check_status_transition("reviewed", "exploring").unwrap_err();
+        assert_eq!(
+            err,
+            "Cannot move a decision from 'reviewed' to 'exploring'. Allowed next statuses: none."
+        );
+        assert!(check_status_transition("exploring", "decided").is_err());
+        assert!(check_status_transition("exploring", "bogus").is_err());
+    }
+
+    #[test]
+    fn unit_merge_summary_with_diff_reports_added_and_changed_fields() {
+        let existing = json!({
+            "options": [{"label": "Stay"}],
+            "variables": [
+                {"label": "Salary", "value": "$100k"},
+                {"label": "Commute", "value": "45 min"}
+            ],
+            "pros_cons": [{"option": "Stay", "pros": ["Stable"], "cons": ["Slow growth"]}],
+            "recommendation": {"choice": "Stay", "confidence": "low", "reasoning": "Default"}
+        })
+        .to_string();
+
+        let update = json!({
+            "options": [{"label": "Stay"}, {"label": "Leave"}],
+            "variables": [
+                {"label": "Salary", "value": "$130k"},
+                {"label": "Commute", "value": "45 min"},
+                {"label": "Equity", "value": "0.1%"}
+            ],
+            "pros_cons": [
+                {"option": "Stay", "pros": ["Stable", "Known team"], "cons": ["Slow growth"]},
+                {"option": "Leave", "pros": ["Growth"], "cons": ["Risk"]}
+            ]
+        });
+
+        let (merged, diff) = merge_summary_with_diff(Some(&existing), &update);
+        assert_eq!(merged, merge_summary(Some(&existing), &update));
+        assert_eq!(diff.options_added, vec!["Leave"]);
+        assert_eq!(diff.variables_added, vec!["Equity"]);
+        assert_eq!(
+            diff.variables_changed,
+            vec![VariableChange { label: "Salary".into(), before: "$100k".into(), after: "$130k".into() }]
+        );
+        assert_eq!(
+            diff.pros_cons_added,
+            vec![
+                ProsConsAdded { option: "Stay".into(), pros: vec!["Known team".into()], cons: vec![] },
+                ProsConsAdded { option: "Leave".into(), pros: vec!["Growth".into()], cons: vec!["Risk".into()] },
+            ]
+        );
+        assert!(!diff.recommendation_changed);
+        assert!(!diff.debate_summary_changed);
+
+        // Re-sending the same data is not a change; a new recommendation is
+        let (_, unchanged) = merge_summary_with_diff(Some(&merged), &update);
+        assert_eq!(unchanged, SummaryDiff::default());
+        let (_, rec) = merge_summary_with_diff(
+            Some(&merged),
+            &json!({"recommendation": {"choice": "Leave", "confidence": "high", "reasoning": "Upside"}}),
+        );
+        assert!(rec.recommendation_changed);
+        assert!(rec.options_added.is_empty());
+
+        // First summary: everything is new
+        let (_, first) = merge_summary_with_diff(None, &json!({"options": [{"label": "A"}], "variables": [{"label": "X", "value": "1"}]}));
+        assert_eq!(first.options_added, vec!["A"]);
+        assert_eq!(first.variables_added, vec!["X"]);
+    }
+
     #[test]
     fn unit_merge_summary_merges_arrays_by_key_and_replaces_recommendation() {
         let existing = json!({
@@ -128,6 +967,14 @@ mod tests {
         assert_eq!(merged_json["variables"][0]["value"], "$130k");
         assert_eq!(merged_json["pros_cons"].as_array().expect("pros_cons array").len(), 2);
         assert_eq!(merged_json["recommendation"]["choice"], "Leave");
+
+        let with_debate = merge_summary(
+            Some(&merged),
+            &json!({"debate_summary": {"vote_tally": {"Leave": 3, "undecided": 0}}}),
+        );
+        let with_debate: Value = serde_json::from_str(&with_debate).unwrap();
+        assert_eq!(with_debate["debate_summary"]["vote_tally"]["Leave"], 3);
+        assert_eq!(with_debate["options"].as_array().unwrap().len(), 2);
     }
 
     #[test]
@@ -144,4 +991,107 @@ mod tests {
         assert_eq!(merged_json["variables"].as_array().expect("variables array").len(), 1);
         assert_eq!(merged_json["variables"][0]["label"], "Risk tolerance");
     }
+
+    #[test]
+    fn integration_log_outcome_requires_a_decided_decision() {
+        let db = crate::db::Database::new(":memory:").expect("in-memory database should initialize");
+        let conv = db.create_conversation_with_type("Move?", "decision").unwrap();
+        let decision = db.create_decision(&conv.id, "Move?").unwrap();
+
+        let err = log_outcome(&db, &decision.id, "Moved anyway", Some(4)).unwrap_err();
+        assert!(err.contains("Cannot move a decision from 'exploring' to 'reviewed'"), "{}", err);
+        let unchanged = db.get_decision(&decision.id).unwrap().unwrap();
+        assert_eq!(unchanged.status, "exploring");
+        assert!(unchanged.outcome.is_none());
+
+        db.update_decision_choice(&decision.id, "Move", None).unwrap();
+        let reviewed = log_outcome(&db, &decision.id, "Moved, happy", Some(4)).unwrap();
+        assert_eq!(reviewed.status, "reviewed");
+        assert_eq!(reviewed.outcome.as_deref(), Some("Moved, happy"));
+        assert!(log_outcome(&db, "missing", "x", None).is_err());
+    }
+
+    #[test]
+    fn integration_compare_decisions_matches_shared_variables_by_label() {
+        let db = crate::db::Database::new(":memory:").expect("in-memory database should initialize");
+        let conv_a = db.create_conversation_with_type("Job offer 2024", "decision").unwrap();
+        let decision_a = db.create_decision(&conv_a.id, "Job offer 2024").unwrap();
+        db.update_decision_summary(&decision_a.id, &merge_summary(None, &json!({
+            "options": [{"label": "Stay"}, {"label": "Leave"}],
+            "variables": [
+                {"label": "Salary", "value": "$100k"},
+                {"label": "Commute", "value": "45 min"},
+                {"label": "Team", "value": "Great"}
+            ],
+            "recommendation": {"choice": "Stay", "confidence": "medium", "reasoning": "Team fit"}
+        }))).unwrap();
+        db.update_decision_outcome(&decision_a.id, "Got promoted a year later", None).unwrap();
+
+        let conv_b = db.create_conversation_with_type("Job offer 2025", "decision").unwrap();
+        let decision_b = db.create_decision(&conv_b.id, "Job offer 2025").unwrap();
+        db.update_decision_summary(&decision_b.id, &merge_summary(None, &json!({
+            "options": [{"label": "Stay"}, {"label": "Join startup"}],
+            "variables": [
+                {"label": "salary ", "value": "$140k"},
+                {"label": "Team", "value": "Great"},
+                {"label": "Equity", "value": "0.5%"}
+            ],
+            "recommendation": {"choice": "Join startup", "confidence": "high", "reasoning": "Upside"}
+        }))).unwrap();
+
+        let a = db.get_decision(&decision_a.id).unwrap().unwrap();
+        let b = db.get_decision(&decision_b.id).unwrap().unwrap();
+        let result = compare_decisions(&a, &b);
+
+        assert_eq!(result.shared_variables.len(), 2);
+        let salary = &result.shared_variables[0];
+        assert_eq!(salary.label, "Salary");
+        assert_eq!(salary.value_a, "$100k");
+        assert_eq!(salary.value_b, "$140k");
+        assert!(salary.differs);
+        assert!(!result.shared_variables[1].differs);
+        assert_eq!(result.only_in_a, vec!["Commute"]);
+        assert_eq!(result.only_in_b, vec!["Equity"]);
+
+        assert_eq!(result.a.recommended_choice.as_deref(), Some("Stay"));
+        assert_eq!(result.b.confidence.as_deref(), Some("high"));
+        assert_eq!(result.a.outcome.as_deref(), Some("Got promoted a year later"));
+        assert!(result.b.outcome.is_none());
+        assert_eq!(result.b.options, vec!["Stay", "Join startup"]);
+    }
+
+    #[test]
+    fn integration_calibration_report_buckets_reviewed_decisions_by_confidence() {
+        let db = crate::db::Database::new(":memory:").expect("in-memory database should initialize");
+        let seed = |title: &str, confidence: Option<&str>, score: Option<i64>, reviewed: bool| {
+            let conv = db.create_conversation_with_type(title, "decision").unwrap();
+            let decision = db.create_decision(&conv.id, title).unwrap();
+            if let Some(c) = confidence {
+                let summary = merge_summary(None, &json!({
+                    "recommendation": {"choice": "A", "confidence": c, "reasoning": "r"}
+                }));
+                db.update_decision_summary(&decision.id, &summary).unwrap();
+            }
+            if reviewed {
+                db.update_decision_outcome(&decision.id, "done", score).unwrap();
+            }
+        };
+        seed("High good", Some("high"), Some(5), true);
+        seed("High poor", Some("high"), Some(2), true);
+        seed("High unscored", Some("high"), None, true);
+        seed("Low", Some("low"), Some(4), true);
+        seed("No rec", None, Some(3), true);
+        seed("Not reviewed", Some("medium"), None, false);
+
+        let report = calibration_report(&db.get_decisions().unwrap());
+        let bucket = |c: &str| report.buckets.iter().find(|b| b.confidence == c).unwrap();
+
+        assert_eq!(bucket("high").count, 3);
+        assert_eq!(bucket("high").scored_count, 2);
+        assert_eq!(bucket("high").average_score, Some(3.5));
+        assert_eq!(bucket("medium").count, 0);
+        assert_eq!(bucket("medium").average_score, None);
+        assert_eq!(bucket("low").average_score, Some(4.0));
+        assert_eq!(report.unrated_count, 1);
+    }
 }
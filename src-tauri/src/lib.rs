@@ -6,6 +6,7 @@ mod debate;
 mod decisions;
 mod llm;
 mod profile;
+mod templates;
 mod tts;
 mod video;
 
@@ -30,40 +31,114 @@ pub fn run() {
                 db: database,
                 app_data_dir,
                 debate_cancel_flags: std::collections::HashMap::new(),
+                model_list_cache: None,
+                live_debate_buffers: llm::LiveBuffers::default(),
             }));
 
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             commands::send_message,
+            commands::regenerate_last_response,
+            commands::edit_message,
             commands::get_conversations,
+            commands::get_conversation,
             commands::get_messages,
+            commands::export_all,
+            commands::import_all,
+            commands::get_system_prompts,
+            commands::update_system_prompt,
             commands::get_settings,
             commands::get_openrouter_models,
+            commands::list_models,
+            commands::validate_model,
+            commands::check_api_keys,
             commands::save_settings,
+            commands::save_max_tokens,
             commands::get_profile_files,
             commands::open_profile_folder,
             commands::delete_conversation,
+            commands::restore_conversation,
+            commands::get_deleted_conversations,
+            commands::purge_conversation,
+            commands::rename_conversation,
+            commands::set_conversation_system_prompt,
             commands::create_decision,
+            commands::add_decision_context,
+            commands::list_decision_context,
+            commands::remove_decision_context,
+            commands::create_decision_from_template,
+            commands::list_decision_templates,
+            commands::save_decision_template,
+            commands::promote_to_decision,
             commands::get_decisions,
+            commands::get_decisions_by_status,
+            commands::get_decision_status_counts,
+            commands::set_decision_due_date,
+            commands::get_decisions_due_soon,
+            commands::get_decisions_with_audio_flag,
             commands::get_decision,
+            commands::compare_decisions,
+            commands::get_calibration_report,
             commands::get_decision_by_conversation,
+            commands::get_related_decisions,
             commands::update_decision_status,
+            commands::reflect_on_outcome,
+            commands::add_decision_tag,
+            commands::remove_decision_tag,
+            commands::get_decision_tags,
+            commands::get_decisions_by_tag,
             commands::get_profile_files_detailed,
             commands::update_profile_file,
+            commands::validate_profile_file,
             commands::remove_profile_file,
+            commands::list_profile_versions,
+            commands::restore_profile_version,
             commands::get_agent_registry,
+            commands::get_agents_with_models,
+            commands::repair_agents,
             commands::get_agent_files,
+            commands::get_agent_prompt,
+            commands::reset_agent_prompt,
             commands::update_agent_file,
             commands::save_agent_model,
+            commands::save_agent_voice_settings,
+            commands::save_default_moderator_model,
+            commands::save_debate_default_model,
             commands::open_agents_folder,
             commands::create_custom_agent,
+            commands::generate_agent_prompt,
             commands::delete_custom_agent,
             commands::start_debate,
+            commands::quick_poll,
+            commands::resume_debate,
+            commands::can_start_debate,
+            commands::get_moderator_synthesis,
+            commands::preview_debate_brief,
+            commands::get_debate_brief,
             commands::get_debate,
+            commands::get_debate_status,
+            commands::get_debate_summary,
+            commands::rebuild_summary_from_debate,
+            commands::undo_summary,
+            commands::get_live_debate_buffer,
+            commands::export_debate_json,
+            commands::replay_debate,
+            commands::delete_debate_round,
+            commands::update_debate_round_content,
             commands::cancel_debate,
+            commands::reset_debate,
             commands::generate_debate_audio,
+            commands::generate_audio_for_debate,
             commands::get_debate_audio,
+            commands::get_partial_debate_audio,
+            commands::get_debate_audio_size,
+            commands::get_total_audio_size,
+            commands::clear_debate_audio,
+            commands::find_orphaned_audio,
+            commands::prune_orphaned_audio,
+            commands::preview_voice,
+            commands::tts_test,
             commands::create_standalone_debate,
             commands::start_standalone_debate,
             commands::get_standalone_debates,
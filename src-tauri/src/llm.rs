@@ -1,15 +1,245 @@
 use crate::commands::AppState;
+use crate::config::AppConfig;
 use crate::decisions;
 use crate::profile;
 use reqwest::Client;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use std::path::PathBuf;
-use std::sync::Mutex;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
 use tauri::ipc::Channel;
 use tauri::{Emitter, Manager};
 
 const OPENROUTER_URL: &str = "https://openrouter.ai/api/v1/chat/completions";
+const OPENAI_URL: &str = "https://api.openai.com/v1/chat/completions";
+/// Model used when switching to the OpenAI provider with a model OpenAI doesn't serve.
+pub const OPENAI_DEFAULT_MODEL: &str = "gpt-4o-mini";
+
+/// Shown when a provider stops responding within the configured request timeout.
+const TIMEOUT_MESSAGE: &str =
+    "Request timed out. The provider took too long to respond; try again or raise the request timeout in Settings.";
+
+static HTTP_CLIENT: OnceLock<Client> = OnceLock::new();
+
+/// The process-wide HTTP client. Built once so LLM and TTS calls share a connection
+/// pool; callers apply the configured timeout per request.
+pub fn http_client() -> &'static Client {
+    HTTP_CLIENT.get_or_init(Client::new)
+}
+
+static STREAMING_CLIENTS: OnceLock<Mutex<HashMap<u64, Client>>> = OnceLock::new();
+
+/// Client for streamed completions. A total-request timeout would cut long replies
+/// off mid-stream, so here the configured timeout bounds connecting and each wait
+/// for the next chunk instead. Cached per timeout so connections are still pooled.
+fn streaming_client(timeout: Duration) -> Result<Client, String> {
+    let mut clients = STREAMING_CLIENTS
+        .get_or_init(Default::default)
+        .lock()
+        .map_err(|e| e.to_string())?;
+    if let Some(client) = clients.get(&timeout.as_secs()) {
+        return Ok(client.clone());
+    }
+    let client = Client::builder()
+        .connect_timeout(timeout)
+        .read_timeout(timeout)
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
+    clients.insert(timeout.as_secs(), client.clone());
+    Ok(client)
+}
+
+/// Format a reqwest failure, replacing timeouts with a message the user can act on.
+pub fn request_error(label: &str, e: reqwest::Error) -> String {
+    if e.is_timeout() {
+        TIMEOUT_MESSAGE.to_string()
+    } else {
+        format!("{}: {}", label, e)
+    }
+}
+
+/// Outcome of testing one service's saved API key.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ServiceHealth {
+    pub configured: bool,
+    pub valid: bool,
+    pub message: String,
+}
+
+/// Interpret a key probe: `probe` is None when no key is saved, otherwise the
+/// HTTP status and body, or the transport error if the service couldn't be reached.
+pub fn service_health(service: &str, probe: Option<Result<(u16, String), String>>) -> ServiceHealth {
+    let (configured, valid, message) = match probe {
+        None => (false, false, format!("No {} API key saved", service)),
+        Some(Ok((status, _))) if (200..300).contains(&status) => {
+            (true, true, format!("{} key is valid", service))
+        }
+        Some(Ok((401 | 403, _))) => (true, false, format!("{} rejected the key", service)),
+        Some(Ok((status, body))) => {
+            let detail: String = body.chars().take(200).collect();
+            (true, false, format!("{} returned {}: {}", service, status, detail))
+        }
+        Some(Err(e)) => (true, false, format!("Could not reach {}: {}", service, e)),
+    };
+    ServiceHealth { configured, valid, message }
+}
+
+/// Key checks back a settings button, so give up quickly.
+const KEY_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Send a key-check request and return its status and body.
+pub async fn probe_key(request: reqwest::RequestBuilder) -> Result<(u16, String), String> {
+    let response = request
+        .timeout(KEY_CHECK_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| request_error("Request failed", e))?;
+    let status = response.status().as_u16();
+    let body = response.text().await.unwrap_or_default();
+    Ok((status, body))
+}
+
+/// Check the OpenRouter key against `GET /api/v1/key`, which needs auth but costs nothing.
+pub async fn check_openrouter_key(api_key: &str) -> ServiceHealth {
+    if api_key.trim().is_empty() {
+        return service_health("OpenRouter", None);
+    }
+    let request = http_client()
+        .get("https://openrouter.ai/api/v1/key")
+        .header("Authorization", format!("Bearer {}", api_key));
+    service_health("OpenRouter", Some(probe_key(request).await))
+}
+
+/// How long a fetched OpenRouter model list is trusted before refetching.
+pub const MODEL_LIST_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// One entry in the model picker.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelInfo {
+    pub id: String,
+    pub name: String,
+    pub context_length: Option<u32>,
+    pub prompt_price_per_million: Option<f64>,
+    pub completion_price_per_million: Option<f64>,
+    pub is_free: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenRouterModelsResponse {
+    data: Vec<OpenRouterModelEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenRouterModelEntry {
+    id: String,
+    name: Option<String>,
+    context_length: Option<u32>,
+    pricing: Option<OpenRouterModelPricing>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenRouterModelPricing {
+    prompt: Option<String>,
+    completion: Option<String>,
+}
+
+/// OpenRouter quotes prices per token as strings; the picker shows them per million.
+fn parse_price(value: Option<&str>) -> Option<f64> {
+    value.and_then(|raw| raw.parse::<f64>().ok()).map(|per_token| per_token * 1_000_000.0)
+}
+
+/// Parse an OpenRouter `/api/v1/models` body into picker entries sorted by name.
+pub fn parse_models_response(body: &str) -> Result<Vec<ModelInfo>, String> {
+    let payload: OpenRouterModelsResponse = serde_json::from_str(body)
+        .map_err(|e| format!("Invalid OpenRouter models response: {}", e))?;
+
+    let mut models: Vec<ModelInfo> = payload
+        .data
+        .into_iter()
+        .map(|entry| {
+            let prompt_price = parse_price(entry.pricing.as_ref().and_then(|p| p.prompt.as_deref()));
+            let completion_price = parse_price(entry.pricing.as_ref().and_then(|p| p.completion.as_deref()));
+            let is_free = prompt_price.unwrap_or(0.0) == 0.0 && completion_price.unwrap_or(0.0) == 0.0;
+
+            ModelInfo {
+                id: entry.id.clone(),
+                name: entry.name.unwrap_or_else(|| entry.id),
+                context_length: entry.context_length,
+                prompt_price_per_million: prompt_price,
+                completion_price_per_million: completion_price,
+                is_free,
+            }
+        })
+        .collect();
+
+    models.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    Ok(models)
+}
+
+/// A few well-known models offered when the catalogue can't be fetched (no API key yet).
+pub fn fallback_models() -> Vec<ModelInfo> {
+    let curated = [
+        ("anthropic/claude-opus-4", "Anthropic: Claude Opus 4"),
+        ("anthropic/claude-sonnet-4-5", "Anthropic: Claude Sonnet 4.5"),
+        ("google/gemini-2.5-pro", "Google: Gemini 2.5 Pro"),
+        ("meta-llama/llama-3.3-70b-instruct", "Meta: Llama 3.3 70B Instruct"),
+        ("openai/gpt-4o", "OpenAI: GPT-4o"),
+        ("openai/gpt-4o-mini", "OpenAI: GPT-4o-mini"),
+    ];
+    curated
+        .iter()
+        .map(|(id, name)| ModelInfo {
+            id: id.to_string(),
+            name: name.to_string(),
+            context_length: None,
+            prompt_price_per_million: None,
+            completion_price_per_million: None,
+            is_free: false,
+        })
+        .collect()
+}
+
+/// Models fetched from OpenRouter, kept briefly so settings validation and the
+/// model picker don't refetch per keystroke.
+pub struct ModelListCache {
+    models: Vec<ModelInfo>,
+    ids: HashSet<String>,
+    fetched_at: Instant,
+}
+
+impl ModelListCache {
+    pub fn new(models: Vec<ModelInfo>, fetched_at: Instant) -> Self {
+        Self {
+            ids: models.iter().map(|m| m.id.clone()).collect(),
+            models,
+            fetched_at,
+        }
+    }
+
+    fn is_fresh(&self, now: Instant) -> bool {
+        now.duration_since(self.fetched_at) <= MODEL_LIST_TTL
+    }
+
+    /// The cached models, or `None` if the list has expired.
+    pub fn models(&self, now: Instant) -> Option<&[ModelInfo]> {
+        self.is_fresh(now).then_some(self.models.as_slice())
+    }
+
+    /// Whether `model_id` is known, or `None` if the cached list has expired.
+    pub fn lookup(&self, model_id: &str, now: Instant) -> Option<bool> {
+        if !self.is_fresh(now) {
+            return None;
+        }
+        Some(self.ids.contains(model_id.trim()))
+    }
+}
+
+/// Sampling temperature for normal chat turns.
+pub const CHAT_TEMPERATURE: f64 = 0.7;
+/// Slightly warmer sampling when the user asks for a different take on the last reply.
+pub const REGENERATE_TEMPERATURE: f64 = 0.9;
 
 const SYSTEM_PROMPT: &str = r#"You are a personal decision-making assistant. Your primary job right now is to deeply understand the user — who they are, what they value, what their life situation looks like, and what matters most to them.
 
@@ -54,6 +284,7 @@ Your job is to:
    - On your VERY FIRST response: identify the options being considered and key variables, then call the tool with at least `options` and `variables` populated, plus set `status` to "exploring".
    - On subsequent responses: update the summary progressively — add new options, refine variables, add pros/cons.
    - Even for seemingly simple or casual decisions (what to watch, where to eat, what to buy), ALWAYS structure them with options and variables. Every decision has factors worth analyzing.
+   - If the conversation shows the real question differs from how the user first phrased it, pass a short `title` with the clearer framing.
 
 3. HAND OFF TO COMMITTEE (status: "analyzing")
    Once you have gathered enough information to frame the decision clearly — meaning you have identified the realistic options AND the key variables/factors — set `status` to "analyzing" in your next `update_decision_summary` call. This AUTOMATICALLY triggers the committee of AI advisors to debate the decision from multiple perspectives. You do NOT make the recommendation yourself. The committee does that.
@@ -86,6 +317,9 @@ pub enum StreamEvent {
     Token { token: String },
     #[serde(rename = "tool_use")]
     ToolUse { tool: String },
+    /// Thinking tokens from reasoning models, kept apart from the reply text.
+    #[serde(rename = "reasoning")]
+    Reasoning { token: String },
 }
 
 // ── OpenAI-compatible tool format (used by OpenRouter) ──
@@ -154,6 +388,10 @@ fn get_tools(is_decision: bool) -> Value {
                     "parameters": {
                         "type": "object",
                         "properties": {
+                            "title": {
+                                "type": "string",
+                                "description": "Optional. A short, clearer framing of the decision (e.g. 'Move to Lisbon or stay in Berlin?'). Only set when the real question differs from the current title."
+                            },
                             "options": {
                                 "type": "array",
                                 "items": {
@@ -198,6 +436,7 @@ fn get_tools(is_decision: bool) -> Value {
                                     "confidence": { "type": "string", "enum": ["high", "medium", "low"] },
                                     "reasoning": { "type": "string" },
                                     "tradeoffs": { "type": "string" },
+                                    "dissent": { "type": "string" },
                                     "next_steps": { "type": "array", "items": { "type": "string" } }
                                 },
                                 "required": ["choice", "confidence", "reasoning"]
@@ -263,12 +502,28 @@ fn execute_tool(
                 .flatten()
                 .and_then(|d| d.summary_json);
 
-            let merged = decisions::merge_summary(existing_summary.as_deref(), input);
+            let (input, problems) = decisions::coerce_summary_input(input);
+            for problem in &problems {
+                eprintln!("update_decision_summary input: {}", problem);
+            }
+            let input = &input;
+
+            let (merged, changes) = decisions::merge_summary_with_diff(existing_summary.as_deref(), input);
 
             if let Err(e) = state_guard.db.update_decision_summary(dec_id, &merged) {
                 return format!("Error saving summary: {}", e);
             }
 
+            // Retitle only once the summary is safely saved; a failed rename doesn't undo it
+            let retitled = if crate::config::load_config(app_data_dir).auto_retitle_decisions {
+                retitle_decision(&state_guard.db, dec_id, input).unwrap_or_else(|e| {
+                    eprintln!("update_decision_summary: could not update title: {}", e);
+                    None
+                })
+            } else {
+                None
+            };
+
             if let Some(status) = input.get("status").and_then(|v| v.as_str()) {
                 if let Err(e) = state_guard.db.update_decision_status(dec_id, status) {
                     return format!("Error updating status: {}", e);
@@ -279,6 +534,8 @@ fn execute_tool(
                 "decision_id": dec_id,
                 "summary": merged,
                 "status": input.get("status").and_then(|v| v.as_str()),
+                "changes": changes,
+                "title": retitled,
             }));
 
             "Decision summary updated successfully.".to_string()
@@ -287,26 +544,595 @@ fn execute_tool(
     }
 }
 
+/// Longest title the assistant may set; anything longer is a sentence, not a title.
+const MAX_DECISION_TITLE_CHARS: usize = 120;
+
+/// Rename the decision (and its conversation) to the `title` in an
+/// `update_decision_summary` call. Returns the new title, or None when the input
+/// has no usable title or it matches the current one.
+fn retitle_decision(db: &crate::db::Database, decision_id: &str, input: &Value) -> Result<Option<String>, String> {
+    let Some(title) = input["title"].as_str().map(str::trim).filter(|t| !t.is_empty()) else {
+        return Ok(None);
+    };
+    if title.chars().count() > MAX_DECISION_TITLE_CHARS {
+        return Ok(None);
+    }
+    let decision = db
+        .get_decision(decision_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Decision not found".to_string())?;
+    if decision.title == title {
+        return Ok(None);
+    }
+    db.update_conversation_title(&decision.conversation_id, title)
+        .map_err(|e| e.to_string())?;
+    Ok(Some(title.to_string()))
+}
+
+// ── Provider selection ──
+// Both providers speak the OpenAI chat-completions protocol, so only the URL,
+// headers, and key differ; streaming and tool parsing are shared.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LlmProvider {
+    OpenRouter,
+    OpenAi,
+    Ollama,
+}
+
+impl LlmProvider {
+    pub fn from_config_value(value: &str) -> Self {
+        match value {
+            "openai" => LlmProvider::OpenAi,
+            "ollama" => LlmProvider::Ollama,
+            _ => LlmProvider::OpenRouter,
+        }
+    }
+
+    fn display_name(self) -> &'static str {
+        match self {
+            LlmProvider::OpenRouter => "OpenRouter",
+            LlmProvider::OpenAi => "OpenAI",
+            LlmProvider::Ollama => "Ollama",
+        }
+    }
+
+    /// A model id this provider can serve. OpenAI takes bare ids, so OpenRouter's
+    /// `openai/` prefix is dropped and other vendors' models fall back to
+    /// `OPENAI_DEFAULT_MODEL`. Empty ids (meaning "use the chat model") are kept.
+    pub fn model_for_provider(self, model: &str) -> String {
+        let model = model.trim();
+        match self {
+            LlmProvider::OpenAi if !model.is_empty() => match model.strip_prefix("openai/") {
+                Some(bare) => bare.to_string(),
+                None if model.contains('/') => OPENAI_DEFAULT_MODEL.to_string(),
+                None => model.to_string(),
+            },
+            _ => model.to_string(),
+        }
+    }
+
+    /// Local models served by Ollama often reject the `tools` field, so we don't send it.
+    pub fn supports_tools(self) -> bool {
+        !matches!(self, LlmProvider::Ollama)
+    }
+}
+
+/// Where chat completions are sent and the key used to authenticate.
+#[derive(Debug, Clone)]
+pub struct LlmEndpoint {
+    pub provider: LlmProvider,
+    pub api_key: String,
+    url: String,
+    timeout_secs: u64,
+    inline_reasoning: bool,
+    models_without_tools: Vec<String>,
+}
+
+impl LlmEndpoint {
+    pub fn from_config(config: &AppConfig) -> Self {
+        let provider = LlmProvider::from_config_value(&config.llm_provider);
+        let (api_key, url) = match provider {
+            LlmProvider::OpenRouter => (config.openrouter_api_key.clone(), OPENROUTER_URL.to_string()),
+            LlmProvider::OpenAi => (config.openai_api_key.clone(), OPENAI_URL.to_string()),
+            LlmProvider::Ollama => (String::new(), config.ollama_base_url.trim().to_string()),
+        };
+        Self {
+            provider,
+            api_key,
+            url,
+            timeout_secs: config.request_timeout_secs,
+            inline_reasoning: config.inline_reasoning,
+            models_without_tools: config.models_without_tools.clone(),
+        }
+    }
+
+    /// Whether `model` can be sent the `tools` field on this provider.
+    pub fn tools_enabled(&self, model: &str) -> bool {
+        self.provider.supports_tools() && !self.models_without_tools.iter().any(|m| m == model)
+    }
+
+    /// Error out early with a settings hint when the selected provider has no key.
+    /// Ollama runs locally and needs no key.
+    pub fn require_api_key(&self) -> Result<(), String> {
+        if self.provider == LlmProvider::Ollama {
+            if self.url.is_empty() {
+                return Err("Ollama URL not set. Please go to Settings to add your Ollama server URL.".to_string());
+            }
+            return Ok(());
+        }
+        if self.api_key.is_empty() {
+            return Err(format!(
+                "API key not set. Please go to Settings to add your {} API key.",
+                self.provider.display_name()
+            ));
+        }
+        Ok(())
+    }
+
+    fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_secs)
+    }
+
+    fn headers(&self) -> reqwest::header::HeaderMap {
+        let mut headers = reqwest::header::HeaderMap::new();
+        if !self.api_key.is_empty() {
+            headers.insert("Authorization", format!("Bearer {}", self.api_key).parse().unwrap());
+        }
+        if self.provider == LlmProvider::OpenRouter {
+            headers.insert("HTTP-Referer", "https://opencouncil.app".parse().unwrap());
+            headers.insert("X-Title", "Open Council".parse().unwrap());
+        }
+        headers.insert("Content-Type", "application/json".parse().unwrap());
+        headers
+    }
+}
+
 // ── Helpers ──
 
-fn openrouter_headers(api_key: &str) -> reqwest::header::HeaderMap {
-    let mut headers = reqwest::header::HeaderMap::new();
-    headers.insert("Authorization", format!("Bearer {}", api_key).parse().unwrap());
-    headers.insert("HTTP-Referer", "https://opencouncil.app".parse().unwrap());
-    headers.insert("X-Title", "Open Council".parse().unwrap());
-    headers.insert("Content-Type", "application/json".parse().unwrap());
-    headers
+/// Reasoning text in a streamed delta. OpenRouter sends `reasoning`; DeepSeek-style
+/// APIs send `reasoning_content`.
+fn delta_reasoning(delta: &Value) -> Option<&str> {
+    delta["reasoning"]
+        .as_str()
+        .or_else(|| delta["reasoning_content"].as_str())
+        .filter(|s| !s.is_empty())
+}
+
+/// Append the reply text in one streamed delta to `text` and forward it through
+/// `emit`. Reasoning is folded into the reply when `inline_reasoning` is set,
+/// otherwise it is emitted on its own and kept out of `text`.
+fn push_delta_text(delta: &Value, inline_reasoning: bool, text: &mut String, emit: &mut impl FnMut(StreamEvent)) {
+    if let Some(reasoning) = delta_reasoning(delta) {
+        if inline_reasoning {
+            text.push_str(reasoning);
+            emit(StreamEvent::Token { token: reasoning.to_string() });
+        } else {
+            emit(StreamEvent::Reasoning { token: reasoning.to_string() });
+        }
+    }
+    if let Some(content) = delta["content"].as_str() {
+        if !content.is_empty() {
+            text.push_str(content);
+            emit(StreamEvent::Token { token: content.to_string() });
+        }
+    }
+}
+
+/// A batched chat token is sent early once it holds this many bytes.
+const TOKEN_BATCH_MAX_BYTES: usize = 64;
+
+/// Coalesces streamed chat tokens so fast models don't flood the IPC channel.
+/// A zero interval disables batching: every token passes straight through.
+struct TokenBatcher {
+    interval: Duration,
+    pending: String,
+    last_flush: Instant,
+}
+
+impl TokenBatcher {
+    fn new(interval: Duration, now: Instant) -> Self {
+        Self { interval, pending: String::new(), last_flush: now }
+    }
+
+    /// Buffer `token`. Returns the batch to emit once the window has elapsed or
+    /// the batch is big enough, otherwise `None`.
+    fn push(&mut self, token: &str, now: Instant) -> Option<String> {
+        self.pending.push_str(token);
+        let due = self.interval.is_zero()
+            || now.duration_since(self.last_flush) >= self.interval
+            || self.pending.len() >= TOKEN_BATCH_MAX_BYTES;
+        if due {
+            self.flush(now)
+        } else {
+            None
+        }
+    }
+
+    /// When the buffered batch falls due, so a pause in the stream doesn't leave it
+    /// unsent until the next token. `None` while nothing is buffered.
+    fn deadline(&self) -> Option<Instant> {
+        (!self.pending.is_empty()).then(|| self.last_flush + self.interval)
+    }
+
+    /// Take whatever is buffered, e.g. before a tool event or at the end of the stream.
+    fn flush(&mut self, now: Instant) -> Option<String> {
+        self.last_flush = now;
+        (!self.pending.is_empty()).then(|| std::mem::take(&mut self.pending))
+    }
+}
+
+fn chat_request_body(
+    model: &str,
+    messages: &[Value],
+    tools: Option<Value>,
+    max_tokens: u32,
+    temperature: f64,
+) -> Value {
+    let mut body = json!({
+        "model": model,
+        "messages": messages,
+        "temperature": temperature,
+        "max_tokens": max_tokens,
+        "stream": true,
+    });
+    if let Some(tools) = tools {
+        body["tools"] = tools;
+    }
+    body
+}
+
+pub fn debate_request_body(model: &str, system_prompt: &str, user_prompt: &str, max_tokens: u32, temperature: f32) -> Value {
+    json!({
+        "model": model,
+        "messages": [
+            {"role": "system", "content": system_prompt},
+            {"role": "user", "content": user_prompt},
+        ],
+        "temperature": temperature,
+        "max_tokens": max_tokens,
+        "stream": true,
+    })
+}
+
+// ── Base system prompt overrides ──
+
+/// Chat modes whose base system prompt can be replaced by a file in the app data dir.
+const SYSTEM_PROMPT_KINDS: [(&str, &str); 2] = [
+    ("chat", "system_prompt.md"),
+    ("decision", "decision_system_prompt.md"),
+];
+
+/// One chat mode's base system prompt as shown in settings.
+#[derive(Debug, Clone, Serialize)]
+pub struct SystemPromptInfo {
+    pub kind: String,
+    pub filename: String,
+    pub content: String,
+    pub is_default: bool,
+    pub default_content: String,
+}
+
+fn system_prompt_file(kind: &str) -> Result<&'static str, String> {
+    SYSTEM_PROMPT_KINDS
+        .iter()
+        .find(|(k, _)| *k == kind)
+        .map(|(_, file)| *file)
+        .ok_or_else(|| format!("Unknown system prompt kind '{}'. Use 'chat' or 'decision'.", kind))
+}
+
+fn default_system_prompt(kind: &str) -> &'static str {
+    if kind == "decision" { DECISION_SYSTEM_PROMPT } else { SYSTEM_PROMPT }
+}
+
+/// The user's override for a chat mode, if its file exists and isn't blank.
+fn read_system_prompt_override(app_data_dir: &Path, kind: &str) -> Option<String> {
+    let file = system_prompt_file(kind).ok()?;
+    std::fs::read_to_string(app_data_dir.join(file))
+        .ok()
+        .filter(|s| !s.trim().is_empty())
+}
+
+/// Base system prompt for a chat mode: the override file if present, else the built-in.
+pub fn base_system_prompt(app_data_dir: &Path, is_decision: bool) -> String {
+    let kind = if is_decision { "decision" } else { "chat" };
+    read_system_prompt_override(app_data_dir, kind)
+        .unwrap_or_else(|| default_system_prompt(kind).to_string())
 }
 
-fn map_api_error(status: reqwest::StatusCode, body: &str) -> String {
+pub fn get_system_prompts(app_data_dir: &Path) -> Vec<SystemPromptInfo> {
+    SYSTEM_PROMPT_KINDS
+        .iter()
+        .map(|(kind, file)| {
+            let custom = read_system_prompt_override(app_data_dir, kind);
+            SystemPromptInfo {
+                kind: kind.to_string(),
+                filename: file.to_string(),
+                is_default: custom.is_none(),
+                content: custom.unwrap_or_else(|| default_system_prompt(kind).to_string()),
+                default_content: default_system_prompt(kind).to_string(),
+            }
+        })
+        .collect()
+}
+
+/// Save a chat mode's base system prompt. Blank content deletes the override,
+/// restoring the built-in prompt.
+pub fn write_system_prompt(app_data_dir: &Path, kind: &str, content: &str) -> Result<(), String> {
+    let path = app_data_dir.join(system_prompt_file(kind)?);
+    if content.trim().is_empty() {
+        if path.exists() {
+            std::fs::remove_file(&path).map_err(|e| e.to_string())?;
+        }
+        return Ok(());
+    }
+    std::fs::create_dir_all(app_data_dir).map_err(|e| e.to_string())?;
+    std::fs::write(&path, content).map_err(|e| e.to_string())
+}
+
+/// Append a conversation's own instructions after the base system prompt.
+fn with_prompt_override(base_prompt: &str, system_prompt_override: Option<&str>) -> String {
+    match system_prompt_override.map(str::trim).filter(|s| !s.is_empty()) {
+        Some(extra) => format!(
+            "{}\n\n## Conversation-specific instructions\n{}",
+            base_prompt, extra
+        ),
+        None => base_prompt.to_string(),
+    }
+}
+
+// ── Tool-less fallback ──
+
+/// Opening fence of the action block tool-less models append to their reply.
+const ACTION_BLOCK_FENCE: &str = "```council-actions";
+
+/// System prompt for models that can't call tools: the profile files are inlined
+/// (there is no `read_profile_files`), and updates are requested as a JSON block
+/// at the end of the reply, which `split_action_block` picks up.
+fn tool_less_system_prompt(base_prompt: &str, is_decision: bool, profiles: &HashMap<String, String>) -> String {
+    let mut prompt = base_prompt.to_string();
+    prompt.push_str("\n\n## Working without tools\nTools are not available in this conversation. Ignore any instruction above to call a tool; use the steps below instead.\n\n### Current profile files\n");
+    if profiles.is_empty() {
+        prompt.push_str("(none yet)\n");
+    } else {
+        let mut names: Vec<&String> = profiles.keys().collect();
+        names.sort();
+        for name in names {
+            prompt.push_str(&format!("\n#### {}\n{}\n", name, profiles[name]));
+        }
+    }
+    let summary_key = if is_decision {
+        ",\n  \"update_decision_summary\": { same fields as the update_decision_summary tool }"
+    } else {
+        ""
+    };
+    prompt.push_str(&format!(
+        "\n### Saving updates\nTo create or update profile files{}, end your reply with exactly one block:\n\n{}\n{{\n  \"write_profile_files\": [{{ \"filename\": \"career.md\", \"content\": \"full new file content\" }}]{}\n}}\n```\n\nInclude only the keys you need, and omit the block entirely when nothing changed. Write the block last; the app removes it from your reply after applying it.",
+        if is_decision { " or the decision summary" } else { "" },
+        ACTION_BLOCK_FENCE,
+        summary_key,
+    ));
+    prompt
+}
+
+/// Split a tool-less reply into the text to keep and its parsed action block.
+/// A block that isn't valid JSON is left in place.
+fn split_action_block(text: &str) -> (String, Option<Value>) {
+    let Some(start) = text.rfind(ACTION_BLOCK_FENCE) else {
+        return (text.to_string(), None);
+    };
+    let body_start = start + ACTION_BLOCK_FENCE.len();
+    let (body, rest) = match text[body_start..].find("```") {
+        Some(end) => (&text[body_start..body_start + end], &text[body_start + end + 3..]),
+        None => (&text[body_start..], ""),
+    };
+    match serde_json::from_str::<Value>(body.trim()) {
+        Ok(actions) if actions.is_object() => {
+            let before = text[..start].trim_end();
+            let after = rest.trim();
+            let kept = if after.is_empty() { before.to_string() } else { format!("{}\n\n{}", before, after) };
+            (kept, Some(actions))
+        }
+        _ => {
+            eprintln!("Ignoring malformed council-actions block");
+            (text.to_string(), None)
+        }
+    }
+}
+
+/// Keeps a tool-less model's action block out of the streamed text. Tokens pass
+/// through until the opening fence appears; from then on nothing more is streamed.
+/// A tail that could still turn into the fence is held back until it's resolved.
+#[derive(Default)]
+struct ActionBlockHoldback {
+    seen: String,
+    streamed: usize,
+    fenced: bool,
+}
+
+impl ActionBlockHoldback {
+    /// Add `token` and return the text that is now safe to stream, if any.
+    fn push(&mut self, token: &str) -> Option<String> {
+        self.seen.push_str(token);
+        if self.fenced {
+            return None;
+        }
+        let safe_end = match self.seen[self.streamed..].find(ACTION_BLOCK_FENCE) {
+            Some(pos) => {
+                self.fenced = true;
+                self.streamed + pos
+            }
+            None => {
+                let partial = (1..ACTION_BLOCK_FENCE.len())
+                    .rev()
+                    .find(|&len| self.seen.ends_with(&ACTION_BLOCK_FENCE[..len]))
+                    .unwrap_or(0);
+                self.seen.len() - partial
+            }
+        };
+        let safe = self.seen[self.streamed..safe_end].to_string();
+        self.streamed = safe_end;
+        (!safe.is_empty()).then_some(safe)
+    }
+
+    /// At the end of the stream: release a held-back tail that never became a fence.
+    fn finish(&mut self) -> Option<String> {
+        if self.fenced || self.streamed == self.seen.len() {
+            return None;
+        }
+        let rest = self.seen[self.streamed..].to_string();
+        self.streamed = self.seen.len();
+        Some(rest)
+    }
+}
+
+/// Apply an action block through the same code paths the tools use.
+fn apply_action_block(
+    actions: &Value,
+    app_data_dir: &PathBuf,
+    decision_id: Option<&str>,
+    app_handle: &tauri::AppHandle,
+    on_event: &Channel<StreamEvent>,
+) {
+    for file in actions["write_profile_files"].as_array().into_iter().flatten() {
+        let _ = on_event.send(StreamEvent::ToolUse { tool: "write_profile_file".to_string() });
+        let result = execute_tool("write_profile_file", file, app_data_dir, decision_id, app_handle);
+        eprintln!("council-actions write_profile_file: {}", result);
+    }
+    if let Some(summary) = actions.get("update_decision_summary").filter(|v| v.is_object()) {
+        let _ = on_event.send(StreamEvent::ToolUse { tool: "update_decision_summary".to_string() });
+        let result = execute_tool("update_decision_summary", summary, app_data_dir, decision_id, app_handle);
+        eprintln!("council-actions update_decision_summary: {}", result);
+    }
+}
+
+// ── SSE parsing ──
+
+/// Incremental parser for the Server-Sent Events streams chat completions arrive in.
+/// Bytes are buffered until a full line is available, so multi-byte characters split
+/// across chunks survive. Consecutive `data:` lines form one event, dispatched at the
+/// next blank line; `\r\n` endings and `:` comment lines are handled.
+#[derive(Default)]
+struct SseParser {
+    buffer: Vec<u8>,
+    data_lines: Vec<String>,
+}
+
+impl SseParser {
+    /// Feed one network chunk; returns the JSON payload of every event it completed.
+    fn push(&mut self, chunk: &[u8]) -> Vec<Value> {
+        self.buffer.extend_from_slice(chunk);
+        let mut events = Vec::new();
+        while let Some(pos) = self.buffer.iter().position(|&b| b == b'\n') {
+            let raw: Vec<u8> = self.buffer.drain(..=pos).collect();
+            let line = String::from_utf8_lossy(&raw[..raw.len() - 1]);
+            self.process_line(line.strip_suffix('\r').unwrap_or(&line), &mut events);
+        }
+        events
+    }
+
+    /// Flush whatever is left when the stream closes without a trailing blank line.
+    fn finish(&mut self) -> Vec<Value> {
+        let mut events = Vec::new();
+        if !self.buffer.is_empty() {
+            let raw = std::mem::take(&mut self.buffer);
+            let line = String::from_utf8_lossy(&raw);
+            self.process_line(line.strip_suffix('\r').unwrap_or(&line), &mut events);
+        }
+        self.dispatch(&mut events);
+        events
+    }
+
+    fn process_line(&mut self, line: &str, events: &mut Vec<Value>) {
+        if line.is_empty() {
+            self.dispatch(events);
+        } else if line.starts_with(':') {
+            // Comment / keep-alive
+        } else if let Some(data) = line.strip_prefix("data:") {
+            self.data_lines.push(data.strip_prefix(' ').unwrap_or(data).to_string());
+        }
+        // Other fields (event:, id:, retry:) carry nothing we use
+    }
+
+    fn dispatch(&mut self, events: &mut Vec<Value>) {
+        let lines = std::mem::take(&mut self.data_lines);
+        if lines.is_empty() {
+            return;
+        }
+        let joined = lines.join("\n");
+        if joined.trim() == "[DONE]" {
+            return;
+        }
+        if let Ok(value) = serde_json::from_str(&joined) {
+            events.push(value);
+            return;
+        }
+        // Some proxies omit the blank line between events; fall back to one event per line
+        for line in &lines {
+            if let Ok(value) = serde_json::from_str(line) {
+                events.push(value);
+            }
+        }
+    }
+}
+
+/// Rough token estimate (~4 characters per token), good enough for budgeting.
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+fn estimate_message_tokens(message: &Value) -> usize {
+    match message["content"].as_str() {
+        Some(content) => estimate_tokens(content),
+        None => estimate_tokens(&message.to_string()),
+    }
+}
+
+/// Drop the oldest history messages until the system prompt plus history fits
+/// `token_budget`. The latest user message (and anything after it) is always
+/// kept, even if that alone exceeds the budget. Returns how many were dropped.
+fn trim_history_to_budget(history: &mut Vec<Value>, system_prompt: &str, token_budget: usize) -> usize {
+    let keep_from = history
+        .iter()
+        .rposition(|m| m["role"] == "user")
+        .unwrap_or(history.len().saturating_sub(1));
+    let mut total = estimate_tokens(system_prompt)
+        + history.iter().map(estimate_message_tokens).sum::<usize>();
+
+    let mut dropped = 0;
+    while total > token_budget && dropped < keep_from {
+        total -= estimate_message_tokens(&history[dropped]);
+        dropped += 1;
+    }
+    history.drain(..dropped);
+    dropped
+}
+
+/// The message list sent to the model: system prompt first, then the history.
+fn outgoing_messages(system_prompt: &str, history: &[Value]) -> Vec<Value> {
+    let mut messages = vec![json!({"role": "system", "content": system_prompt})];
+    messages.extend(history.iter().cloned());
+    messages
+}
+
+fn map_api_error(provider: LlmProvider, status: reqwest::StatusCode, body: &str) -> String {
+    let (keys_url, billing_url, models_url) = match provider {
+        LlmProvider::OpenRouter => ("openrouter.ai/keys", "openrouter.ai", "openrouter.ai/models"),
+        LlmProvider::OpenAi => ("platform.openai.com/api-keys", "platform.openai.com", "platform.openai.com/docs/models"),
+        LlmProvider::Ollama => ("your Ollama server", "your Ollama server", "`ollama list`"),
+    };
     match status.as_u16() {
-        401 => "Invalid API key. Check your key at openrouter.ai/keys".to_string(),
-        402 => "Insufficient credits. Visit openrouter.ai to add funds.".to_string(),
+        401 => format!("Invalid API key. Check your key at {}", keys_url),
+        402 => format!("Insufficient credits. Visit {} to add funds.", billing_url),
         429 => "Rate limited. Please wait a moment and try again.".to_string(),
-        400 if body.contains("model_not_found") || body.contains("not found") => {
-            "Model not found. Check the model ID at openrouter.ai/models".to_string()
+        400 | 404 if body.contains("model_not_found") || body.contains("not found") => {
+            format!("Model not found. Check the model ID at {}", models_url)
         }
-        500 | 502 | 503 => "OpenRouter is temporarily unavailable. Try again in a moment.".to_string(),
+        500 | 502 | 503 => format!("{} is temporarily unavailable. Try again in a moment.", provider.display_name()),
         _ => format!("API error ({}): {}", status, body),
     }
 }
@@ -324,9 +1150,18 @@ struct PendingToolCall {
 
 // ── Public entry point: send_message ──
 
+/// Model and sampling settings for one chat turn.
+pub struct ChatSettings<'a> {
+    pub endpoint: &'a LlmEndpoint,
+    pub model: &'a str,
+    pub max_tokens: u32,
+    pub temperature: f64,
+    pub system_prompt_override: Option<&'a str>,
+    pub context_token_budget: u32,
+}
+
 pub async fn send_message(
-    api_key: &str,
-    model: &str,
+    settings: &ChatSettings<'_>,
     messages: Vec<Value>,
     app_data_dir: &PathBuf,
     on_event: &Channel<StreamEvent>,
@@ -334,84 +1169,101 @@ pub async fn send_message(
     decision_id: Option<&str>,
     app_handle: &tauri::AppHandle,
 ) -> Result<String, String> {
-    let client = Client::new();
+    let &ChatSettings { endpoint, model, max_tokens, temperature, system_prompt_override, context_token_budget } = settings;
+    let client = streaming_client(endpoint.timeout())?;
     let is_decision = conv_type == "decision";
-    let system_prompt = if is_decision { DECISION_SYSTEM_PROMPT } else { SYSTEM_PROMPT };
+    let use_tools = endpoint.tools_enabled(model);
+    let mut base_prompt = base_system_prompt(app_data_dir, is_decision);
+    if !use_tools {
+        let profiles = profile::read_all_profiles(app_data_dir).unwrap_or_default();
+        base_prompt = tool_less_system_prompt(&base_prompt, is_decision, &profiles);
+    }
+    let system_prompt = with_prompt_override(&base_prompt, system_prompt_override);
 
-    // Build message list with system prompt as first message
-    let mut openrouter_messages: Vec<Value> = vec![
-        json!({"role": "system", "content": system_prompt}),
-    ];
-    for msg in &messages {
-        openrouter_messages.push(msg.clone());
+    // Keep long decision chats inside the model's context window
+    let mut messages = messages;
+    let dropped = trim_history_to_budget(&mut messages, &system_prompt, context_token_budget as usize);
+    if dropped > 0 {
+        eprintln!("Trimmed {} oldest messages to fit the {}-token context budget", dropped, context_token_budget);
     }
 
+    // Build message list with system prompt as first message
+    let mut openrouter_messages = outgoing_messages(&system_prompt, &messages);
+
     let mut all_text = String::new();
+    let flush_interval = Duration::from_millis(crate::config::load_config(app_data_dir).token_flush_interval_ms);
+    let mut batcher = TokenBatcher::new(flush_interval, Instant::now());
+    let send_batch = |batch: Option<String>| {
+        if let Some(token) = batch {
+            let _ = on_event.send(StreamEvent::Token { token });
+        }
+    };
+    // Only tool-less replies carry an action block; tool-capable ones stream as-is
+    let mut holdback = (!use_tools).then(ActionBlockHoldback::default);
 
     loop {
-        let request_body = json!({
-            "model": model,
-            "messages": openrouter_messages,
-            "tools": get_tools(is_decision),
-            "temperature": 0.7,
-            "max_tokens": 4096,
-            "stream": true,
-        });
+        let tools = use_tools.then(|| get_tools(is_decision));
+        let request_body = chat_request_body(model, &openrouter_messages, tools, max_tokens, temperature);
 
         let mut response = client
-            .post(OPENROUTER_URL)
-            .headers(openrouter_headers(api_key))
+            .post(endpoint.url())
+            .headers(endpoint.headers())
             .json(&request_body)
             .send()
             .await
-            .map_err(|e| format!("Network error: {}", e))?;
+            .map_err(|e| request_error("Network error", e))?;
 
         let status = response.status();
         if !status.is_success() {
-            let error_text = response.text().await.map_err(|e| format!("Read error: {}", e))?;
-            return Err(map_api_error(status, &error_text));
+            let error_text = response.text().await.map_err(|e| request_error("Read error", e))?;
+            return Err(map_api_error(endpoint.provider, status, &error_text));
         }
 
         let mut iteration_text = String::new();
         let mut pending_tool_calls: Vec<PendingToolCall> = Vec::new();
-        let mut buffer = String::new();
-
-        while let Some(chunk) = response.chunk().await.map_err(|e| format!("Stream error: {}", e))? {
-            buffer.push_str(&String::from_utf8_lossy(&chunk));
-
-            // Process complete SSE lines (data: {...}\n\n)
-            while let Some(pos) = buffer.find('\n') {
-                let line = buffer[..pos].trim_end().to_string();
-                buffer = buffer[pos + 1..].to_string();
-
-                if line.is_empty() {
-                    continue;
-                }
-
-                let data_str = match line.strip_prefix("data: ") {
-                    Some(d) => d,
-                    None => continue,
-                };
-
-                if data_str == "[DONE]" {
-                    continue;
+        let mut sse = SseParser::default();
+        let mut stream_done = false;
+
+        while !stream_done {
+            // Wait for the next chunk, but no longer than the pending batch may sit
+            let next = match batcher.deadline() {
+                Some(deadline) => match tokio::time::timeout_at(deadline.into(), response.chunk()).await {
+                    Ok(next) => next,
+                    Err(_) => {
+                        send_batch(batcher.flush(Instant::now()));
+                        continue;
+                    }
+                },
+                None => response.chunk().await,
+            };
+            let events = match next.map_err(|e| request_error("Stream error", e))? {
+                Some(chunk) => sse.push(&chunk),
+                None => {
+                    stream_done = true;
+                    sse.finish()
                 }
+            };
 
-                let data: Value = match serde_json::from_str(data_str) {
-                    Ok(v) => v,
-                    Err(_) => continue,
-                };
-
+            for data in events {
                 let choice = &data["choices"][0];
                 let delta = &choice["delta"];
 
-                // Text content
-                if let Some(content) = delta["content"].as_str() {
-                    if !content.is_empty() {
-                        iteration_text.push_str(content);
-                        let _ = on_event.send(StreamEvent::Token { token: content.to_string() });
+                // Text content and reasoning
+                push_delta_text(delta, endpoint.inline_reasoning, &mut iteration_text, &mut |event| match event {
+                    StreamEvent::Token { token } => {
+                        let token = match holdback.as_mut() {
+                            Some(holdback) => holdback.push(&token),
+                            None => Some(token),
+                        };
+                        if let Some(token) = token {
+                            send_batch(batcher.push(&token, Instant::now()));
+                        }
                     }
-                }
+                    other => {
+                        send_batch(batcher.flush(Instant::now()));
+                        let _ = on_event.send(other);
+                    }
+                });
 
                 // Tool calls (streamed incrementally)
                 if let Some(tool_calls) = delta["tool_calls"].as_array() {
@@ -433,6 +1285,7 @@ pub async fn send_message(
                         }
                         if let Some(name) = tc["function"]["name"].as_str() {
                             pending_tool_calls[index].name = name.to_string();
+                            send_batch(batcher.flush(Instant::now()));
                             let _ = on_event.send(StreamEvent::ToolUse { tool: name.to_string() });
                         }
                         // Subsequent chunks append to arguments
@@ -443,6 +1296,10 @@ pub async fn send_message(
                 }
             }
         }
+        if let Some(rest) = holdback.as_mut().and_then(ActionBlockHoldback::finish) {
+            send_batch(batcher.push(&rest, Instant::now()));
+        }
+        send_batch(batcher.flush(Instant::now()));
 
         // Filter out empty tool calls (shouldn't happen, but defensive)
         let tool_calls: Vec<PendingToolCall> = pending_tool_calls
@@ -452,6 +1309,13 @@ pub async fn send_message(
 
         if tool_calls.is_empty() {
             all_text.push_str(&iteration_text);
+            if !use_tools {
+                let (reply, actions) = split_action_block(&all_text);
+                if let Some(actions) = actions {
+                    apply_action_block(&actions, app_data_dir, decision_id, app_handle, on_event);
+                }
+                return Ok(reply);
+            }
             return Ok(all_text);
         }
 
@@ -494,99 +1358,204 @@ pub async fn send_message(
 
 // ── Streaming LLM call for debate (no tools, emits per-token events) ──
 
-pub async fn call_llm_streaming_debate(
-    api_key: &str,
-    model: &str,
-    system_prompt: &str,
-    user_prompt: &str,
-    app_handle: &tauri::AppHandle,
+/// Payload for `debate-agent-token` events. The agent key is what lets the UI route
+/// tokens to the right speaker, including the moderator (round 99).
+pub fn debate_token_payload(
     decision_id: &str,
     round_number: i32,
     exchange_number: i32,
     agent_key: &str,
-) -> Result<String, String> {
-    let client = Client::new();
-    let request_body = json!({
-        "model": model,
-        "messages": [
-            {"role": "system", "content": system_prompt},
-            {"role": "user", "content": user_prompt},
-        ],
-        "temperature": 0.7,
-        "max_tokens": 2048,
-        "stream": true,
-    });
+    token: &str,
+) -> Value {
+    json!({
+        "decision_id": decision_id,
+        "round_number": round_number,
+        "exchange_number": exchange_number,
+        "agent": agent_key,
+        "token": token,
+    })
+}
 
-    let mut response = client
-        .post(OPENROUTER_URL)
-        .headers(openrouter_headers(api_key))
-        .json(&request_body)
-        .send()
-        .await
-        .map_err(|e| format!("Network error: {}", e))?;
+/// The reply an agent is streaming right now, kept per decision so a UI that
+/// remounts mid-turn can show the partial text instead of starting blank.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LiveBuffer {
+    pub agent: String,
+    pub round: i32,
+    pub exchange: i32,
+    pub text: String,
+}
 
-    let status = response.status();
-    if !status.is_success() {
-        let error_text = response.text().await.map_err(|e| format!("Read error: {}", e))?;
-        return Err(map_api_error(status, &error_text));
+/// One streaming turn's key in `LiveBuffers`: decision, agent, round, exchange.
+type LiveBufferKey = (String, String, i32, i32);
+
+/// Replies being streamed right now, one per turn so parallel openings don't
+/// overwrite each other. Held behind its own lock, apart from `AppState`, so
+/// writing streamed text never contends with the rest of the app. Clones share
+/// the same buffers.
+#[derive(Debug, Clone, Default)]
+pub struct LiveBuffers(Arc<Mutex<HashMap<LiveBufferKey, LiveBuffer>>>);
+
+impl LiveBuffers {
+    fn key(turn: DebateTurnRef<'_>) -> LiveBufferKey {
+        (turn.decision_id.to_string(), turn.agent_key.to_string(), turn.round_number, turn.exchange_number)
     }
 
-    let mut all_text = String::new();
-    let mut buffer = String::new();
+    fn update(&self, update: impl FnOnce(&mut HashMap<LiveBufferKey, LiveBuffer>)) {
+        if let Ok(mut buffers) = self.0.lock() {
+            update(&mut buffers);
+        }
+    }
 
-    while let Some(chunk) = response.chunk().await.map_err(|e| format!("Stream error: {}", e))? {
-        buffer.push_str(&String::from_utf8_lossy(&chunk));
+    /// Start an empty buffer for a turn. A retried attempt starts over rather
+    /// than appending to the failed attempt's text.
+    pub fn begin(&self, turn: DebateTurnRef<'_>) {
+        self.update(|buffers| {
+            buffers.insert(Self::key(turn), LiveBuffer {
+                agent: turn.agent_key.to_string(),
+                round: turn.round_number,
+                exchange: turn.exchange_number,
+                text: String::new(),
+            });
+        });
+    }
 
-        while let Some(pos) = buffer.find('\n') {
-            let line = buffer[..pos].trim_end().to_string();
-            buffer = buffer[pos + 1..].to_string();
+    /// Append streamed text to the turn's buffer, starting one if needed.
+    pub fn append(&self, turn: DebateTurnRef<'_>, text: &str) {
+        self.update(|buffers| {
+            buffers
+                .entry(Self::key(turn))
+                .or_insert_with(|| LiveBuffer {
+                    agent: turn.agent_key.to_string(),
+                    round: turn.round_number,
+                    exchange: turn.exchange_number,
+                    text: String::new(),
+                })
+                .text
+                .push_str(text);
+        });
+    }
 
-            if line.is_empty() {
-                continue;
-            }
+    /// Drop a turn's buffer once it has been saved.
+    pub fn clear(&self, turn: DebateTurnRef<'_>) {
+        self.update(|buffers| {
+            buffers.remove(&Self::key(turn));
+        });
+    }
 
-            let data_str = match line.strip_prefix("data: ") {
-                Some(d) => d,
-                None => continue,
-            };
+    /// Drop every buffer for a decision, e.g. when its debate is cancelled.
+    pub fn clear_decision(&self, decision_id: &str) {
+        self.update(|buffers| buffers.retain(|key, _| key.0 != decision_id));
+    }
 
-            if data_str == "[DONE]" {
-                continue;
-            }
+    /// The decision's in-progress turns in speaking order.
+    pub fn for_decision(&self, decision_id: &str) -> Vec<LiveBuffer> {
+        let Ok(buffers) = self.0.lock() else {
+            return Vec::new();
+        };
+        let mut turns: Vec<LiveBuffer> = buffers
+            .iter()
+            .filter(|(key, _)| key.0 == decision_id)
+            .map(|(_, buffer)| buffer.clone())
+            .collect();
+        turns.sort_by(|a, b| (a.round, a.exchange, &a.agent).cmp(&(b.round, b.exchange, &b.agent)));
+        turns
+    }
+}
 
-            let data: Value = match serde_json::from_str(data_str) {
-                Ok(v) => v,
-                Err(_) => continue,
-            };
+/// Which debate turn a streamed reply belongs to. Tags the token events and the live buffer.
+#[derive(Debug, Clone, Copy)]
+pub struct DebateTurnRef<'a> {
+    pub decision_id: &'a str,
+    pub agent_key: &'a str,
+    pub round_number: i32,
+    pub exchange_number: i32,
+}
 
-            if let Some(content) = data["choices"][0]["delta"]["content"].as_str() {
-                if !content.is_empty() {
-                    all_text.push_str(content);
-                    let _ = app_handle.emit("debate-agent-token", json!({
-                        "decision_id": decision_id,
-                        "round_number": round_number,
-                        "exchange_number": exchange_number,
-                        "agent": agent_key,
-                        "token": content,
-                    }));
+/// Stream one debate turn built by `debate_request_body`, emitting tokens for `turn`.
+/// Streamed text reaches the turn's live buffer in batches of `flush_interval`.
+pub async fn call_llm_streaming_debate(
+    endpoint: &LlmEndpoint,
+    request_body: &Value,
+    app_handle: &tauri::AppHandle,
+    turn: DebateTurnRef<'_>,
+    flush_interval: Duration,
+) -> Result<String, String> {
+    let DebateTurnRef { decision_id, agent_key, round_number, exchange_number } = turn;
+    let live = {
+        let state: tauri::State<'_, Mutex<AppState>> = app_handle.state();
+        let guard = state.lock().map_err(|e| e.to_string())?;
+        guard.live_debate_buffers.clone()
+    };
+    live.begin(turn);
+    let mut batcher = TokenBatcher::new(flush_interval, Instant::now());
+    let result = stream_completion(endpoint, request_body, |event| {
+        let (name, token) = match &event {
+            StreamEvent::Token { token } => {
+                if let Some(batch) = batcher.push(token, Instant::now()) {
+                    live.append(turn, &batch);
                 }
+                ("debate-agent-token", token)
             }
+            StreamEvent::Reasoning { token } => ("debate-agent-reasoning", token),
+            StreamEvent::ToolUse { .. } => return,
+        };
+        let _ = app_handle.emit(name, debate_token_payload(decision_id, round_number, exchange_number, agent_key, token));
+    })
+    .await;
+    if let Some(rest) = batcher.flush(Instant::now()) {
+        live.append(turn, &rest);
+    }
+    result
+}
+
+// ── Shared streaming completion (no tools) ──
+
+/// POST a streaming completion request and pass each text delta to `emit`.
+/// Returns the assembled reply once the stream closes.
+async fn stream_completion(
+    endpoint: &LlmEndpoint,
+    request_body: &Value,
+    mut emit: impl FnMut(StreamEvent),
+) -> Result<String, String> {
+    let mut response = streaming_client(endpoint.timeout())?
+        .post(endpoint.url())
+        .headers(endpoint.headers())
+        .json(request_body)
+        .send()
+        .await
+        .map_err(|e| request_error("Network error", e))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let error_text = response.text().await.map_err(|e| request_error("Read error", e))?;
+        return Err(map_api_error(endpoint.provider, status, &error_text));
+    }
+
+    let mut all_text = String::new();
+    let mut sse = SseParser::default();
+    let mut stream_done = false;
+
+    while !stream_done {
+        let events = match response.chunk().await.map_err(|e| request_error("Stream error", e))? {
+            Some(chunk) => sse.push(&chunk),
+            None => {
+                stream_done = true;
+                sse.finish()
+            }
+        };
+        for data in events {
+            push_delta_text(&data["choices"][0]["delta"], endpoint.inline_reasoning, &mut all_text, &mut emit);
         }
     }
 
     Ok(all_text)
 }
 
-// ── Non-streaming LLM call for simple one-shot generation (e.g. agent prompt creation) ──
+// ── One-shot generation (e.g. agent prompt creation) ──
 
-pub async fn call_llm_simple(
-    api_key: &str,
-    model: &str,
-    system_prompt: &str,
-    user_prompt: &str,
-) -> Result<String, String> {
-    let client = Client::new();
-    let request_body = json!({
+fn simple_request_body(model: &str, system_prompt: &str, user_prompt: &str) -> Value {
+    json!({
         "model": model,
         "messages": [
             {"role": "system", "content": system_prompt},
@@ -594,28 +1563,630 @@ pub async fn call_llm_simple(
         ],
         "temperature": 0.7,
         "max_tokens": 2048,
-    });
+        "stream": true,
+    })
+}
 
-    let response = client
-        .post(OPENROUTER_URL)
-        .headers(openrouter_headers(api_key))
-        .json(&request_body)
-        .send()
-        .await
-        .map_err(|e| format!("Network error: {}", e))?;
+pub async fn call_llm_simple(
+    endpoint: &LlmEndpoint,
+    model: &str,
+    system_prompt: &str,
+    user_prompt: &str,
+) -> Result<String, String> {
+    let request_body = simple_request_body(model, system_prompt, user_prompt);
+    let text = stream_completion(endpoint, &request_body, |_| {}).await?;
+    if text.is_empty() {
+        return Err("No content in LLM response".to_string());
+    }
+    Ok(text)
+}
 
-    let status = response.status();
-    let body = response.text().await.map_err(|e| format!("Read error: {}", e))?;
+/// `call_llm_simple`, forwarding tokens to the frontend as they arrive.
+pub async fn call_llm_simple_streaming(
+    endpoint: &LlmEndpoint,
+    model: &str,
+    system_prompt: &str,
+    user_prompt: &str,
+    on_event: &Channel<StreamEvent>,
+) -> Result<String, String> {
+    let request_body = simple_request_body(model, system_prompt, user_prompt);
+    let text = stream_completion(endpoint, &request_body, |event| {
+        let _ = on_event.send(event);
+    })
+    .await?;
+    if text.is_empty() {
+        return Err("No content in LLM response".to_string());
+    }
+    Ok(text)
+}
 
-    if !status.is_success() {
-        return Err(map_api_error(status, &body));
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unit_service_health_reports_each_probe_outcome() {
+        assert_eq!(
+            service_health("OpenRouter", None),
+            ServiceHealth { configured: false, valid: false, message: "No OpenRouter API key saved".to_string() }
+        );
+
+        let ok = service_health("OpenRouter", Some(Ok((200, r#"{"data":{"label":"sk-or-v1-abc"}}"#.to_string()))));
+        assert!(ok.configured && ok.valid);
+        assert_eq!(ok.message, "OpenRouter key is valid");
+
+        let rejected = service_health("ElevenLabs", Some(Ok((401, "invalid_api_key".to_string()))));
+        assert!(rejected.configured && !rejected.valid);
+        assert_eq!(rejected.message, "ElevenLabs rejected the key");
+
+        let server_error = service_health("ElevenLabs", Some(Ok((503, "x".repeat(500)))));
+        assert!(!server_error.valid);
+        assert!(server_error.message.starts_with("ElevenLabs returned 503: "));
+        assert!(server_error.message.len() < 250, "body is truncated");
+
+        let offline = service_health("OpenRouter", Some(Err("Request failed: dns error".to_string())));
+        assert!(offline.configured && !offline.valid);
+        assert_eq!(offline.message, "Could not reach OpenRouter: Request failed: dns error");
     }
 
-    let data: Value = serde_json::from_str(&body)
-        .map_err(|e| format!("JSON parse error: {}", e))?;
+    #[test]
+    fn integration_summary_title_retitles_decision_and_conversation() {
+        let db = crate::db::Database::new(":memory:").expect("in-memory database should initialize");
+        let conv = db.create_conversation_with_type("Should I quit?", "decision").unwrap();
+        let decision = db.create_decision(&conv.id, "Should I quit?").unwrap();
+
+        let input = json!({"title": "  Go freelance or take the Berlin offer?  ", "options": []});
+        let retitled = retitle_decision(&db, &decision.id, &input).expect("retitle should succeed");
+        assert_eq!(retitled.as_deref(), Some("Go freelance or take the Berlin offer?"));
+        assert_eq!(db.get_decision(&decision.id).unwrap().unwrap().title, "Go freelance or take the Berlin offer?");
+        assert_eq!(db.get_conversation(&conv.id).unwrap().unwrap().title, "Go freelance or take the Berlin offer?");
+
+        assert_eq!(retitle_decision(&db, &decision.id, &input).unwrap(), None, "unchanged title is a no-op");
+        assert_eq!(retitle_decision(&db, &decision.id, &json!({"options": []})).unwrap(), None);
+        assert_eq!(retitle_decision(&db, &decision.id, &json!({"title": "   "})).unwrap(), None);
+        assert_eq!(retitle_decision(&db, &decision.id, &json!({"title": "x".repeat(200)})).unwrap(), None);
+        assert!(retitle_decision(&db, "missing", &json!({"title": "New"})).is_err());
+    }
 
-    data["choices"][0]["message"]["content"]
-        .as_str()
-        .map(|s| s.to_string())
-        .ok_or_else(|| "No content in LLM response".to_string())
+    #[tokio::test]
+    async fn integration_http_client_applies_configured_timeout() {
+        // A server that accepts the connection but never answers.
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("listener should bind");
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            let _held: Vec<_> = listener.incoming().take(1).collect();
+            std::thread::sleep(Duration::from_secs(5));
+        });
+
+        let started = Instant::now();
+        let err = http_client()
+            .get(format!("http://{}", addr))
+            .timeout(Duration::from_secs(1))
+            .send()
+            .await
+            .expect_err("hung server should time out");
+        assert!(err.is_timeout());
+        assert!(started.elapsed() < Duration::from_secs(4));
+        assert_eq!(request_error("Network error", err), TIMEOUT_MESSAGE);
+    }
+
+    #[tokio::test]
+    async fn integration_call_llm_simple_assembles_streamed_sse_reply() {
+        use std::io::{Read, Write};
+
+        // A stub provider that reads the request, then streams an SSE body in two
+        // writes with an event split across them.
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("listener should bind");
+        let addr = listener.local_addr().unwrap();
+        let server = std::thread::spawn(move || {
+            let (mut socket, _) = listener.accept().expect("client should connect");
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            let body_len = loop {
+                let n = socket.read(&mut buf).expect("request should be readable");
+                request.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&request).to_string();
+                if let Some(end) = text.find("\r\n\r\n") {
+                    let content_length = text[..end]
+                        .lines()
+                        .find_map(|l| l.to_ascii_lowercase().strip_prefix("content-length:").map(|v| v.trim().parse::<usize>().unwrap()))
+                        .unwrap_or(0);
+                    if request.len() >= end + 4 + content_length {
+                        break content_length;
+                    }
+                }
+            };
+            let text = String::from_utf8_lossy(&request).to_string();
+            let body = text[text.len() - body_len..].to_string();
+
+            socket
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nConnection: close\r\n\r\n")
+                .unwrap();
+            socket
+                .write_all(b"data: {\"choices\":[{\"delta\":{\"content\":\"Hello\"}}]}\n\ndata: {\"choices\":[{\"de")
+                .unwrap();
+            socket.flush().unwrap();
+            std::thread::sleep(Duration::from_millis(50));
+            socket
+                .write_all(b"lta\":{\"content\":\", council\"}}]}\n\n: keep-alive\n\ndata: [DONE]\n\n")
+                .unwrap();
+            body
+        });
+
+        let endpoint = LlmEndpoint {
+            provider: LlmProvider::Ollama,
+            api_key: String::new(),
+            url: format!("http://{}/v1/chat/completions", addr),
+            timeout_secs: 5,
+            inline_reasoning: false,
+            models_without_tools: Vec::new(),
+        };
+        let text = call_llm_simple(&endpoint, "stub-model", "You are terse.", "Say hello")
+            .await
+            .expect("streamed reply should be assembled");
+        assert_eq!(text, "Hello, council");
+
+        let sent: Value = serde_json::from_str(&server.join().unwrap()).expect("request body should be JSON");
+        assert_eq!(sent["stream"], true);
+        assert_eq!(sent["model"], "stub-model");
+        assert_eq!(sent["messages"][1]["content"], "Say hello");
+    }
+
+    #[test]
+    fn unit_system_prompt_override_is_appended_to_outgoing_messages() {
+        let history = vec![json!({"role": "user", "content": "Should I move?"})];
+
+        let prompt = with_prompt_override(SYSTEM_PROMPT, Some("  Be extremely terse.  "));
+        let messages = outgoing_messages(&prompt, &history);
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0]["role"], "system");
+        let system = messages[0]["content"].as_str().unwrap();
+        assert!(system.starts_with(SYSTEM_PROMPT));
+        assert!(system.ends_with("Be extremely terse."));
+        assert_eq!(messages[1]["content"], "Should I move?");
+
+        assert_eq!(with_prompt_override(DECISION_SYSTEM_PROMPT, None), DECISION_SYSTEM_PROMPT);
+        assert_eq!(with_prompt_override(SYSTEM_PROMPT, Some("   ")), SYSTEM_PROMPT);
+    }
+
+    #[test]
+    fn unit_trim_history_drops_oldest_and_keeps_latest_user_message() {
+        let long = "x".repeat(400); // ~100 tokens
+        let mut history: Vec<Value> = (0..10)
+            .map(|i| {
+                let role = if i % 2 == 0 { "user" } else { "assistant" };
+                json!({"role": role, "content": format!("{}{}", i, long)})
+            })
+            .collect();
+        history.push(json!({"role": "user", "content": "latest question"}));
+
+        let dropped = trim_history_to_budget(&mut history, "sys", 350);
+        assert_eq!(dropped, 7);
+        assert_eq!(history.len(), 4);
+        assert!(history[0]["content"].as_str().unwrap().starts_with('7'));
+        assert_eq!(history.last().unwrap()["content"], "latest question");
+
+        // A budget too small for anything still keeps the latest user turn
+        let mut tiny = vec![
+            json!({"role": "user", "content": long.clone()}),
+            json!({"role": "assistant", "content": long.clone()}),
+            json!({"role": "user", "content": long.clone()}),
+        ];
+        assert_eq!(trim_history_to_budget(&mut tiny, "sys", 1), 2);
+        assert_eq!(tiny.len(), 1);
+        assert_eq!(tiny[0]["role"], "user");
+
+        let mut small = vec![json!({"role": "user", "content": "hi"})];
+        assert_eq!(trim_history_to_budget(&mut small, "sys", 1000), 0);
+    }
+
+    #[test]
+    fn integration_system_prompt_override_file_replaces_builtin() {
+        let dir = tempfile::tempdir().expect("temp directory should exist");
+        let app_data_dir = dir.path();
+
+        assert_eq!(base_system_prompt(app_data_dir, false), SYSTEM_PROMPT);
+        assert_eq!(base_system_prompt(app_data_dir, true), DECISION_SYSTEM_PROMPT);
+
+        std::fs::write(app_data_dir.join("system_prompt.md"), "Réponds en français.").unwrap();
+        assert_eq!(base_system_prompt(app_data_dir, false), "Réponds en français.");
+        assert_eq!(base_system_prompt(app_data_dir, true), DECISION_SYSTEM_PROMPT);
+
+        write_system_prompt(app_data_dir, "decision", "Decide carefully.").unwrap();
+        let prompts = get_system_prompts(app_data_dir);
+        assert_eq!(prompts.len(), 2);
+        assert!(prompts.iter().all(|p| !p.is_default));
+        assert_eq!(prompts[1].content, "Decide carefully.");
+        assert_eq!(prompts[1].default_content, DECISION_SYSTEM_PROMPT);
+
+        // Blank content resets to the built-in prompt
+        write_system_prompt(app_data_dir, "chat", "  ").unwrap();
+        assert!(!app_data_dir.join("system_prompt.md").exists());
+        assert_eq!(base_system_prompt(app_data_dir, false), SYSTEM_PROMPT);
+        assert!(write_system_prompt(app_data_dir, "debate", "x").is_err());
+    }
+
+    fn sse_tokens(events: &[Value]) -> String {
+        events
+            .iter()
+            .filter_map(|e| e["choices"][0]["delta"]["content"].as_str())
+            .collect()
+    }
+
+    #[test]
+    fn unit_sse_parser_handles_chunk_boundaries_and_multiline_events() {
+        let stream = concat!(
+            ": OPENROUTER PROCESSING\n\n",
+            "data: {\"choices\":[{\"delta\":{\"content\":\"Hel\"}}]}\n\n",
+            "data: {\"choices\":[{\"delta\":\n",
+            "data: {\"content\":\"lo \"}}]}\n\n",
+            "event: message\n",
+            "data: {\"choices\":[{\"delta\":{\"content\":\"wörld\"}}]}\n\n",
+            "data: [DONE]\n\n",
+        );
+
+        // Feed in awkward 7-byte slices, splitting lines and the multi-byte 'ö'
+        let mut parser = SseParser::default();
+        let mut events = Vec::new();
+        for chunk in stream.as_bytes().chunks(7) {
+            events.extend(parser.push(chunk));
+        }
+        events.extend(parser.finish());
+
+        assert_eq!(events.len(), 3);
+        assert_eq!(sse_tokens(&events), "Hello wörld");
+    }
+
+    #[test]
+    fn unit_sse_parser_handles_crlf_and_missing_separators() {
+        let mut parser = SseParser::default();
+        let crlf = "data: {\"choices\":[{\"delta\":{\"content\":\"a\"}}]}\r\n\r\n:keep-alive\r\n\r\ndata:{\"choices\":[{\"delta\":{\"content\":\"b\"}}]}\r\n\r\n";
+        let events = parser.push(crlf.as_bytes());
+        assert_eq!(sse_tokens(&events), "ab");
+
+        // Back-to-back data lines with no blank line between, and no trailing newline
+        let mut parser = SseParser::default();
+        let squashed = "data: {\"choices\":[{\"delta\":{\"content\":\"x\"}}]}\ndata: {\"choices\":[{\"delta\":{\"content\":\"y\"}}]}";
+        let mut events = parser.push(squashed.as_bytes());
+        assert!(events.is_empty());
+        events.extend(parser.finish());
+        assert_eq!(sse_tokens(&events), "xy");
+    }
+
+    #[test]
+    fn unit_streamed_tokens_are_sent_over_channel() {
+        let sent = std::sync::Arc::new(Mutex::new(Vec::<Value>::new()));
+        let sink = sent.clone();
+        let channel: Channel<StreamEvent> = Channel::new(move |body| {
+            sink.lock().unwrap().push(body.deserialize()?);
+            Ok(())
+        });
+
+        let mut parser = SseParser::default();
+        let mut events = parser.push(concat!(
+            "data: {\"choices\":[{\"delta\":{\"reasoning\":\"Draft a skeptic.\"}}]}\n\n",
+            "data: {\"choices\":[{\"delta\":{\"content\":\"You are \"}}]}\n\n",
+            "data: {\"choices\":[{\"delta\":{\"content\":\"the Skeptic.\"}}]}\n\ndata: [DONE]\n\n",
+        ).as_bytes());
+        events.extend(parser.finish());
+
+        let mut text = String::new();
+        for data in &events {
+            push_delta_text(&data["choices"][0]["delta"], false, &mut text, &mut |event| {
+                let _ = channel.send(event);
+            });
+        }
+
+        assert_eq!(text, "You are the Skeptic.");
+        let sent = sent.lock().unwrap();
+        assert_eq!(sent.len(), 3);
+        assert_eq!(sent[0], json!({"type": "reasoning", "token": "Draft a skeptic."}));
+        assert_eq!(sent[1], json!({"type": "token", "token": "You are "}));
+        assert_eq!(sent[2], json!({"type": "token", "token": "the Skeptic."}));
+
+        let mut inline = String::new();
+        push_delta_text(&events[0]["choices"][0]["delta"], true, &mut inline, &mut |_| {});
+        assert_eq!(inline, "Draft a skeptic.");
+    }
+
+    #[test]
+    fn unit_delta_reasoning_reads_both_field_names() {
+        let mut parser = SseParser::default();
+        let events = parser.push(concat!(
+            "data: {\"choices\":[{\"delta\":{\"role\":\"assistant\",\"content\":\"\",\"reasoning\":\"Weigh the rent first.\"}}]}\n\n",
+            "data: {\"choices\":[{\"delta\":{\"reasoning_content\":\"Then the commute.\"}}]}\n\n",
+            "data: {\"choices\":[{\"delta\":{\"content\":\"Move.\",\"reasoning\":null}}]}\n\n",
+        ).as_bytes());
+        let deltas: Vec<&Value> = events.iter().map(|e| &e["choices"][0]["delta"]).collect();
+
+        assert_eq!(delta_reasoning(deltas[0]), Some("Weigh the rent first."));
+        assert_eq!(deltas[0]["content"], "");
+        assert_eq!(delta_reasoning(deltas[1]), Some("Then the commute."));
+        assert_eq!(delta_reasoning(deltas[2]), None);
+        assert_eq!(deltas[2]["content"], "Move.");
+
+        let event = serde_json::to_value(StreamEvent::Reasoning { token: "hmm".to_string() }).unwrap();
+        assert_eq!(event, json!({"type": "reasoning", "token": "hmm"}));
+    }
+
+    #[test]
+    fn unit_http_client_is_constructed_once() {
+        assert!(std::ptr::eq(http_client(), http_client()));
+    }
+
+    #[test]
+    fn unit_token_batcher_coalesces_until_window_or_size() {
+        let start = Instant::now();
+        let ms = Duration::from_millis;
+
+        let mut passthrough = TokenBatcher::new(Duration::ZERO, start);
+        assert_eq!(passthrough.push("Hi", start), Some("Hi".to_string()));
+        assert_eq!(passthrough.flush(start), None);
+
+        let mut batcher = TokenBatcher::new(ms(30), start);
+        assert_eq!(batcher.push("Go ", start + ms(5)), None);
+        assert_eq!(batcher.push("with ", start + ms(20)), None);
+        assert_eq!(batcher.push("B", start + ms(31)), Some("Go with B".to_string()));
+        assert_eq!(batcher.push(".", start + ms(40)), None);
+
+        let long = "x".repeat(TOKEN_BATCH_MAX_BYTES);
+        assert_eq!(batcher.push(&long, start + ms(45)), Some(format!(".{}", long)));
+
+        // A buffered batch falls due one window after the last flush
+        assert_eq!(batcher.deadline(), None);
+        assert_eq!(batcher.push(" Done", start + ms(50)), None);
+        assert_eq!(batcher.deadline(), Some(start + ms(45) + ms(30)));
+
+        // The final flush sends whatever is left
+        assert_eq!(batcher.flush(start + ms(55)), Some(" Done".to_string()));
+        assert_eq!(batcher.flush(start + ms(60)), None);
+    }
+
+    #[test]
+    fn unit_live_buffers_keep_parallel_turns_apart() {
+        let turn = |agent_key: &'static str, round_number: i32| DebateTurnRef {
+            decision_id: "d1",
+            agent_key,
+            round_number,
+            exchange_number: 1,
+        };
+        let buffers = LiveBuffers::default();
+        buffers.begin(turn("rationalist", 1));
+        buffers.begin(turn("advocate", 1));
+        for token in ["Run ", "the ", "numbers."] {
+            buffers.append(turn("rationalist", 1), token);
+        }
+        buffers.append(turn("advocate", 1), "Go.");
+        buffers.append(DebateTurnRef { decision_id: "d2", ..turn("advocate", 1) }, "Elsewhere.");
+
+        let shared = buffers.clone();
+        assert_eq!(
+            shared.for_decision("d1"),
+            vec![
+                LiveBuffer { agent: "advocate".to_string(), round: 1, exchange: 1, text: "Go.".to_string() },
+                LiveBuffer { agent: "rationalist".to_string(), round: 1, exchange: 1, text: "Run the numbers.".to_string() },
+            ]
+        );
+
+        // A retry starts the turn over
+        buffers.begin(turn("rationalist", 1));
+        buffers.append(turn("rationalist", 1), "Retry.");
+        assert_eq!(buffers.for_decision("d1")[1].text, "Retry.");
+
+        // Clearing one turn leaves the others; a stale turn is a no-op
+        buffers.clear(turn("rationalist", 2));
+        buffers.clear(turn("rationalist", 1));
+        assert_eq!(buffers.for_decision("d1").len(), 1);
+        buffers.clear_decision("d1");
+        assert!(buffers.for_decision("d1").is_empty());
+        assert_eq!(buffers.for_decision("d2").len(), 1);
+    }
+
+    #[test]
+    fn unit_model_list_cache_answers_until_expiry() {
+        let fetched_at = Instant::now();
+        let model = |id: &str| ModelInfo {
+            id: id.to_string(),
+            name: id.to_string(),
+            context_length: None,
+            prompt_price_per_million: None,
+            completion_price_per_million: None,
+            is_free: false,
+        };
+        let cache = ModelListCache::new(
+            vec![model("openai/gpt-4o"), model("anthropic/claude-sonnet-4")],
+            fetched_at,
+        );
+
+        assert_eq!(cache.lookup("openai/gpt-4o", fetched_at), Some(true));
+        assert_eq!(cache.lookup(" anthropic/claude-sonnet-4 ", fetched_at), Some(true));
+        assert_eq!(cache.lookup("openai/gpt-4-turbo-typo", fetched_at), Some(false));
+        assert_eq!(
+            cache.lookup("openai/gpt-4o", fetched_at + MODEL_LIST_TTL + Duration::from_secs(1)),
+            None
+        );
+        assert_eq!(cache.models(fetched_at).map(|m| m.len()), Some(2));
+        assert!(cache.models(fetched_at + MODEL_LIST_TTL + Duration::from_secs(1)).is_none());
+    }
+
+    #[test]
+    fn unit_parse_models_response_reads_pricing_and_sorts_by_name() {
+        let body = r#"{"data": [
+            {"id": "openai/gpt-4o", "name": "OpenAI: GPT-4o", "context_length": 128000,
+             "pricing": {"prompt": "0.0000025", "completion": "0.00001"}},
+            {"id": "meta-llama/llama-3.3-70b-instruct:free", "context_length": 131072,
+             "pricing": {"prompt": "0", "completion": "0"}},
+            {"id": "anthropic/claude-sonnet-4-5", "name": "anthropic: Claude Sonnet 4.5"}
+        ]}"#;
+
+        let models = parse_models_response(body).expect("sample response should parse");
+        let ids: Vec<&str> = models.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec!["anthropic/claude-sonnet-4-5", "meta-llama/llama-3.3-70b-instruct:free", "openai/gpt-4o"]
+        );
+
+        let gpt = &models[2];
+        assert_eq!(gpt.context_length, Some(128000));
+        assert!((gpt.prompt_price_per_million.unwrap() - 2.5).abs() < 1e-9);
+        assert!((gpt.completion_price_per_million.unwrap() - 10.0).abs() < 1e-9);
+        assert!(!gpt.is_free);
+
+        let llama = &models[1];
+        assert_eq!(llama.name, llama.id, "missing name falls back to id");
+        assert!(llama.is_free);
+        assert!(models[0].is_free, "no pricing counts as free");
+
+        assert!(parse_models_response("{\"models\": []}").is_err());
+        assert!(fallback_models().iter().any(|m| m.id == "anthropic/claude-sonnet-4-5"));
+    }
+
+    #[test]
+    fn unit_request_bodies_use_configured_max_tokens() {
+        let messages = vec![json!({"role": "user", "content": "hi"})];
+        let chat = chat_request_body("test/model", &messages, Some(get_tools(false)), 8192, CHAT_TEMPERATURE);
+        assert_eq!(chat["max_tokens"], 8192);
+        assert_eq!(chat["model"], "test/model");
+        assert!(chat["tools"].is_array());
+        assert_eq!(chat["temperature"], CHAT_TEMPERATURE);
+
+        let regenerated = chat_request_body("test/model", &messages, None, 8192, REGENERATE_TEMPERATURE);
+        assert_eq!(regenerated["temperature"], REGENERATE_TEMPERATURE);
+
+        let debate = debate_request_body("test/model", "system", "user", 512, 1.25);
+        assert_eq!(debate["max_tokens"], 512);
+        assert_eq!(debate["temperature"], 1.25);
+        assert_eq!(debate["messages"][0]["content"], "system");
+        assert_eq!(debate["messages"][1]["content"], "user");
+    }
+
+    #[test]
+    fn unit_debate_token_payload_carries_moderator_agent_key() {
+        let payload = debate_token_payload("dec-1", 99, 1, "moderator", "Recommend");
+        assert_eq!(payload["agent"], "moderator");
+        assert_eq!(payload["round_number"], 99);
+        assert_eq!(payload["decision_id"], "dec-1");
+        assert_eq!(payload["token"], "Recommend");
+    }
+
+    #[test]
+    fn unit_model_for_provider_maps_ids_onto_openai() {
+        let openai = LlmProvider::OpenAi;
+        assert_eq!(openai.model_for_provider("openai/gpt-4o"), "gpt-4o");
+        assert_eq!(openai.model_for_provider("anthropic/claude-sonnet-4-5"), OPENAI_DEFAULT_MODEL);
+        assert_eq!(openai.model_for_provider("gpt-4.1"), "gpt-4.1");
+        assert_eq!(openai.model_for_provider(""), "");
+        assert_eq!(
+            LlmProvider::OpenRouter.model_for_provider("anthropic/claude-sonnet-4-5"),
+            "anthropic/claude-sonnet-4-5"
+        );
+        assert_eq!(LlmProvider::Ollama.model_for_provider("llama3.2"), "llama3.2");
+    }
+
+    #[test]
+    fn unit_endpoint_selects_url_headers_and_key_per_provider() {
+        let mut config = AppConfig {
+            openrouter_api_key: "sk-or".to_string(),
+            openai_api_key: "sk-oa".to_string(),
+            ..AppConfig::default()
+        };
+
+        let openrouter = LlmEndpoint::from_config(&config);
+        assert_eq!(openrouter.provider, LlmProvider::OpenRouter);
+        assert_eq!(openrouter.url(), OPENROUTER_URL);
+        let headers = openrouter.headers();
+        assert_eq!(headers["Authorization"], "Bearer sk-or");
+        assert!(headers.contains_key("HTTP-Referer"));
+        assert!(headers.contains_key("X-Title"));
+
+        config.llm_provider = "openai".to_string();
+        let openai = LlmEndpoint::from_config(&config);
+        assert_eq!(openai.provider, LlmProvider::OpenAi);
+        assert_eq!(openai.url(), "https://api.openai.com/v1/chat/completions");
+        let headers = openai.headers();
+        assert_eq!(headers["Authorization"], "Bearer sk-oa");
+        assert!(!headers.contains_key("HTTP-Referer"));
+        assert!(!headers.contains_key("X-Title"));
+
+        config.openai_api_key.clear();
+        let err = LlmEndpoint::from_config(&config).require_api_key().unwrap_err();
+        assert!(err.contains("OpenAI"));
+    }
+
+    #[test]
+    fn unit_models_without_tools_get_action_block_prompt() {
+        let config = AppConfig {
+            openrouter_api_key: "sk-or".to_string(),
+            models_without_tools: vec!["meta-llama/llama-3-8b-instruct".to_string()],
+            ..AppConfig::default()
+        };
+        let endpoint = LlmEndpoint::from_config(&config);
+        assert!(endpoint.tools_enabled("anthropic/claude-sonnet-4-5"));
+        assert!(!endpoint.tools_enabled("meta-llama/llama-3-8b-instruct"));
+        let ollama = LlmEndpoint::from_config(&AppConfig { llm_provider: "ollama".to_string(), ..AppConfig::default() });
+        assert!(!ollama.tools_enabled("llama3.1"));
+
+        let profiles = HashMap::from([("career.md".to_string(), "# Career\n- Engineer".to_string())]);
+        let prompt = tool_less_system_prompt(DECISION_SYSTEM_PROMPT, true, &profiles);
+        assert!(prompt.starts_with(DECISION_SYSTEM_PROMPT));
+        assert!(prompt.contains("#### career.md\n# Career\n- Engineer"));
+        assert!(prompt.contains(ACTION_BLOCK_FENCE));
+        assert!(prompt.contains("update_decision_summary"));
+        assert!(!tool_less_system_prompt(SYSTEM_PROMPT, false, &HashMap::new()).contains("update_decision_summary"));
+
+        let reply = "Noted, I'll remember that.\n\n```council-actions\n{\"write_profile_files\": [{\"filename\": \"career.md\", \"content\": \"# Career\"}]}\n```\n";
+        let (text, actions) = split_action_block(reply);
+        assert_eq!(text, "Noted, I'll remember that.");
+        assert_eq!(actions.unwrap()["write_profile_files"][0]["filename"], "career.md");
+
+        let broken = "Sure.\n```council-actions\n{not json\n```";
+        assert_eq!(split_action_block(broken), (broken.to_string(), None));
+        assert_eq!(split_action_block("Plain reply."), ("Plain reply.".to_string(), None));
+    }
+
+    #[test]
+    fn unit_action_block_holdback_never_streams_the_block() {
+        let stream = |tokens: &[&str]| {
+            let mut holdback = ActionBlockHoldback::default();
+            let mut streamed: String = tokens.iter().filter_map(|t| holdback.push(t)).collect();
+            streamed.extend(holdback.finish());
+            streamed
+        };
+
+        // The fence split across tokens is held back, not leaked
+        let reply = ["Noted.\n\n``", "`council", "-actions\n{\"write_profile_files\"", ": []}\n```\n"];
+        assert_eq!(stream(&reply), "Noted.\n\n");
+
+        // A tail that looked like the fence start is released once it isn't
+        assert_eq!(stream(&["Use ``", "`rust` here."]), "Use ```rust` here.");
+        assert_eq!(stream(&["Ends with ``"]), "Ends with ``");
+
+        let mut holdback = ActionBlockHoldback::default();
+        assert_eq!(holdback.push("Plain "), Some("Plain ".to_string()));
+        assert_eq!(holdback.push("reply."), Some("reply.".to_string()));
+        assert_eq!(holdback.finish(), None);
+    }
+
+    #[test]
+    fn unit_ollama_endpoint_needs_no_key_and_omits_tools() {
+        let config = AppConfig {
+            llm_provider: "ollama".to_string(),
+            openrouter_api_key: "sk-or".to_string(),
+            ..AppConfig::default()
+        };
+        let endpoint = LlmEndpoint::from_config(&config);
+        assert_eq!(endpoint.provider, LlmProvider::Ollama);
+        assert_eq!(endpoint.url(), "http://localhost:11434/v1/chat/completions");
+        assert!(endpoint.require_api_key().is_ok());
+        let headers = endpoint.headers();
+        assert!(!headers.contains_key("Authorization"));
+        assert!(!headers.contains_key("HTTP-Referer"));
+
+        let messages = vec![json!({"role": "user", "content": "hi"})];
+        let tools = endpoint.provider.supports_tools().then(|| get_tools(true));
+        let body = chat_request_body("llama3.1", &messages, tools, 4096, CHAT_TEMPERATURE);
+        assert!(body.get("tools").is_none());
+        assert_eq!(body["model"], "llama3.1");
+        assert!(LlmProvider::OpenRouter.supports_tools());
+    }
 }
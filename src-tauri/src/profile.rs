@@ -1,7 +1,14 @@
+use crate::config;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+/// How many snapshots to keep per profile file when versioning is enabled.
+const MAX_PROFILE_VERSIONS: usize = 20;
+
+/// Deepest heading level profile files need; anything below renders as noise.
+const MAX_HEADING_DEPTH: usize = 3;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ProfileFileInfo {
@@ -9,12 +16,42 @@ pub struct ProfileFileInfo {
     pub content: String,
     pub modified_at: String,
     pub size_bytes: u64,
+    #[serde(default)]
+    pub word_count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProfileVersion {
+    pub timestamp: String,
+    pub size_bytes: u64,
+}
+
+/// A markdown problem found in a profile file. `line` is 1-based.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LintWarning {
+    pub line: usize,
+    pub kind: String,
+    pub message: String,
+}
+
+impl LintWarning {
+    fn new(line: usize, kind: &str, message: String) -> Self {
+        Self { line, kind: kind.to_string(), message }
+    }
 }
 
 pub fn get_profile_dir(app_data_dir: &PathBuf) -> PathBuf {
     app_data_dir.join("profile")
 }
 
+fn history_dir(app_data_dir: &PathBuf) -> PathBuf {
+    get_profile_dir(app_data_dir).join(".history")
+}
+
+fn version_path(history: &Path, filename: &str, timestamp: &str) -> PathBuf {
+    history.join(format!("{}.{}.md", filename, timestamp))
+}
+
 pub fn read_all_profiles(app_data_dir: &PathBuf) -> Result<HashMap<String, String>, String> {
     let dir = get_profile_dir(app_data_dir);
     if !dir.exists() {
@@ -35,15 +72,84 @@ pub fn read_all_profiles(app_data_dir: &PathBuf) -> Result<HashMap<String, Strin
     Ok(files)
 }
 
+/// Reject anything but a bare `.md` filename so callers can't escape the target directory.
+pub fn validate_md_filename(filename: &str) -> Result<(), String> {
+    let stem = filename.strip_suffix(".md").unwrap_or("");
+    if stem.is_empty()
+        || filename.contains('/')
+        || filename.contains('\\')
+        || filename.contains("..")
+    {
+        return Err("Invalid filename".to_string());
+    }
+    Ok(())
+}
+
 pub fn write_profile_file(app_data_dir: &PathBuf, filename: &str, content: &str) -> Result<String, String> {
+    validate_md_filename(filename)?;
     let dir = get_profile_dir(app_data_dir);
     fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
     let path = dir.join(filename);
-    fs::write(&path, content).map_err(|e| e.to_string())?;
+    if path.exists() && config::load_config(app_data_dir).profile_versioning {
+        snapshot_profile_file(app_data_dir, filename, &path)?;
+    }
+    config::write_atomic(&path, content.as_bytes())?;
     Ok(format!("Successfully wrote {}", filename))
 }
 
+/// Copy the current file into `.history/` and drop the oldest snapshots beyond the cap.
+fn snapshot_profile_file(app_data_dir: &PathBuf, filename: &str, path: &Path) -> Result<(), String> {
+    let history = history_dir(app_data_dir);
+    fs::create_dir_all(&history).map_err(|e| e.to_string())?;
+    let timestamp = chrono::Utc::now().format("%Y%m%dT%H%M%S%6fZ").to_string();
+    fs::copy(path, version_path(&history, filename, &timestamp)).map_err(|e| e.to_string())?;
+
+    let versions = list_profile_versions(app_data_dir, filename)?;
+    for old in versions.iter().skip(MAX_PROFILE_VERSIONS) {
+        let _ = fs::remove_file(version_path(&history, filename, &old.timestamp));
+    }
+    Ok(())
+}
+
+/// Snapshots of a profile file, newest first.
+pub fn list_profile_versions(app_data_dir: &PathBuf, filename: &str) -> Result<Vec<ProfileVersion>, String> {
+    validate_md_filename(filename)?;
+    let history = history_dir(app_data_dir);
+    if !history.exists() {
+        return Ok(Vec::new());
+    }
+    let prefix = format!("{}.", filename);
+    let mut versions = Vec::new();
+    for entry in fs::read_dir(&history).map_err(|e| e.to_string())? {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        let timestamp = match name.strip_prefix(&prefix).and_then(|rest| rest.strip_suffix(".md")) {
+            Some(ts) if !ts.is_empty() && !ts.contains('.') => ts.to_string(),
+            _ => continue,
+        };
+        let size_bytes = entry.metadata().map(|m| m.len()).unwrap_or(0);
+        versions.push(ProfileVersion { timestamp, size_bytes });
+    }
+    versions.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    Ok(versions)
+}
+
+/// Overwrite a profile file with one of its snapshots. The current content is
+/// itself snapshotted first (when versioning is on), so a restore can be undone.
+pub fn restore_profile_version(app_data_dir: &PathBuf, filename: &str, timestamp: &str) -> Result<String, String> {
+    validate_md_filename(filename)?;
+    if timestamp.is_empty() || !timestamp.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err("Invalid version timestamp".to_string());
+    }
+    let snapshot = version_path(&history_dir(app_data_dir), filename, timestamp);
+    let content = fs::read_to_string(&snapshot)
+        .map_err(|_| format!("No saved version {} for {}", timestamp, filename))?;
+    write_profile_file(app_data_dir, filename, &content)?;
+    Ok(content)
+}
+
 pub fn delete_profile_file(app_data_dir: &PathBuf, filename: &str) -> Result<String, String> {
+    validate_md_filename(filename)?;
     let dir = get_profile_dir(app_data_dir);
     let path = dir.join(filename);
     if path.exists() {
@@ -54,6 +160,11 @@ pub fn delete_profile_file(app_data_dir: &PathBuf, filename: &str) -> Result<Str
     }
 }
 
+/// Whitespace-separated word count, shown next to profile and agent prompt files.
+pub fn word_count(content: &str) -> usize {
+    content.split_whitespace().count()
+}
+
 pub fn read_all_profiles_detailed(app_data_dir: &PathBuf) -> Result<Vec<ProfileFileInfo>, String> {
     let dir = get_profile_dir(app_data_dir);
     if !dir.exists() {
@@ -76,6 +187,7 @@ pub fn read_all_profiles_detailed(app_data_dir: &PathBuf) -> Result<Vec<ProfileF
             let size_bytes = metadata.len();
             files.push(ProfileFileInfo {
                 filename,
+                word_count: word_count(&content),
                 content,
                 modified_at,
                 size_bytes,
@@ -86,6 +198,77 @@ pub fn read_all_profiles_detailed(app_data_dir: &PathBuf) -> Result<Vec<ProfileF
     Ok(files)
 }
 
+/// Check profile markdown for problems that render poorly in the viewer:
+/// empty files, unclosed code fences, overly deep headings and repeated headings.
+/// Headings inside code fences are ignored.
+pub fn lint_profile_markdown(content: &str) -> Vec<LintWarning> {
+    if content.trim().is_empty() {
+        return vec![LintWarning::new(1, "empty_file", "File is empty".to_string())];
+    }
+
+    let mut warnings = Vec::new();
+    let mut open_fence: Option<usize> = None;
+    let mut seen_headings: HashMap<String, usize> = HashMap::new();
+    for (index, line) in content.lines().enumerate() {
+        let line_number = index + 1;
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") {
+            open_fence = match open_fence {
+                Some(_) => None,
+                None => Some(line_number),
+            };
+            continue;
+        }
+        if open_fence.is_some() {
+            continue;
+        }
+
+        let depth = trimmed.chars().take_while(|c| *c == '#').count();
+        let rest = &trimmed[depth..];
+        if depth == 0 || !(rest.is_empty() || rest.starts_with(' ')) {
+            continue;
+        }
+        if depth > MAX_HEADING_DEPTH {
+            warnings.push(LintWarning::new(
+                line_number,
+                "deep_heading",
+                format!("Heading level {} is deeper than {}", depth, MAX_HEADING_DEPTH),
+            ));
+        }
+        let title = rest.trim().to_lowercase();
+        if title.is_empty() {
+            continue;
+        }
+        match seen_headings.get(&title) {
+            Some(first) => warnings.push(LintWarning::new(
+                line_number,
+                "duplicate_heading",
+                format!("Heading \"{}\" already appears on line {}", rest.trim(), first),
+            )),
+            None => {
+                seen_headings.insert(title, line_number);
+            }
+        }
+    }
+
+    if let Some(line_number) = open_fence {
+        warnings.push(LintWarning::new(
+            line_number,
+            "unclosed_fence",
+            "Code fence is never closed".to_string(),
+        ));
+    }
+    warnings.sort_by_key(|w| w.line);
+    warnings
+}
+
+pub fn validate_profile_file(app_data_dir: &PathBuf, filename: &str) -> Result<Vec<LintWarning>, String> {
+    validate_md_filename(filename)?;
+    let path = get_profile_dir(app_data_dir).join(filename);
+    let content = fs::read_to_string(&path).map_err(|_| format!("File {} does not exist", filename))?;
+    Ok(lint_profile_markdown(&content))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -116,6 +299,8 @@ mod tests {
         assert_eq!(detailed[0].filename, "career.md");
         assert_eq!(detailed[1].filename, "values.md");
         assert!(detailed[0].size_bytes > 0);
+        assert_eq!(detailed[0].word_count, 4);
+        assert_eq!(detailed[1].word_count, 4);
     }
 
     #[test]
@@ -131,4 +316,106 @@ mod tests {
         let deleted = delete_profile_file(&app_data_dir, "notes.md").expect("file should delete");
         assert_eq!(deleted, "Successfully deleted notes.md");
     }
+
+    #[test]
+    fn unit_profile_file_operations_reject_path_traversal() {
+        let dir = tempdir().expect("temp directory should exist");
+        let app_data_dir = dir.path().join("app");
+
+        for bad in ["../evil.md", "sub/dir.md", "sub\\dir.md", "notes.txt", ".md", ""] {
+            assert_eq!(
+                write_profile_file(&app_data_dir, bad, "x").unwrap_err(),
+                "Invalid filename",
+                "{bad:?} should be rejected"
+            );
+            assert_eq!(delete_profile_file(&app_data_dir, bad).unwrap_err(), "Invalid filename");
+        }
+        assert!(!dir.path().join("evil.md").exists());
+        assert!(validate_md_filename("values.md").is_ok());
+    }
+
+    #[test]
+    fn integration_profile_versioning_snapshots_and_restores() {
+        let dir = tempdir().expect("temp directory should exist");
+        let app_data_dir = dir.path().to_path_buf();
+
+        // Versioning is off by default: overwrites leave no history.
+        write_profile_file(&app_data_dir, "values.md", "v1").unwrap();
+        write_profile_file(&app_data_dir, "values.md", "v2").unwrap();
+        assert!(list_profile_versions(&app_data_dir, "values.md").unwrap().is_empty());
+
+        let cfg = config::AppConfig { profile_versioning: true, ..config::AppConfig::default() };
+        config::save_config(&app_data_dir, &cfg).unwrap();
+
+        write_profile_file(&app_data_dir, "values.md", "v3").unwrap();
+        let versions = list_profile_versions(&app_data_dir, "values.md").unwrap();
+        assert_eq!(versions.len(), 1);
+        assert_eq!(versions[0].size_bytes, 2);
+
+        // History files must not show up as profile files.
+        let profiles = read_all_profiles(&app_data_dir).unwrap();
+        assert_eq!(profiles.len(), 1);
+        assert_eq!(profiles.get("values.md").map(String::as_str), Some("v3"));
+
+        let restored = restore_profile_version(&app_data_dir, "values.md", &versions[0].timestamp).unwrap();
+        assert_eq!(restored, "v2");
+        let profiles = read_all_profiles(&app_data_dir).unwrap();
+        assert_eq!(profiles.get("values.md").map(String::as_str), Some("v2"));
+        assert_eq!(list_profile_versions(&app_data_dir, "values.md").unwrap().len(), 2);
+
+        assert!(restore_profile_version(&app_data_dir, "values.md", "../x").is_err());
+        assert!(restore_profile_version(&app_data_dir, "values.md", "19990101T000000000000Z").is_err());
+    }
+
+    #[test]
+    fn unit_profile_versions_are_capped_per_file() {
+        let dir = tempdir().expect("temp directory should exist");
+        let app_data_dir = dir.path().to_path_buf();
+        let history = history_dir(&app_data_dir);
+        fs::create_dir_all(&history).unwrap();
+        for i in 0..MAX_PROFILE_VERSIONS + 3 {
+            fs::write(version_path(&history, "career.md", &format!("20260101T0000{:02}000000Z", i)), "old").unwrap();
+        }
+        fs::write(version_path(&history, "values.md", "20260101T000000000000Z"), "other").unwrap();
+
+        let cfg = config::AppConfig { profile_versioning: true, ..config::AppConfig::default() };
+        config::save_config(&app_data_dir, &cfg).unwrap();
+        write_profile_file(&app_data_dir, "career.md", "current").unwrap();
+        write_profile_file(&app_data_dir, "career.md", "next").unwrap();
+
+        let versions = list_profile_versions(&app_data_dir, "career.md").unwrap();
+        assert_eq!(versions.len(), MAX_PROFILE_VERSIONS);
+        assert!(versions[0].timestamp > versions[1].timestamp);
+        assert_eq!(list_profile_versions(&app_data_dir, "values.md").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn unit_lint_flags_unclosed_fence_and_heading_problems() {
+        let content = "# Career\n\n```\n# not a heading\n```\n\n#### Details\n# Career\n\n```python\nprint(1)\n";
+        let warnings = lint_profile_markdown(content);
+        let kinds: Vec<(usize, &str)> = warnings.iter().map(|w| (w.line, w.kind.as_str())).collect();
+        assert_eq!(
+            kinds,
+            vec![(7, "deep_heading"), (8, "duplicate_heading"), (10, "unclosed_fence")]
+        );
+        assert!(warnings[1].message.contains("line 1"));
+
+        assert_eq!(lint_profile_markdown("  \n\n")[0].kind, "empty_file");
+    }
+
+    #[test]
+    fn integration_validate_profile_file_passes_clean_file() {
+        let dir = tempdir().expect("temp directory should exist");
+        let app_data_dir = dir.path().to_path_buf();
+        write_profile_file(
+            &app_data_dir,
+            "career.md",
+            "# Career\n## Current role\n- Engineer\n\n```\n#hashtag\n```\n## Goals\n- Lead a team",
+        )
+        .unwrap();
+
+        assert!(validate_profile_file(&app_data_dir, "career.md").unwrap().is_empty());
+        assert!(validate_profile_file(&app_data_dir, "missing.md").is_err());
+        assert!(validate_profile_file(&app_data_dir, "../career.md").is_err());
+    }
 }
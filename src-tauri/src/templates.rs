@@ -0,0 +1,256 @@
+use crate::db::{Database, Decision};
+use crate::decisions;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::fs;
+use std::path::PathBuf;
+
+/// An option a template pre-seeds into a new decision's summary.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemplateOption {
+    pub label: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+/// A variable a template pre-seeds, usually with an empty value for the user to fill in.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemplateVariable {
+    pub label: String,
+    #[serde(default)]
+    pub value: String,
+    #[serde(default = "default_impact")]
+    pub impact: String,
+}
+
+fn default_impact() -> String {
+    "medium".to_string()
+}
+
+/// A recurring kind of decision (job offer, big purchase) with its usual options and variables.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DecisionTemplate {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub options: Vec<TemplateOption>,
+    #[serde(default)]
+    pub variables: Vec<TemplateVariable>,
+}
+
+fn option(label: &str, description: &str) -> TemplateOption {
+    TemplateOption { label: label.into(), description: description.into() }
+}
+
+fn variable(label: &str, impact: &str) -> TemplateVariable {
+    TemplateVariable { label: label.into(), value: String::new(), impact: impact.into() }
+}
+
+// ── Built-in templates ──
+
+pub fn builtin_templates() -> Vec<DecisionTemplate> {
+    vec![
+        DecisionTemplate {
+            name: "job_offer".into(),
+            description: "Weigh a new job offer against staying put.".into(),
+            options: vec![
+                option("Accept the offer", "Take the new role"),
+                option("Stay in current role", "Keep the current job"),
+                option("Negotiate", "Counter before deciding"),
+            ],
+            variables: vec![
+                variable("Compensation difference", "high"),
+                variable("Growth opportunity", "high"),
+                variable("Commute and location", "medium"),
+                variable("Team and manager", "medium"),
+            ],
+        },
+        DecisionTemplate {
+            name: "major_purchase".into(),
+            description: "Decide whether and how to make a large purchase.".into(),
+            options: vec![
+                option("Buy now", "Make the purchase at today's price"),
+                option("Wait", "Hold off and save or watch prices"),
+                option("Buy a cheaper alternative", "Meet the need for less"),
+            ],
+            variables: vec![
+                variable("Total cost", "high"),
+                variable("Savings after purchase", "high"),
+                variable("How often it will be used", "medium"),
+                variable("Ongoing costs", "medium"),
+            ],
+        },
+        DecisionTemplate {
+            name: "relocation".into(),
+            description: "Consider moving to a new city.".into(),
+            options: vec![
+                option("Move", "Relocate to the new city"),
+                option("Stay", "Remain where you are"),
+            ],
+            variables: vec![
+                variable("Cost of living", "high"),
+                variable("Distance from family and friends", "high"),
+                variable("Career prospects", "medium"),
+            ],
+        },
+    ]
+}
+
+// ── Template file ──
+
+fn templates_path(app_data_dir: &PathBuf) -> PathBuf {
+    app_data_dir.join("decision_templates.json")
+}
+
+/// The templates on disk, or `Ok(None)` if the file hasn't been created yet.
+/// A file that exists but can't be read or parsed is an error.
+fn read_templates_file(app_data_dir: &PathBuf) -> Result<Option<Vec<DecisionTemplate>>, String> {
+    let path = templates_path(app_data_dir);
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+    };
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("{} is not valid template JSON ({}). Fix or remove it before saving templates.", path.display(), e))
+}
+
+/// Load the editable template file, seeding it with the built-ins on first use.
+/// A corrupt file falls back to the built-ins without overwriting the user's edits.
+pub fn load_templates(app_data_dir: &PathBuf) -> Vec<DecisionTemplate> {
+    match read_templates_file(app_data_dir) {
+        Ok(Some(templates)) => templates,
+        Ok(None) => {
+            let templates = builtin_templates();
+            let _ = write_templates(app_data_dir, &templates);
+            templates
+        }
+        Err(_) => builtin_templates(),
+    }
+}
+
+fn write_templates(app_data_dir: &PathBuf, templates: &[DecisionTemplate]) -> Result<(), String> {
+    fs::create_dir_all(app_data_dir).map_err(|e| e.to_string())?;
+    let content = serde_json::to_string_pretty(templates).map_err(|e| e.to_string())?;
+    crate::config::write_atomic(&templates_path(app_data_dir), content.as_bytes())
+}
+
+/// Add a template, or replace the one with the same name. Refuses to write
+/// over a templates file that can't be parsed, so hand edits aren't lost.
+pub fn save_template(app_data_dir: &PathBuf, template: DecisionTemplate) -> Result<DecisionTemplate, String> {
+    let name = template.name.trim();
+    if name.is_empty() {
+        return Err("Template name cannot be empty".to_string());
+    }
+    if template.options.iter().any(|o| o.label.trim().is_empty())
+        || template.variables.iter().any(|v| v.label.trim().is_empty())
+    {
+        return Err("Template options and variables need a label".to_string());
+    }
+    let template = DecisionTemplate { name: name.to_string(), ..template };
+
+    let mut templates = read_templates_file(app_data_dir)?.unwrap_or_else(builtin_templates);
+    match templates.iter_mut().find(|t| t.name == template.name) {
+        Some(existing) => *existing = template.clone(),
+        None => templates.push(template.clone()),
+    }
+    write_templates(app_data_dir, &templates)?;
+    Ok(template)
+}
+
+pub fn find_template(app_data_dir: &PathBuf, name: &str) -> Result<DecisionTemplate, String> {
+    load_templates(app_data_dir)
+        .into_iter()
+        .find(|t| t.name == name)
+        .ok_or_else(|| format!("Template '{}' not found", name))
+}
+
+/// Summary JSON with the template's options and variables, in the same shape
+/// `update_decision_summary` produces.
+pub fn template_summary_json(template: &DecisionTemplate) -> String {
+    decisions::merge_summary(None, &json!({
+        "options": template.options,
+        "variables": template.variables,
+    }))
+}
+
+/// Create a decision (and its conversation) pre-seeded from a template.
+/// The template is resolved first, so an unknown name creates nothing, and the
+/// rows are written in one transaction.
+pub fn create_decision_from_template(
+    db: &Database,
+    app_data_dir: &PathBuf,
+    title: &str,
+    template_name: &str,
+) -> Result<Decision, String> {
+    let template = find_template(app_data_dir, template_name)?;
+    let summary_json = template_summary_json(&template);
+    db.create_decision_with_summary(title, &summary_json).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use tempfile::tempdir;
+
+    #[test]
+    fn integration_create_decision_from_template_seeds_summary() {
+        let dir = tempdir().expect("temp directory should exist");
+        let app_data_dir = dir.path().to_path_buf();
+        let db = Database::new(":memory:").expect("in-memory database should initialize");
+
+        let decision = create_decision_from_template(&db, &app_data_dir, "Offer from Acme", "job_offer")
+            .expect("decision should be created from template");
+        assert_eq!(decision.title, "Offer from Acme");
+        let summary: Value = serde_json::from_str(decision.summary_json.as_deref().unwrap()).unwrap();
+        assert_eq!(summary["options"].as_array().unwrap().len(), 3);
+        assert_eq!(summary["options"][0]["label"], "Accept the offer");
+        assert_eq!(summary["variables"][0]["label"], "Compensation difference");
+        assert_eq!(summary["variables"][0]["impact"], "high");
+        assert!(templates_path(&app_data_dir).exists(), "built-ins should be seeded to disk");
+        let conv = db.get_conversation(&decision.conversation_id).unwrap().expect("conversation should exist");
+        assert_eq!(conv.conv_type, "decision");
+        assert!(!db.undo_summary_update(&decision.id).unwrap(), "the seeded summary has no earlier version");
+
+        assert_eq!(
+            create_decision_from_template(&db, &app_data_dir, "Mystery", "nope").unwrap_err(),
+            "Template 'nope' not found"
+        );
+        assert_eq!(db.get_decisions().unwrap().len(), 1, "unknown template should create nothing");
+    }
+
+    #[test]
+    fn integration_save_template_adds_and_replaces_by_name() {
+        let dir = tempdir().expect("temp directory should exist");
+        let app_data_dir = dir.path().to_path_buf();
+
+        let custom = DecisionTemplate {
+            name: " grad_school ".into(),
+            description: "Go back to school?".into(),
+            options: vec![option("Enroll", ""), option("Keep working", "")],
+            variables: vec![variable("Tuition", "high")],
+        };
+        let saved = save_template(&app_data_dir, custom).expect("template should save");
+        assert_eq!(saved.name, "grad_school");
+        assert_eq!(load_templates(&app_data_dir).len(), builtin_templates().len() + 1);
+
+        let replacement = DecisionTemplate { options: vec![option("Enroll part-time", "")], ..saved };
+        save_template(&app_data_dir, replacement).expect("template should replace");
+        let found = find_template(&app_data_dir, "grad_school").unwrap();
+        assert_eq!(found.options, vec![option("Enroll part-time", "")]);
+        assert_eq!(load_templates(&app_data_dir).len(), builtin_templates().len() + 1);
+
+        let unnamed = DecisionTemplate { name: "  ".into(), ..found.clone() };
+        assert!(save_template(&app_data_dir, unnamed).is_err());
+
+        // A hand-edited file that no longer parses is reported, not overwritten
+        fs::write(templates_path(&app_data_dir), "[{ broken").unwrap();
+        let err = save_template(&app_data_dir, found).unwrap_err();
+        assert!(err.contains("not valid template JSON"), "unexpected error: {}", err);
+        assert_eq!(fs::read_to_string(templates_path(&app_data_dir)).unwrap(), "[{ broken");
+        assert_eq!(load_templates(&app_data_dir).len(), builtin_templates().len());
+    }
+}
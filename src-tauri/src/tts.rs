@@ -3,10 +3,12 @@
 
 use crate::agents::AgentInfo;
 use crate::config::AppConfig;
-use crate::db::DebateRound;
+use crate::db::{Database, DebateAudio, DebateRound};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 use tauri::Emitter;
 
 // ── Types ──
@@ -30,6 +32,50 @@ pub struct AudioManifest {
     pub total_duration_ms: u64,
 }
 
+/// When debate audio is produced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TtsMode {
+    /// Never generate audio automatically.
+    Off,
+    /// Generate each segment as soon as its round is saved.
+    Live,
+    /// Generate the whole debate in one pass once it completes.
+    Post,
+}
+
+impl TtsMode {
+    pub fn from_config_value(value: &str) -> Self {
+        match value {
+            "off" => TtsMode::Off,
+            "post" => TtsMode::Post,
+            _ => TtsMode::Live,
+        }
+    }
+}
+
+/// The API key for the configured TTS provider, or a user-facing error if it's missing.
+pub fn tts_api_key(config: &AppConfig) -> Result<String, String> {
+    match config.tts_provider.as_str() {
+        "openai" => {
+            if config.openrouter_api_key.is_empty() {
+                return Err("OpenRouter API key not set. Required for OpenAI TTS.".into());
+            }
+            Ok(config.openrouter_api_key.clone())
+        }
+        _ => {
+            if config.elevenlabs_api_key.is_empty() {
+                return Err("ElevenLabs API key not set. Go to Settings to add it.".into());
+            }
+            Ok(config.elevenlabs_api_key.clone())
+        }
+    }
+}
+
+/// Whether the configured TTS provider has the key it needs.
+pub fn has_tts_key(config: &AppConfig) -> bool {
+    tts_api_key(config).is_ok()
+}
+
 struct VoiceConfig {
     voice_id: String,
     stability: f32,
@@ -47,7 +93,7 @@ fn default_elevenlabs_voice(agent_key: &str, voice_gender: &str) -> VoiceConfig
     // - Lower stability → more expressive, varied intonation (less monotone/robotic)
     // - Higher style → more dramatic, emotionally responsive delivery
     // - similarity_boost stays moderate-high so voices remain distinct & clear
-    match agent_key {
+    let mut voice = match agent_key {
         "rationalist" => VoiceConfig {
             voice_id: "onwK4e9ZLuTAKqWW03F9".into(), // Daniel
             stability: 0.45, similarity_boost: 0.78, style: 0.55,
@@ -86,12 +132,64 @@ fn default_elevenlabs_voice(agent_key: &str, voice_gender: &str) -> VoiceConfig
                 }
             }
         }
+    };
+    // Built-in switched to the other gender: keep the persona tuning, swap the voice
+    if builtin_gender_flipped(agent_key, voice_gender) {
+        let counterpart = match agent_key {
+            "rationalist" => "MF3mGyEYCl7XYWbV9V6O", // Elli
+            "advocate" => "TxGEqnHWrfWFTfGW9XWX",    // Josh
+            "contrarian" => "AZnzlk1XvdvUeBnXmlld",  // Domi
+            "visionary" => "pNInz6obpgDQGcFmaJgB",   // Adam
+            "pragmatist" => "ThT5KcBeYPX3keUQqHPh",  // Dorothy
+            _ => "XrExE9yKIg1WjnnlVkGX",             // Matilda
+        };
+        voice.voice_id = counterpart.into();
     }
+    voice
+}
+
+/// Whether a built-in agent's stored `voice_gender` differs from the one it ships
+/// with, so its stock voice no longer fits. Always false for custom agents.
+fn builtin_gender_flipped(agent_key: &str, voice_gender: &str) -> bool {
+    crate::agents::builtin_agents()
+        .iter()
+        .find(|a| a.key == agent_key)
+        .is_some_and(|a| (a.voice_gender == "female") != (voice_gender == "female"))
+}
+
+/// Persona defaults with the user's overrides applied field by field: the voice id
+/// from `config.voices`, then anything set in `config.elevenlabs_voice_settings`.
+fn elevenlabs_voice_for(config: &AppConfig, agent_key: &str, voice_gender: &str) -> VoiceConfig {
+    let mut voice = default_elevenlabs_voice(agent_key, voice_gender);
+    if let Some(custom_id) = config.voices.get(agent_key) {
+        voice.voice_id = custom_id.clone();
+    }
+    if let Some(settings) = config.elevenlabs_voice_settings.get(agent_key) {
+        if let Some(voice_id) = settings.voice_id.as_ref().filter(|id| !id.trim().is_empty()) {
+            voice.voice_id = voice_id.clone();
+        }
+        voice.stability = settings.stability.unwrap_or(voice.stability);
+        voice.similarity_boost = settings.similarity_boost.unwrap_or(voice.similarity_boost);
+        voice.style = settings.style.unwrap_or(voice.style);
+    }
+    voice
 }
 
 /// OpenAI TTS voice names. 6 voices available: alloy, echo, fable, onyx, nova, shimmer.
 /// We assign unique voices per agent to make them distinguishable.
+/// Built-ins switched to the other gender get a counterpart voice; the moderator's
+/// neutral voice fits either.
 fn default_openai_voice(agent_key: &str, voice_gender: &str) -> &'static str {
+    if builtin_gender_flipped(agent_key, voice_gender) {
+        match agent_key {
+            "rationalist" => return "sage",
+            "advocate" => return "ash",
+            "contrarian" => return "coral",
+            "visionary" => return "echo",
+            "pragmatist" => return "shimmer",
+            _ => {}
+        }
+    }
     match agent_key {
         "rationalist" => "onyx",     // deep male
         "advocate"    => "nova",     // warm female
@@ -105,6 +203,23 @@ fn default_openai_voice(agent_key: &str, voice_gender: &str) -> &'static str {
     }
 }
 
+/// The OpenAI voice for an agent: a `config.voices` override, else the persona default.
+fn openai_voice_for<'a>(config: &'a AppConfig, agent_key: &str, voice_gender: &str) -> &'a str {
+    match config.voices.get(agent_key) {
+        Some(custom_voice) => custom_voice.as_str(),
+        None => default_openai_voice(agent_key, voice_gender),
+    }
+}
+
+/// The voice a clip for this agent would use with the configured provider:
+/// an OpenAI voice name, or an ElevenLabs voice id.
+pub fn resolved_voice(config: &AppConfig, agent_key: &str, voice_gender: &str) -> String {
+    match config.tts_provider.as_str() {
+        "openai" => openai_voice_for(config, agent_key, voice_gender).to_string(),
+        _ => elevenlabs_voice_for(config, agent_key, voice_gender).voice_id,
+    }
+}
+
 // ── TTS text preprocessing ──
 
 /// Prepare normalized debate text for TTS synthesis to sound more natural.
@@ -179,6 +294,15 @@ pub fn prepare_text_for_tts(text: &str, provider: &str) -> String {
     result.trim().to_string()
 }
 
+/// Open each segment with a short pause on providers that understand SSML breaks,
+/// so a new speaker doesn't start mid-breath.
+fn with_leading_pause(text: &str, provider: &str) -> String {
+    match provider {
+        "elevenlabs" => format!("<break time=\"0.3s\" /> {}", text),
+        _ => text.to_string(),
+    }
+}
+
 /// Capitalize the first character of a string.
 fn capitalize_first(s: &str) -> String {
     let mut chars = s.chars();
@@ -190,15 +314,44 @@ fn capitalize_first(s: &str) -> String {
 
 // ── Audio generation ──
 
+/// A failed TTS request. `status` is set when the provider answered with an HTTP error.
+#[derive(Debug, Clone, PartialEq)]
+struct TtsError {
+    status: Option<u16>,
+    message: String,
+}
+
+impl TtsError {
+    fn api(provider: &str, status: reqwest::StatusCode, body: &str) -> Self {
+        TtsError {
+            status: Some(status.as_u16()),
+            message: format!("{} API error ({}): {}", provider, status, body),
+        }
+    }
+
+    /// Rate limits and server errors are worth retrying; bad keys, bad requests
+    /// and network failures are not.
+    fn is_retryable(&self) -> bool {
+        matches!(self.status, Some(429) | Some(500..=599))
+    }
+}
+
+impl From<String> for TtsError {
+    fn from(message: String) -> Self {
+        TtsError { status: None, message }
+    }
+}
+
 /// Generate audio for a single segment via ElevenLabs API.
 async fn generate_elevenlabs(
+    client: &reqwest::Client,
+    timeout: Duration,
     api_key: &str,
     model_id: &str,
     voice_config: &VoiceConfig,
     text: &str,
     output_path: &Path,
-) -> Result<(), String> {
-    let client = reqwest::Client::new();
+) -> Result<(), TtsError> {
     let response = client
         .post(format!(
             "https://api.elevenlabs.io/v1/text-to-speech/{}",
@@ -206,6 +359,7 @@ async fn generate_elevenlabs(
         ))
         .header("xi-api-key", api_key)
         .header("Content-Type", "application/json")
+        .timeout(timeout)
         .json(&json!({
             "text": text,
             "model_id": model_id,
@@ -218,12 +372,12 @@ async fn generate_elevenlabs(
         }))
         .send()
         .await
-        .map_err(|e| format!("ElevenLabs request failed: {}", e))?;
+        .map_err(|e| crate::llm::request_error("ElevenLabs request failed", e))?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
-        return Err(format!("ElevenLabs API error ({}): {}", status, body));
+        return Err(TtsError::api("ElevenLabs", status, &body));
     }
 
     let bytes = response.bytes().await.map_err(|e| format!("Failed to read audio: {}", e))?;
@@ -233,16 +387,18 @@ async fn generate_elevenlabs(
 
 /// Generate audio for a single segment via OpenAI TTS API.
 async fn generate_openai(
+    client: &reqwest::Client,
+    timeout: Duration,
     api_key: &str,
     voice: &str,
     text: &str,
     output_path: &Path,
-) -> Result<(), String> {
-    let client = reqwest::Client::new();
+) -> Result<(), TtsError> {
     let response = client
         .post("https://api.openai.com/v1/audio/speech")
         .header("Authorization", format!("Bearer {}", api_key))
         .header("Content-Type", "application/json")
+        .timeout(timeout)
         .json(&json!({
             "model": "tts-1-hd",
             "input": text,
@@ -251,12 +407,12 @@ async fn generate_openai(
         }))
         .send()
         .await
-        .map_err(|e| format!("OpenAI TTS request failed: {}", e))?;
+        .map_err(|e| crate::llm::request_error("OpenAI TTS request failed", e))?;
 
     if !response.status().is_success() {
         let status = response.status();
         let body = response.text().await.unwrap_or_default();
-        return Err(format!("OpenAI TTS API error ({}): {}", status, body));
+        return Err(TtsError::api("OpenAI TTS", status, &body));
     }
 
     let bytes = response.bytes().await.map_err(|e| format!("Failed to read audio: {}", e))?;
@@ -264,6 +420,170 @@ async fn generate_openai(
     Ok(())
 }
 
+/// Delay before the first TTS retry; doubles on each further attempt.
+const TTS_RETRY_BASE_DELAY: Duration = Duration::from_secs(1);
+
+/// Run `request` up to `max_attempts` times, backing off exponentially from
+/// `base_delay` between attempts that failed with a retryable status. This is
+/// the only retry policy for TTS requests.
+async fn retry_tts_request<T, F, Fut>(max_attempts: u32, base_delay: Duration, mut request: F) -> Result<T, String>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T, TtsError>>,
+{
+    let max_attempts = max_attempts.max(1);
+    let mut delay = base_delay;
+    let mut attempt = 1;
+    loop {
+        match request().await {
+            Err(e) if attempt < max_attempts && e.is_retryable() => {
+                eprintln!("TTS attempt {}/{} failed, retrying in {:?}: {}", attempt, max_attempts, delay, e.message);
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+                attempt += 1;
+            }
+            result => return result.map_err(|e| e.message),
+        }
+    }
+}
+
+/// Synthesize one clip in an agent's voice: a per-agent override from
+/// `config.voices` wins, otherwise the provider default for that persona.
+async fn synthesize_agent_clip(
+    config: &AppConfig,
+    api_key: &str,
+    agent_key: &str,
+    voice_gender: &str,
+    tts_text: &str,
+    output_path: &Path,
+) -> Result<(), String> {
+    let client = crate::llm::http_client();
+    let timeout = Duration::from_secs(config.request_timeout_secs);
+    match config.tts_provider.as_str() {
+        "openai" => {
+            let voice = openai_voice_for(config, agent_key, voice_gender);
+            retry_tts_request(config.tts_retry_attempts, TTS_RETRY_BASE_DELAY, || {
+                generate_openai(client, timeout, api_key, voice, tts_text, output_path)
+            })
+            .await
+        }
+        _ => {
+            let elevenlabs_model = if config.elevenlabs_model.trim().is_empty() {
+                "eleven_flash_v2_5"
+            } else {
+                config.elevenlabs_model.trim()
+            };
+            let voice_config = elevenlabs_voice_for(config, agent_key, voice_gender);
+            retry_tts_request(config.tts_retry_attempts, TTS_RETRY_BASE_DELAY, || {
+                generate_elevenlabs(client, timeout, api_key, elevenlabs_model, &voice_config, tts_text, output_path)
+            })
+            .await
+        }
+    }
+}
+
+/// Previews older than this are removed the next time one is generated.
+const PREVIEW_MAX_AGE: std::time::Duration = std::time::Duration::from_secs(10 * 60);
+
+/// Check the ElevenLabs key against `GET /v1/user`.
+pub async fn check_elevenlabs_key(api_key: &str) -> crate::llm::ServiceHealth {
+    if api_key.trim().is_empty() {
+        return crate::llm::service_health("ElevenLabs", None);
+    }
+    let request = crate::llm::http_client()
+        .get("https://api.elevenlabs.io/v1/user")
+        .header("xi-api-key", api_key);
+    crate::llm::service_health("ElevenLabs", Some(crate::llm::probe_key(request).await))
+}
+
+/// Scratch directory for voice previews.
+fn preview_dir(app_data_dir: &Path) -> PathBuf {
+    app_data_dir.join("previews")
+}
+
+/// Delete preview clips older than `PREVIEW_MAX_AGE`.
+fn prune_previews(dir: &Path) {
+    let Ok(entries) = std::fs::read_dir(dir) else { return };
+    for entry in entries.flatten() {
+        let stale = entry
+            .metadata()
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age > PREVIEW_MAX_AGE);
+        if stale {
+            let _ = std::fs::remove_file(entry.path());
+        }
+    }
+}
+
+/// Generate a short sample in an agent's voice so users can audition voice
+/// assignments without running a debate. Returns the path of the MP3.
+pub async fn generate_voice_preview(
+    config: &AppConfig,
+    registry: &[AgentInfo],
+    app_data_dir: &Path,
+    agent_key: &str,
+    sample_text: Option<&str>,
+) -> Result<PathBuf, String> {
+    let api_key = tts_api_key(config)?;
+    let agent = registry
+        .iter()
+        .find(|a| a.key == agent_key)
+        .ok_or_else(|| format!("Unknown agent: {}", agent_key))?;
+
+    let text = match sample_text.map(str::trim).filter(|t| !t.is_empty()) {
+        Some(t) => t.to_string(),
+        None => format!("Hi, I'm the {}, here's my take.", agent.label),
+    };
+
+    let dir = preview_dir(app_data_dir);
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create preview dir: {}", e))?;
+    prune_previews(&dir);
+
+    let filename = format!("{}_{}.mp3", agent.key, chrono::Utc::now().format("%Y%m%dT%H%M%S%3f"));
+    let output_path = dir.join(filename);
+    let provider = config.tts_provider.as_str();
+    let tts_text = prepare_text_for_tts(&text, provider);
+    synthesize_agent_clip(config, &api_key, &agent.key, &agent.voice_gender, &tts_text, &output_path).await?;
+    Ok(output_path)
+}
+
+/// Generate one clip of caller-supplied text, for checking voice settings and
+/// provider connectivity. Uses the moderator's voice when no agent is given and
+/// writes to the system temp directory, naming the file after the agent and
+/// resolved voice. Returns the path of the MP3.
+pub async fn generate_test_clip(
+    config: &AppConfig,
+    registry: &[AgentInfo],
+    agent_key: Option<&str>,
+    text: &str,
+) -> Result<PathBuf, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("Test text cannot be empty".to_string());
+    }
+    let api_key = tts_api_key(config)?;
+    let agent_key = agent_key.map(str::trim).filter(|k| !k.is_empty()).unwrap_or("moderator");
+    let agent = registry
+        .iter()
+        .find(|a| a.key == agent_key)
+        .ok_or_else(|| format!("Unknown agent: {}", agent_key))?;
+
+    let dir = std::env::temp_dir().join("open-council-tts-test");
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    // Name the file after the voice it used, so a wrong voice is obvious at a glance
+    let voice: String = resolved_voice(config, &agent.key, &agent.voice_gender)
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-')
+        .collect();
+    let filename = format!("{}_{}_{}.mp3", agent.key, voice, chrono::Utc::now().format("%Y%m%dT%H%M%S%3f"));
+    let output_path = dir.join(filename);
+    let tts_text = prepare_text_for_tts(text, config.tts_provider.as_str());
+    synthesize_agent_clip(config, &api_key, &agent.key, &agent.voice_gender, &tts_text, &output_path).await?;
+    Ok(output_path)
+}
+
 /// Estimate MP3 duration from file size (assumes ~128kbps CBR, reasonable for speech).
 fn estimate_duration_ms(file_path: &Path) -> u64 {
     let bytes = std::fs::metadata(file_path).map(|m| m.len()).unwrap_or(0);
@@ -276,6 +596,103 @@ fn audio_dir(app_data_dir: &Path, decision_id: &str) -> PathBuf {
     app_data_dir.join("debates").join(decision_id)
 }
 
+/// Total size in bytes of the files under `dir`, recursively. Missing dirs count as 0.
+fn dir_size(dir: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(dir) else { return 0 };
+    entries
+        .flatten()
+        .map(|entry| match entry.metadata() {
+            Ok(meta) if meta.is_dir() => dir_size(&entry.path()),
+            Ok(meta) => meta.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+/// Disk used by one decision's narrated audio.
+pub fn debate_audio_size(app_data_dir: &Path, decision_id: &str) -> u64 {
+    dir_size(&audio_dir(app_data_dir, decision_id))
+}
+
+/// Disk used by narrated audio across all debates.
+pub fn total_audio_size(app_data_dir: &Path) -> u64 {
+    dir_size(&app_data_dir.join("debates"))
+}
+
+/// Delete one decision's audio directory. Returns whether it existed.
+pub fn remove_debate_audio_dir(app_data_dir: &Path, decision_id: &str) -> Result<bool, String> {
+    let dir = audio_dir(app_data_dir, decision_id);
+    if !dir.is_dir() {
+        return Ok(false);
+    }
+    std::fs::remove_dir_all(&dir).map_err(|e| format!("Failed to remove {}: {}", dir.display(), e))?;
+    Ok(true)
+}
+
+/// Audio directories under `debates/` whose decision no longer exists, sorted by id.
+pub fn find_orphaned_audio(app_data_dir: &Path, decision_ids: &HashSet<String>) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(app_data_dir.join("debates")) else {
+        return Vec::new();
+    };
+    let mut orphans: Vec<String> = entries
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|id| !decision_ids.contains(id))
+        .collect();
+    orphans.sort();
+    orphans
+}
+
+/// Delete every orphaned audio directory. Returns the bytes reclaimed.
+pub fn prune_orphaned_audio(app_data_dir: &Path, decision_ids: &HashSet<String>) -> Result<u64, String> {
+    let mut reclaimed = 0;
+    for id in find_orphaned_audio(app_data_dir, decision_ids) {
+        let size = debate_audio_size(app_data_dir, &id);
+        if remove_debate_audio_dir(app_data_dir, &id)? {
+            reclaimed += size;
+        }
+    }
+    Ok(reclaimed)
+}
+
+/// File name for a live segment's MP3, e.g. `003_rationalist_r2.mp3`.
+fn segment_filename(segment_index: usize, agent: &str, round_number: i32) -> String {
+    format!("{:03}_{}_r{}.mp3", segment_index + 1, agent, round_number)
+}
+
+/// Rebuild segments for rounds whose live audio was already written to disk.
+/// Used when resuming a debate so earlier audio is kept instead of regenerated.
+/// `rounds` must be in the order they were originally spoken.
+pub fn recover_live_segments(
+    app_data_dir: &Path,
+    decision_id: &str,
+    rounds: &[DebateRound],
+) -> Vec<AudioSegment> {
+    let dir = audio_dir(app_data_dir, decision_id);
+    rounds
+        .iter()
+        .enumerate()
+        .filter_map(|(index, round)| {
+            let filename = segment_filename(index, &round.agent, round.round_number);
+            let path = dir.join(&filename);
+            if !path.exists() {
+                return None;
+            }
+            Some(AudioSegment {
+                index,
+                agent: round.agent.clone(),
+                round: round.round_number,
+                exchange: round.exchange_number,
+                text: round.content.clone(),
+                audio_file: filename,
+                duration_ms: estimate_duration_ms(&path),
+                start_ms: 0,
+            })
+        })
+        .collect()
+}
+
 /// Generate TTS audio for a single debate segment (one agent's response).
 /// Designed to be called from a `tokio::spawn` context during live debate.
 pub async fn generate_segment_audio(
@@ -287,11 +704,6 @@ pub async fn generate_segment_audio(
     app_data_dir: &PathBuf,
 ) -> Result<AudioSegment, String> {
     let provider = &config.tts_provider;
-    let elevenlabs_model = if config.elevenlabs_model.trim().is_empty() {
-        "eleven_flash_v2_5"
-    } else {
-        config.elevenlabs_model.trim()
-    };
     let api_key = match provider.as_str() {
         "openai" => {
             if config.openrouter_api_key.is_empty() {
@@ -311,37 +723,16 @@ pub async fn generate_segment_audio(
     std::fs::create_dir_all(&out_dir)
         .map_err(|e| format!("Failed to create audio dir: {}", e))?;
 
-    let filename = format!(
-        "{:03}_{}_r{}.mp3",
-        segment_index + 1,
-        round.agent,
-        round.round_number
-    );
+    let filename = segment_filename(segment_index, &round.agent, round.round_number);
     let output_path = out_dir.join(&filename);
 
     let agent_info = registry.iter().find(|a| a.key == round.agent);
     let voice_gender = agent_info.map(|a| a.voice_gender.as_str()).unwrap_or("male");
 
     // Preprocess text for natural-sounding TTS (pauses, rhythm, emphasis)
-    let tts_text = prepare_text_for_tts(&round.content, provider);
+    let tts_text = with_leading_pause(&prepare_text_for_tts(&round.content, provider), provider);
 
-    match provider.as_str() {
-        "openai" => {
-            let voice = if let Some(custom_voice) = config.voices.get(&round.agent) {
-                custom_voice.as_str()
-            } else {
-                default_openai_voice(&round.agent, voice_gender)
-            };
-            generate_openai(&api_key, voice, &tts_text, &output_path).await?;
-        }
-        _ => {
-            let mut voice_config = default_elevenlabs_voice(&round.agent, voice_gender);
-            if let Some(custom_id) = config.voices.get(&round.agent) {
-                voice_config.voice_id = custom_id.clone();
-            }
-            generate_elevenlabs(&api_key, elevenlabs_model, &voice_config, &tts_text, &output_path).await?;
-        }
-    }
+    synthesize_agent_clip(config, &api_key, &round.agent, voice_gender, &tts_text, &output_path).await?;
 
     let duration_ms = estimate_duration_ms(&output_path);
 
@@ -358,14 +749,19 @@ pub async fn generate_segment_audio(
 }
 
 /// Build an AudioManifest from a collection of AudioSegments.
-/// Sorts by index and calculates cumulative start_ms for sequential playback.
+/// Sorts by index and calculates cumulative start_ms for sequential playback,
+/// leaving `gap_ms` of silence between consecutive segments.
 pub fn build_manifest_from_segments(
     decision_id: &str,
     mut segments: Vec<AudioSegment>,
+    gap_ms: u64,
 ) -> AudioManifest {
     segments.sort_by_key(|s| s.index);
     let mut cumulative_ms = 0u64;
-    for seg in &mut segments {
+    for (i, seg) in segments.iter_mut().enumerate() {
+        if i > 0 {
+            cumulative_ms += gap_ms;
+        }
         seg.start_ms = cumulative_ms;
         cumulative_ms += seg.duration_ms;
     }
@@ -376,9 +772,80 @@ pub fn build_manifest_from_segments(
     }
 }
 
+/// Live segments finished so far, kept next to the audio so a debate that crashes
+/// mid-way still has a playable manifest.
+const PARTIAL_MANIFEST_FILE: &str = "manifest.partial.json";
+
+/// Serializes read-modify-write of partial manifests across concurrent TTS tasks.
+static PARTIAL_MANIFEST_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// The partial manifest for a debate, if live segments have been recorded.
+pub fn load_partial_manifest(app_data_dir: &Path, decision_id: &str) -> Option<AudioManifest> {
+    let path = audio_dir(app_data_dir, decision_id).join(PARTIAL_MANIFEST_FILE);
+    let content = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Add one finished live segment to the debate's partial manifest (replacing a
+/// segment with the same index) and rewrite it. Returns the updated manifest.
+pub fn append_partial_manifest(
+    app_data_dir: &Path,
+    decision_id: &str,
+    segment: AudioSegment,
+    gap_ms: u64,
+) -> Result<AudioManifest, String> {
+    let _guard = PARTIAL_MANIFEST_LOCK.lock().map_err(|e| e.to_string())?;
+    let mut segments = load_partial_manifest(app_data_dir, decision_id)
+        .map(|m| m.segments)
+        .unwrap_or_default();
+    segments.retain(|s| s.index != segment.index);
+    segments.push(segment);
+    let manifest = build_manifest_from_segments(decision_id, segments, gap_ms);
+
+    let dir = audio_dir(app_data_dir, decision_id);
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let manifest_json = serde_json::to_string_pretty(&manifest).map_err(|e| e.to_string())?;
+    crate::config::write_atomic(&dir.join(PARTIAL_MANIFEST_FILE), manifest_json.as_bytes())?;
+    Ok(manifest)
+}
+
+/// Promote a completed debate's manifest: write `manifest.json` and drop the partial.
+pub fn promote_partial_manifest(app_data_dir: &Path, decision_id: &str, manifest_json: &str) -> Result<(), String> {
+    let _guard = PARTIAL_MANIFEST_LOCK.lock().map_err(|e| e.to_string())?;
+    let dir = audio_dir(app_data_dir, decision_id);
+    crate::config::write_atomic(&dir.join("manifest.json"), manifest_json.as_bytes())?;
+    let partial = dir.join(PARTIAL_MANIFEST_FILE);
+    if partial.exists() {
+        std::fs::remove_file(&partial).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Persist a finished debate's audio: write `manifest.json` (dropping any partial
+/// manifest left by live narration) and record it in the `debate_audio` table.
+pub fn persist_debate_audio(
+    db: &Database,
+    app_data_dir: &Path,
+    manifest: &AudioManifest,
+) -> Result<DebateAudio, String> {
+    let decision_id = &manifest.decision_id;
+    let manifest_json = serde_json::to_string_pretty(manifest).map_err(|e| e.to_string())?;
+    let dir = audio_dir(app_data_dir, decision_id);
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create audio dir: {}", e))?;
+    promote_partial_manifest(app_data_dir, decision_id, &manifest_json)
+        .map_err(|e| format!("Failed to write manifest: {}", e))?;
+    db.save_debate_audio(
+        decision_id,
+        &manifest_json,
+        manifest.total_duration_ms as i64,
+        &dir.to_string_lossy(),
+    )
+    .map_err(|e| e.to_string())
+}
+
 /// Generate TTS audio for an entire debate (bulk, post-debate).
 /// Takes pre-extracted rounds, config, and registry. Calls TTS for each segment,
-/// saves MP3 files, and returns a manifest. DB persistence is handled by the caller.
+/// saves MP3 files, and returns a manifest. The caller persists it with `persist_debate_audio`.
 pub async fn generate_debate_audio(
     app_handle: &tauri::AppHandle,
     decision_id: &str,
@@ -389,25 +856,7 @@ pub async fn generate_debate_audio(
 ) -> Result<AudioManifest, String> {
     // Determine provider and key
     let provider = &config.tts_provider;
-    let elevenlabs_model = if config.elevenlabs_model.trim().is_empty() {
-        "eleven_flash_v2_5"
-    } else {
-        config.elevenlabs_model.trim()
-    };
-    let api_key = match provider.as_str() {
-        "openai" => {
-            if config.openrouter_api_key.is_empty() {
-                return Err("OpenRouter API key not set. Required for OpenAI TTS.".into());
-            }
-            config.openrouter_api_key.clone()
-        }
-        _ => {
-            if config.elevenlabs_api_key.is_empty() {
-                return Err("ElevenLabs API key not set. Go to Settings to add it.".into());
-            }
-            config.elevenlabs_api_key.clone()
-        }
-    };
+    let api_key = tts_api_key(config)?;
 
     // Create audio output directory
     let out_dir = audio_dir(app_data_dir, decision_id);
@@ -438,29 +887,12 @@ pub async fn generate_debate_audio(
         }));
 
         // Preprocess text for natural-sounding TTS (pauses, rhythm, emphasis)
-        let tts_text = prepare_text_for_tts(&round.content, provider);
+        let tts_text = with_leading_pause(&prepare_text_for_tts(&round.content, provider), provider);
 
         // Generate audio via selected provider
-        match provider.as_str() {
-            "openai" => {
-                let voice = if let Some(custom_voice) = config.voices.get(&round.agent) {
-                    custom_voice.as_str()
-                } else {
-                    default_openai_voice(&round.agent, voice_gender)
-                };
-                generate_openai(&api_key, voice, &tts_text, &output_path).await?;
-            }
-            _ => {
-                let mut voice_config = default_elevenlabs_voice(&round.agent, voice_gender);
-                if let Some(custom_id) = config.voices.get(&round.agent) {
-                    voice_config.voice_id = custom_id.clone();
-                }
-                generate_elevenlabs(&api_key, elevenlabs_model, &voice_config, &tts_text, &output_path).await?;
-            }
-        }
+        synthesize_agent_clip(config, &api_key, &round.agent, voice_gender, &tts_text, &output_path).await?;
 
         let duration_ms = estimate_duration_ms(&output_path);
-        let start_ms = segments.last().map(|s: &AudioSegment| s.start_ms + s.duration_ms).unwrap_or(0);
 
         segments.push(AudioSegment {
             index: i,
@@ -470,28 +902,11 @@ pub async fn generate_debate_audio(
             text: round.content.clone(),
             audio_file: filename,
             duration_ms,
-            start_ms,
+            start_ms: 0, // Calculated when building final manifest
         });
     }
 
-    let total_duration_ms = segments.last().map(|s| s.start_ms + s.duration_ms).unwrap_or(0);
-
-    let manifest = AudioManifest {
-        decision_id: decision_id.to_string(),
-        segments,
-        total_duration_ms,
-    };
-
-    // Save manifest JSON file to disk
-    let manifest_json = serde_json::to_string_pretty(&manifest).map_err(|e| e.to_string())?;
-    std::fs::write(out_dir.join("manifest.json"), &manifest_json)
-        .map_err(|e| format!("Failed to write manifest: {}", e))?;
-
-    // Emit completion
-    let _ = app_handle.emit("audio-generation-complete", json!({
-        "decision_id": decision_id,
-        "manifest": manifest,
-    }));
+    let manifest = build_manifest_from_segments(decision_id, segments, config.segment_gap_ms);
 
     let _ = app_handle.emit("audio-generation-progress", json!({
         "decision_id": decision_id,
@@ -507,6 +922,227 @@ pub async fn generate_debate_audio(
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn unit_tts_retry_recovers_from_transient_errors_only() {
+        let calls = std::cell::Cell::new(0);
+        let result = retry_tts_request(3, Duration::ZERO, || {
+            calls.set(calls.get() + 1);
+            let attempt = calls.get();
+            async move {
+                match attempt {
+                    1 => Err(TtsError::api("ElevenLabs", reqwest::StatusCode::TOO_MANY_REQUESTS, "slow down")),
+                    2 => Err(TtsError::api("OpenAI TTS", reqwest::StatusCode::SERVICE_UNAVAILABLE, "busy")),
+                    _ => Ok("clip.mp3"),
+                }
+            }
+        })
+        .await;
+        assert_eq!(result, Ok("clip.mp3"));
+        assert_eq!(calls.get(), 3);
+
+        calls.set(0);
+        let result = retry_tts_request(3, Duration::ZERO, || {
+            calls.set(calls.get() + 1);
+            async { Err::<(), _>(TtsError::api("ElevenLabs", reqwest::StatusCode::UNAUTHORIZED, "invalid_api_key")) }
+        })
+        .await;
+        assert!(result.unwrap_err().contains("401"));
+        assert_eq!(calls.get(), 1, "auth failures are not retried");
+
+        calls.set(0);
+        let result = retry_tts_request(2, Duration::ZERO, || {
+            calls.set(calls.get() + 1);
+            async { Err::<(), _>(TtsError::api("ElevenLabs", reqwest::StatusCode::INTERNAL_SERVER_ERROR, "oops")) }
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls.get(), 2, "gives up after the configured attempts");
+
+        assert!(!TtsError::api("ElevenLabs", reqwest::StatusCode::BAD_REQUEST, "text too long").is_retryable());
+        // The status is typed, so a body that merely mentions a retryable code doesn't count
+        assert!(!TtsError::from("ElevenLabs request failed: API error (503) upstream".to_string()).is_retryable());
+        assert_eq!(
+            TtsError::api("OpenAI TTS", reqwest::StatusCode::TOO_MANY_REQUESTS, "quota").message,
+            "OpenAI TTS API error (429 Too Many Requests): quota"
+        );
+    }
+
+    #[test]
+    fn integration_debate_audio_sizes_sum_files_and_clear_removes_dir() {
+        let dir = tempfile::tempdir().expect("temp directory should exist");
+        let first = audio_dir(dir.path(), "decision-a");
+        let second = audio_dir(dir.path(), "decision-b");
+        std::fs::create_dir_all(first.join("segments")).unwrap();
+        std::fs::create_dir_all(&second).unwrap();
+        std::fs::write(first.join("001_rationalist_r1.mp3"), vec![0u8; 1000]).unwrap();
+        std::fs::write(first.join("segments").join("002_contrarian_r1.mp3"), vec![0u8; 500]).unwrap();
+        std::fs::write(second.join("001_advocate_r1.mp3"), vec![0u8; 250]).unwrap();
+
+        assert_eq!(debate_audio_size(dir.path(), "decision-a"), 1500);
+        assert_eq!(debate_audio_size(dir.path(), "decision-b"), 250);
+        assert_eq!(debate_audio_size(dir.path(), "missing"), 0);
+        assert_eq!(total_audio_size(dir.path()), 1750);
+
+        assert!(remove_debate_audio_dir(dir.path(), "decision-a").unwrap());
+        assert!(!first.exists());
+        assert!(!remove_debate_audio_dir(dir.path(), "decision-a").unwrap());
+        assert_eq!(total_audio_size(dir.path()), 250);
+    }
+
+    #[test]
+    fn integration_orphaned_audio_is_found_and_pruned() {
+        let dir = tempfile::tempdir().expect("temp directory should exist");
+        let db = crate::db::Database::new(":memory:").expect("in-memory database should initialize");
+        let conv = db.create_conversation_with_type("Move abroad?", "decision").unwrap();
+        let decision = db.create_decision(&conv.id, "Move abroad?").unwrap();
+
+        let kept = audio_dir(dir.path(), &decision.id);
+        let orphan = audio_dir(dir.path(), "deleted-decision");
+        std::fs::create_dir_all(&kept).unwrap();
+        std::fs::create_dir_all(&orphan).unwrap();
+        std::fs::write(kept.join("001_rationalist_r1.mp3"), vec![0u8; 300]).unwrap();
+        std::fs::write(orphan.join("001_advocate_r1.mp3"), vec![0u8; 700]).unwrap();
+        std::fs::write(dir.path().join("debates").join("stray.txt"), "not a debate").unwrap();
+
+        let ids = db.get_decision_ids().unwrap();
+        assert_eq!(find_orphaned_audio(dir.path(), &ids), vec!["deleted-decision".to_string()]);
+        assert_eq!(prune_orphaned_audio(dir.path(), &ids).unwrap(), 700);
+        assert!(!orphan.exists());
+        assert!(kept.exists());
+        assert!(find_orphaned_audio(dir.path(), &ids).is_empty());
+    }
+
+    #[test]
+    fn integration_partial_manifest_grows_as_segments_finish() {
+        let dir = tempfile::tempdir().expect("temp directory should exist");
+        let segment = |index: usize, agent: &str| AudioSegment {
+            index,
+            agent: agent.to_string(),
+            round: 1,
+            exchange: 1,
+            text: format!("{} speaks", agent),
+            audio_file: segment_filename(index, agent, 1),
+            duration_ms: 1000,
+            start_ms: 0,
+        };
+        assert!(load_partial_manifest(dir.path(), "d1").is_none());
+
+        // Segments can finish out of order
+        append_partial_manifest(dir.path(), "d1", segment(1, "advocate"), 300).unwrap();
+        let partial = load_partial_manifest(dir.path(), "d1").expect("partial should be on disk");
+        assert_eq!(partial.segments.len(), 1);
+
+        append_partial_manifest(dir.path(), "d1", segment(0, "rationalist"), 300).unwrap();
+        let partial = load_partial_manifest(dir.path(), "d1").unwrap();
+        let agents: Vec<&str> = partial.segments.iter().map(|s| s.agent.as_str()).collect();
+        assert_eq!(agents, vec!["rationalist", "advocate"]);
+        assert_eq!(partial.segments[1].start_ms, 1300);
+        assert_eq!(partial.total_duration_ms, 2300);
+
+        let manifest_json = serde_json::to_string(&partial).unwrap();
+        promote_partial_manifest(dir.path(), "d1", &manifest_json).unwrap();
+        assert!(load_partial_manifest(dir.path(), "d1").is_none());
+        assert!(audio_dir(dir.path(), "d1").join("manifest.json").exists());
+    }
+
+    #[test]
+    fn integration_persist_debate_audio_writes_manifest_and_db_row() {
+        let dir = tempfile::tempdir().expect("temp directory should exist");
+        let db = Database::new(":memory:").expect("in-memory database should initialize");
+        let conversation = db.create_conversation("Audio").unwrap();
+        let decision = db.create_decision(&conversation.id, "Audio").unwrap();
+        let segment = |index: usize, agent: &str| AudioSegment {
+            index,
+            agent: agent.to_string(),
+            round: 1,
+            exchange: 1,
+            text: format!("{} speaks", agent),
+            audio_file: segment_filename(index, agent, 1),
+            duration_ms: 1000,
+            start_ms: 0,
+        };
+
+        append_partial_manifest(dir.path(), &decision.id, segment(0, "rationalist"), 300).unwrap();
+        let manifest = build_manifest_from_segments(
+            &decision.id,
+            vec![segment(0, "rationalist"), segment(1, "advocate")],
+            300,
+        );
+        persist_debate_audio(&db, dir.path(), &manifest).unwrap();
+
+        let on_disk = std::fs::read_to_string(audio_dir(dir.path(), &decision.id).join("manifest.json")).unwrap();
+        let on_disk: AudioManifest = serde_json::from_str(&on_disk).unwrap();
+        assert_eq!(on_disk.segments.len(), 2);
+        assert_eq!(on_disk.total_duration_ms, manifest.total_duration_ms);
+        assert!(load_partial_manifest(dir.path(), &decision.id).is_none());
+
+        let row = db.get_debate_audio(&decision.id).unwrap().expect("audio row should be saved");
+        assert_eq!(row.total_duration_ms, manifest.total_duration_ms as i64);
+        assert_eq!(row.audio_dir, audio_dir(dir.path(), &decision.id).to_string_lossy());
+        let saved: AudioManifest = serde_json::from_str(&row.manifest_json).unwrap();
+        assert_eq!(saved.segments[1].agent, "advocate");
+    }
+
+    #[tokio::test]
+    async fn integration_voice_preview_errors_clearly_without_api_key() {
+        let dir = tempfile::tempdir().expect("temp directory should exist");
+        let registry = crate::agents::builtin_agents();
+
+        let elevenlabs = AppConfig::default();
+        let err = generate_voice_preview(&elevenlabs, &registry, dir.path(), "advocate", None)
+            .await
+            .unwrap_err();
+        assert_eq!(err, "ElevenLabs API key not set. Go to Settings to add it.");
+
+        let openai = AppConfig {
+            tts_provider: "openai".to_string(),
+            ..Default::default()
+        };
+        let err = generate_voice_preview(&openai, &registry, dir.path(), "advocate", Some("Hello"))
+            .await
+            .unwrap_err();
+        assert!(err.contains("OpenRouter API key not set"));
+        assert!(!preview_dir(dir.path()).exists(), "nothing should be written without a key");
+    }
+
+    #[tokio::test]
+    async fn integration_test_clip_errors_clearly_without_api_key() {
+        let registry = crate::agents::builtin_agents();
+        let err = generate_test_clip(&AppConfig::default(), &registry, None, "Testing, one two.")
+            .await
+            .unwrap_err();
+        assert_eq!(err, "ElevenLabs API key not set. Go to Settings to add it.");
+
+        let keyed = AppConfig {
+            elevenlabs_api_key: "sk-test".to_string(),
+            ..Default::default()
+        };
+        let err = generate_test_clip(&keyed, &registry, Some("ghost"), "Hello").await.unwrap_err();
+        assert_eq!(err, "Unknown agent: ghost");
+        let err = generate_test_clip(&keyed, &registry, None, "   ").await.unwrap_err();
+        assert_eq!(err, "Test text cannot be empty");
+    }
+
+    #[test]
+    fn unit_resolved_voice_follows_provider_and_overrides() {
+        let mut config = AppConfig {
+            tts_provider: "openai".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(resolved_voice(&config, "advocate", "female"), "nova");
+        assert_eq!(resolved_voice(&config, "moderator", "male"), "alloy");
+        config.voices.insert("advocate".to_string(), "fable".to_string());
+        assert_eq!(resolved_voice(&config, "advocate", "female"), "fable");
+
+        config.tts_provider = "elevenlabs".to_string();
+        config.voices.clear();
+        assert_eq!(resolved_voice(&config, "moderator", "male"), "2EiwWnXFnvU5JabPnv8n");
+        assert_eq!(
+            resolved_voice(&config, "contrarian", "male"),
+            default_elevenlabs_voice("contrarian", "male").voice_id
+        );
+    }
+
     #[test]
     fn unit_default_elevenlabs_voice_returns_config_for_builtins() {
         let config = default_elevenlabs_voice("rationalist", "male");
@@ -524,6 +1160,30 @@ mod tests {
         assert_ne!(male.voice_id, female.voice_id);
     }
 
+    #[test]
+    fn unit_partial_voice_settings_override_keeps_other_defaults() {
+        let defaults = default_elevenlabs_voice("contrarian", "male");
+        let mut config = AppConfig::default();
+        config.elevenlabs_voice_settings.insert(
+            "contrarian".to_string(),
+            crate::config::ElevenLabsVoiceSettings { stability: Some(0.6), ..Default::default() },
+        );
+
+        let voice = elevenlabs_voice_for(&config, "contrarian", "male");
+        assert_eq!(voice.stability, 0.6);
+        assert_eq!(voice.similarity_boost, defaults.similarity_boost);
+        assert_eq!(voice.style, defaults.style);
+        assert_eq!(voice.voice_id, defaults.voice_id);
+
+        config.voices.insert("contrarian".to_string(), "legacy-voice".to_string());
+        assert_eq!(elevenlabs_voice_for(&config, "contrarian", "male").voice_id, "legacy-voice");
+        config.elevenlabs_voice_settings.get_mut("contrarian").unwrap().voice_id = Some("tuned-voice".to_string());
+        assert_eq!(elevenlabs_voice_for(&config, "contrarian", "male").voice_id, "tuned-voice");
+
+        let untouched = elevenlabs_voice_for(&config, "rationalist", "male");
+        assert_eq!(untouched.stability, default_elevenlabs_voice("rationalist", "male").stability);
+    }
+
     #[test]
     fn unit_default_openai_voice_returns_voice_for_builtins() {
         assert_eq!(default_openai_voice("rationalist", "male"), "onyx");
@@ -531,12 +1191,48 @@ mod tests {
         assert_eq!(default_openai_voice("moderator", "male"), "alloy");
     }
 
+    #[test]
+    fn unit_flipping_builtin_voice_gender_changes_default_voice() {
+        let male = default_elevenlabs_voice("rationalist", "male");
+        let female = default_elevenlabs_voice("rationalist", "female");
+        assert_ne!(male.voice_id, female.voice_id);
+        assert_eq!(male.stability, female.stability, "persona tuning should carry over");
+        assert_ne!(
+            default_elevenlabs_voice("advocate", "male").voice_id,
+            default_elevenlabs_voice("advocate", "female").voice_id
+        );
+
+        assert_eq!(default_openai_voice("rationalist", "female"), "sage");
+        assert_eq!(default_openai_voice("advocate", "male"), "ash");
+        assert_eq!(default_openai_voice("moderator", "female"), "alloy");
+
+        // Explicit overrides still win
+        let mut config = AppConfig::default();
+        config.voices.insert("rationalist".to_string(), "chosen-voice".to_string());
+        assert_eq!(elevenlabs_voice_for(&config, "rationalist", "female").voice_id, "chosen-voice");
+    }
+
     #[test]
     fn unit_default_openai_voice_uses_gender_for_custom_agents() {
         assert_eq!(default_openai_voice("custom", "female"), "nova");
         assert_eq!(default_openai_voice("custom", "male"), "onyx");
     }
 
+    #[test]
+    fn unit_tts_api_key_errors_without_configured_key() {
+        let config = AppConfig::default();
+        let err = tts_api_key(&config).expect_err("missing ElevenLabs key should error");
+        assert!(err.contains("ElevenLabs API key not set"));
+        assert!(!has_tts_key(&config));
+
+        let openai = AppConfig { tts_provider: "openai".into(), ..AppConfig::default() };
+        let err = tts_api_key(&openai).expect_err("missing OpenRouter key should error");
+        assert!(err.contains("OpenRouter API key not set"));
+
+        let configured = AppConfig { elevenlabs_api_key: "el-key".into(), ..AppConfig::default() };
+        assert_eq!(tts_api_key(&configured).unwrap(), "el-key");
+    }
+
     #[test]
     fn unit_audio_manifest_serialization() {
         let manifest = AudioManifest {
@@ -582,7 +1278,7 @@ mod tests {
                 duration_ms: 4000, start_ms: 0,
             },
         ];
-        let manifest = build_manifest_from_segments("test-123", segments);
+        let manifest = build_manifest_from_segments("test-123", segments.clone(), 0);
         assert_eq!(manifest.segments.len(), 3);
         assert_eq!(manifest.segments[0].index, 0);
         assert_eq!(manifest.segments[0].start_ms, 0);
@@ -591,6 +1287,44 @@ mod tests {
         assert_eq!(manifest.segments[2].index, 2);
         assert_eq!(manifest.segments[2].start_ms, 9000);
         assert_eq!(manifest.total_duration_ms, 12000);
+
+        // With a gap, each later segment shifts by the cumulative pause
+        let gapped = build_manifest_from_segments("test-123", segments, 400);
+        assert_eq!(gapped.segments[0].start_ms, 0);
+        assert_eq!(gapped.segments[1].start_ms, 5400);
+        assert_eq!(gapped.segments[2].start_ms, 9800);
+        assert_eq!(gapped.total_duration_ms, 12800);
+    }
+
+    #[test]
+    fn unit_with_leading_pause_only_for_ssml_providers() {
+        assert!(with_leading_pause("Hello.", "elevenlabs").starts_with("<break time="));
+        assert_eq!(with_leading_pause("Hello.", "openai"), "Hello.");
+        assert_eq!(with_leading_pause("Hello.", "some-future-provider"), "Hello.");
+    }
+
+    #[test]
+    fn integration_recover_live_segments_keeps_existing_audio_files() {
+        let dir = tempfile::tempdir().expect("temp directory should exist");
+        let out_dir = audio_dir(dir.path(), "dec-1");
+        std::fs::create_dir_all(&out_dir).unwrap();
+        std::fs::write(out_dir.join("001_rationalist_r1.mp3"), vec![0u8; 16000]).unwrap();
+
+        let round = |agent: &str| DebateRound {
+            id: String::new(),
+            decision_id: "dec-1".into(),
+            round_number: 1,
+            exchange_number: 1,
+            agent: agent.into(),
+            content: format!("{} opening", agent),
+            created_at: String::new(),
+        };
+        let segments = recover_live_segments(dir.path(), "dec-1", &[round("rationalist"), round("advocate")]);
+
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].index, 0);
+        assert_eq!(segments[0].audio_file, "001_rationalist_r1.mp3");
+        assert_eq!(segments[0].duration_ms, 1000);
     }
 
     #[test]